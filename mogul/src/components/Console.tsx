@@ -5,7 +5,9 @@ import {
   consoleOut,
   type ConsoleItem,
   type ConsoleItemContext,
+  type ConsoleItemData,
   type ConsoleItemDelete,
+  type ConsoleItemImport,
   type ConsoleItemQueryDefined,
   type ConsoleItemQueryUndefined,
   type ConsoleItemReduce,
@@ -13,6 +15,8 @@ import {
   type ConsoleItemReduceLast,
   type ConsoleItemReduceTail,
   type ConsoleItemUnlambda,
+  type ConsoleItemMetrics,
+  type ConsoleItemTypeOf,
   type ConsoleItemUpdate,
   type ConsoleItemParseError,
   context,
@@ -21,6 +25,7 @@ import {
 import styles from "./Console.module.css";
 import { ReduceRow } from "./ReduceRow";
 import { renderExpr } from "~/service/unlambda";
+import { funcType } from "~/service/types";
 import { throttle } from "~/lib/throttle";
 
 interface ConsoleProps {
@@ -97,8 +102,16 @@ function ConsoleUnit(item: ConsoleItem): JSX.Element {
       return <ConsoleUnitQueryUndefined {...item} />;
     case "Context":
       return <ConsoleUnitContext {...item} />;
+    case "Import":
+      return <ConsoleUnitImport {...item} />;
+    case "Data":
+      return <ConsoleUnitData {...item} />;
     case "Unlambda":
       return <ConsoleUnitUnlambda {...item} />;
+    case "Metrics":
+      return <ConsoleUnitMetrics {...item} />;
+    case "TypeOf":
+      return <ConsoleUnitTypeOf {...item} />;
     case "ParseError":
       return <ConsoleUnitParseError {...item} />;
     default:
@@ -143,6 +156,7 @@ export function ConsoleUnitReduce(props: ConsoleItemReduce): JSX.Element {
           <ReduceRow
             expr={props.formed.expr}
             reducibleRange={props.formed.reducibleRange}
+            decoded={props.formed.decoded}
           />
         </code>
       </li>
@@ -154,6 +168,7 @@ export function ConsoleUnitReduce(props: ConsoleItemReduce): JSX.Element {
                 expr={result().formed.expr}
                 reducedRange={result().formed.reducedRange}
                 reducibleRange={result().formed.reducibleRange}
+                decoded={result().formed.decoded}
               />
             </code>
           </li>
@@ -173,6 +188,7 @@ export function ConsoleUnitReduceLast(
           <ReduceRow
             expr={props.formed.expr}
             reducibleRange={props.formed.reducibleRange}
+            decoded={props.formed.decoded}
           />
         </code>
       </li>
@@ -187,6 +203,7 @@ export function ConsoleUnitReduceLast(
                 expr={result().formed.expr}
                 reducedRange={result().formed.reducedRange}
                 reducibleRange={result().formed.reducibleRange}
+                decoded={result().formed.decoded}
               />
             </code>
           </li>
@@ -206,6 +223,7 @@ export function ConsoleUnitReduceHead(
           <ReduceRow
             expr={props.formed.expr}
             reducibleRange={props.formed.reducibleRange}
+            decoded={props.formed.decoded}
           />
         </code>
       </li>
@@ -217,6 +235,7 @@ export function ConsoleUnitReduceHead(
                 expr={result().formed.expr}
                 reducedRange={result().formed.reducedRange}
                 reducibleRange={result().formed.reducibleRange}
+                decoded={result().formed.decoded}
               />
             </code>
           </li>
@@ -236,6 +255,7 @@ export function ConsoleUnitReduceTail(
           <ReduceRow
             expr={props.formed.expr}
             reducibleRange={props.formed.reducibleRange}
+            decoded={props.formed.decoded}
           />
         </code>
       </li>
@@ -250,6 +270,7 @@ export function ConsoleUnitReduceTail(
                 expr={result().formed.expr}
                 reducedRange={result().formed.reducedRange}
                 reducibleRange={result().formed.reducibleRange}
+                decoded={result().formed.decoded}
               />
             </code>
           </li>
@@ -291,12 +312,58 @@ export function ConsoleUnitQueryUndefined(
 }
 
 function ConsoleUnitContext(_props: ConsoleItemContext): JSX.Element {
-  const funcs = sortFuncs(Object.values(context())).map((func) =>
-    renderFunc(func),
+  const funcs = sortFuncs(Object.values(context().funcs)).map((func) => [
+    ...renderFunc(func),
+    funcType(func[0]),
+  ]);
+
+  return (
+    <ul class={classNames(styles.unit, styles.unordered)}>
+      <For each={funcs}>
+        {([signature, body, type]) => {
+          return (
+            <li>
+              <code>{`${signature} = ${body}`}</code>
+              <Show when={type}>
+                <span class={styles.funcType}>{`  # ${type}`}</span>
+              </Show>
+            </li>
+          );
+        }}
+      </For>
+    </ul>
+  );
+}
+
+function ConsoleUnitImport(props: ConsoleItemImport): JSX.Element {
+  const funcs = sortFuncs(props.funcs).map((func) => renderFunc(func));
+
+  return (
+    <ul class={classNames(styles.unit, styles.unordered)}>
+      <li>
+        <code>{`import ${props.module}`}</code>
+      </li>
+      <For each={funcs}>
+        {([signature, body]) => {
+          return (
+            <li>
+              <code>{`${signature} = ${body}`}</code>
+            </li>
+          );
+        }}
+      </For>
+    </ul>
   );
+}
+
+function ConsoleUnitData(props: ConsoleItemData): JSX.Element {
+  const funcs = sortFuncs(props.funcs).map((func) => renderFunc(func));
 
   return (
     <ul class={classNames(styles.unit, styles.unordered)}>
+      <li>
+        <code>{`data ${props.name}`}</code>
+      </li>
       <For each={funcs}>
         {([signature, body]) => {
           return (
@@ -328,6 +395,56 @@ export function ConsoleUnitUnlambda(
   );
 }
 
+export function ConsoleUnitMetrics(
+  props: ConsoleItemMetrics & { displayStyle?: DisplayStyle },
+): JSX.Element {
+  const expr = renderExpr(props.comparison.expr, props.displayStyle);
+  const reports = props.comparison.reports.map((report) => {
+    const steps = report.steps.map((steps) => steps ?? "-").join(", ");
+    return {
+      strategy: report.strategy,
+      result: renderExpr(report.result, props.displayStyle),
+      metrics: `size ${report.size}, depth ${report.depth}, steps ${steps}`,
+    };
+  });
+
+  return (
+    <ul class={classNames(styles.unit, styles.unordered, styles.unlambda)}>
+      <li>
+        <code>{expr}</code>
+      </li>
+      <For each={reports}>
+        {(report) => (
+          <li>
+            <code>
+              <span class="expr-metrics">{`${report.strategy}: `}</span>
+              {report.result}
+              <span class="expr-metrics">{` (${report.metrics})`}</span>
+            </code>
+          </li>
+        )}
+      </For>
+    </ul>
+  );
+}
+
+export function ConsoleUnitTypeOf(
+  props: ConsoleItemTypeOf & { displayStyle?: DisplayStyle },
+): JSX.Element {
+  const expr = renderExpr(props.expr, props.displayStyle);
+
+  return (
+    <ul class={classNames(styles.unit, styles.unordered, styles.typeOf)}>
+      <li>
+        <code>{expr}</code>
+      </li>
+      <li>
+        <code>{props.inferred}</code>
+      </li>
+    </ul>
+  );
+}
+
 export function ConsoleUnitParseError(
   props: ConsoleItemParseError,
 ): JSX.Element {
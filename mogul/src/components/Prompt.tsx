@@ -1,6 +1,11 @@
 import classNames from "classnames";
 import { type JSX, createEffect, createSignal, splitProps } from "solid-js";
-import { type Command, parseCommand, runCommand } from "~/service/command";
+import {
+  type Command,
+  isParseError,
+  parseCommand,
+  runCommand,
+} from "~/service/command";
 import { randomSpell } from "~/service/randomSpell";
 import {
   addCommandHistory,
@@ -35,7 +40,7 @@ export default function Prompt(props: Props): JSX.Element {
       command = parseCommand(commandStr());
     } catch (err) {
       console.error(err);
-      if (err instanceof Error) showError(err);
+      if (isParseError(err) || err instanceof Error) showError(err);
       return;
     }
     console.info({ command });
@@ -1,4 +1,4 @@
-import { type JSX, onMount } from "solid-js";
+import { type JSX, Show, onMount } from "solid-js";
 import {
   type ExprRange,
   type ReducibleRange,
@@ -10,6 +10,7 @@ interface ReduceRowProps {
   expr: string;
   reducedRange?: ExprRange | null;
   reducibleRange?: ReducibleRange | null;
+  decoded?: string | null;
 }
 
 export function ReduceRow(props: ReduceRowProps): JSX.Element {
@@ -38,6 +39,9 @@ export function ReduceRow(props: ReduceRowProps): JSX.Element {
     <>
       <span class="expr-mark-reduced" ref={reducedRef} />
       <span class="expr-mark-reducible" ref={reducibleRef} />
+      <Show when={props.decoded}>
+        {(decoded) => <span class="expr-decoded"> = {decoded()}</span>}
+      </Show>
     </>
   );
 }
@@ -10,7 +10,7 @@ interface ContextProps {
 
 export default function Context(props: ContextProps): JSX.Element {
   const funcs = () =>
-    sortFuncs(Object.values(context())).map((func) =>
+    sortFuncs(Object.values(context().funcs)).map((func) =>
       renderFunc(func, props.displayStyle),
     );
 
@@ -1,10 +1,6 @@
 import { createSignal } from "solid-js";
 import { putConsoleItem } from "~/service/console";
-import {
-  deleteFunction,
-  queryFunction,
-  updateFunction,
-} from "~/service/context";
+import { runEngine } from "~/service/engine";
 import {
   type FormedReducedExpr,
   reduceHead,
@@ -13,20 +9,30 @@ import {
 } from "~/service/reduce";
 import { unlambda } from "~/service/unlambda";
 import { addAlias } from "~/service/aliases";
-import { type Command, parseCommand } from "../../../ski3/pkg/index";
-export { type Command, parseCommand };
+import { showError } from "~/service/error";
+import {
+  type Command,
+  type ParseError,
+  parseCommand,
+} from "../../../ski3/pkg/index";
+export { type Command, type ParseError, parseCommand };
+
+export function isParseError(err: unknown): err is ParseError {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    "charSpan" in err &&
+    "message" in err
+  );
+}
 
 export async function runCommand(command: Command) {
   switch (command.type) {
-    case "Delete": {
-      deleteFunction(command.identifier);
-      putConsoleItem({ type: "Delete", identifier: command.identifier });
-      return;
-    }
-
-    case "Update": {
-      updateFunction(command.func);
-      putConsoleItem({ type: "Update", func: command.func });
+    case "Delete":
+    case "Update":
+    case "Query":
+    case "Import": {
+      runInEngine(command);
       return;
     }
 
@@ -157,21 +163,13 @@ export async function runCommand(command: Command) {
       return;
     }
 
-    case "Query": {
-      const func = queryFunction(command.identifier);
-      if (func == null) {
-        putConsoleItem({
-          type: "QueryUndefined",
-          identifier: command.identifier,
-        });
-      } else {
-        putConsoleItem({ type: "QueryDefined", func });
-      }
+    case "Context": {
+      putConsoleItem({ type: "Context" });
       return;
     }
 
-    case "Context": {
-      putConsoleItem({ type: "Context" });
+    case "Data": {
+      runInEngine(command);
       return;
     }
 
@@ -181,5 +179,38 @@ export async function runCommand(command: Command) {
       addAlias(result);
       return;
     }
+
+    case "Metrics": {
+      runInEngine(command);
+      return;
+    }
+
+    case "TypeOf": {
+      runInEngine(command);
+      return;
+    }
+  }
+}
+
+// 定義の変更は Context::def や import の検査を通すため、ski3 の Engine で実行する
+// 結果を一度に返すコマンドも、tuber と同じ実装を使うため Engine で実行する
+function runInEngine(command: Command) {
+  try {
+    const output = runEngine(command);
+    switch (output.type) {
+      case "Delete":
+      case "Update":
+      case "QueryDefined":
+      case "QueryUndefined":
+      case "Import":
+      case "Data":
+      case "Metrics":
+      case "TypeOf":
+        putConsoleItem(output);
+        return;
+    }
+  } catch (err) {
+    if (!(err instanceof Error)) throw err;
+    showError(err);
   }
 }
@@ -0,0 +1,21 @@
+import { context, setContext, aliases, displayStyle } from "~/signals";
+import {
+  type Command,
+  type Output,
+  Engine,
+} from "../../../ski3/pkg/index";
+export type { Output };
+
+// ski3 の Engine でコマンドを実行し、書き換えられた Context を反映する
+// Context::def や import のエラーは例外として投げられる
+export function runEngine(command: Command): Output {
+  const engine = new Engine(context(), aliases());
+  try {
+    engine.displayStyle = displayStyle();
+    const output = engine.run(command);
+    setContext(engine.context);
+    return output;
+  } finally {
+    engine.free();
+  }
+}
@@ -1,5 +1,5 @@
 import { putConsoleItem } from "~/service/console";
 
-export function showError(error: Error) {
+export function showError(error: { readonly message: string }) {
   putConsoleItem({ type: "ParseError", message: error.message });
 }
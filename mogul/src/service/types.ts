@@ -0,0 +1,22 @@
+import {
+  type TypeError,
+  inferType as infer,
+} from "../../../ski3/pkg/index";
+import { context, displayStyle } from "~/signals";
+export { type TypeError };
+
+export function isTypeError(err: unknown): err is TypeError {
+  return (
+    typeof err === "object" && err !== null && "path" in err && "message" in err
+  );
+}
+
+// 型の付かない関数は null
+export function funcType(identifier: string): string | null {
+  try {
+    return infer(context(), { V: identifier }, displayStyle());
+  } catch (err) {
+    if (isTypeError(err)) return null;
+    throw err;
+  }
+}
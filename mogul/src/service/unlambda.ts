@@ -5,6 +5,8 @@ import {
   unlambdaRecursive,
   unlambdaRecursive_,
   unlambdaIota,
+  unlambdaTurner,
+  unlambdaKiselyov,
   renderExpr as render,
 } from "../../../ski3/pkg/index";
 import { context, displayStyle as getDisplayStyle } from "~/signals";
@@ -19,6 +21,10 @@ export function unlambda(level: number, expr: Expr): Expr {
       return unlambdaRecursive_(context(), expr);
     case 4:
       return unlambdaIota(context(), expr);
+    case 5:
+      return unlambdaTurner(context(), expr);
+    case 6:
+      return unlambdaKiselyov(context(), expr);
     default:
       throw new Error("Invalid level");
   }
@@ -4,6 +4,7 @@ import {
   type Context,
   type Aliases,
   type DisplayStyle,
+  type Comparison,
   type Expr,
   type FormedExpr,
   type FormedReducedExpr,
@@ -40,14 +41,16 @@ export const [context, setContext] = makePersisted(
   {
     name: "context",
     storage: sessionStorage,
-    serialize: (context: Context) => JSON.stringify(Object.values(context)),
     deserialize: (json: string) => {
-      const funcs: Func[] = JSON.parse(json);
-      const context: Context = {};
-      for (const func of funcs) {
-        context[func[0]] = func;
+      const saved: Context | Func[] = JSON.parse(json);
+      if (!Array.isArray(saved)) return saved;
+
+      // 以前の形式: 関数定義だけの配列 (import の情報は持たない)
+      const context: Context = { funcs: {}, modules: [], imported: {} };
+      for (const func of saved) {
+        context.funcs[func[0]] = func;
       }
-      return context
+      return context;
     },
   },
 );
@@ -183,12 +186,35 @@ export interface ConsoleItemContext {
   type: "Context";
 }
 
+export interface ConsoleItemImport {
+  type: "Import";
+  module: string;
+  funcs: Func[];
+}
+
+export interface ConsoleItemData {
+  type: "Data";
+  name: Identifier;
+  funcs: Func[];
+}
+
 export interface ConsoleItemUnlambda {
   type: "Unlambda";
   expr: Expr;
   result: Expr;
 }
 
+export interface ConsoleItemMetrics {
+  type: "Metrics";
+  comparison: Comparison;
+}
+
+export interface ConsoleItemTypeOf {
+  type: "TypeOf";
+  expr: Expr;
+  inferred: string;
+}
+
 export interface ConsoleItemParseError {
   type: "ParseError";
   message: string;
@@ -204,5 +230,9 @@ export type ConsoleItem =
   | ConsoleItemQueryDefined
   | ConsoleItemQueryUndefined
   | ConsoleItemContext
+  | ConsoleItemImport
+  | ConsoleItemData
   | ConsoleItemUnlambda
+  | ConsoleItemMetrics
+  | ConsoleItemTypeOf
   | ConsoleItemParseError;
@@ -0,0 +1,73 @@
+import type { Meta, StoryObj } from "storybook-solidjs";
+import { ConsoleUnitTypeOf } from "~/components/Console";
+
+const meta = {
+  component: ConsoleUnitTypeOf,
+  parameters: {
+    layout: "fullscreen",
+  },
+} satisfies Meta<typeof ConsoleUnitTypeOf>;
+
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const LazyK: Story = {
+  name: "Lazy_K style",
+  args: {
+    displayStyle: "LazyK",
+    expr: dummyExpr(),
+    inferred: "(a -> b -> c) -> b -> a -> c",
+  },
+};
+
+export const EcmaScript: Story = {
+  name: "ECMAScript style",
+  args: {
+    displayStyle: "EcmaScript",
+    expr: dummyExpr(),
+    inferred: "(a -> b -> c) -> b -> a -> c",
+  },
+};
+
+// ========================================================================== //
+
+function dummyExpr() {
+  return {
+    Lambda: {
+      param: "f",
+      body: {
+        Lambda: {
+          param: "x",
+          body: {
+            Lambda: {
+              param: "y",
+              body: {
+                Apply: {
+                  lhs: {
+                    Apply: {
+                      lhs: {
+                        Variable: {
+                          identifier: "f",
+                        },
+                      },
+                      rhs: {
+                        Variable: {
+                          identifier: "y",
+                        },
+                      },
+                    },
+                  },
+                  rhs: {
+                    Variable: {
+                      identifier: "x",
+                    },
+                  },
+                },
+              },
+            },
+          },
+        },
+      },
+    },
+  };
+}
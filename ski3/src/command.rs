@@ -1,4 +1,4 @@
-use crate::{expression::Expr, function::Func};
+use crate::{data_type::DataType, expression::Expr, function::Func, parse_error::ParseError};
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
 use wasm_bindgen::prelude::*;
@@ -15,7 +15,11 @@ pub enum Command {
     ReduceTail { count: usize, expr: Expr },
     Query { identifier: String },
     Context,
+    Import { module: String },
+    Data { data: DataType },
     Unlambda { level: u8, expr: Expr },
+    Metrics { expr: Expr },
+    TypeOf { expr: Expr },
 }
 
 impl From<tuber::Command> for Command {
@@ -45,10 +49,18 @@ impl From<tuber::Command> for Command {
                 identifier: id.as_ref().to_string(),
             },
             tuber::Command::Context => Command::Context,
+            tuber::Command::Import(module) => Command::Import { module },
+            tuber::Command::Data(data) => Command::Data { data: data.into() },
             tuber::Command::Unlambda(level, expr) => Command::Unlambda {
                 level,
                 expr: Expr::from(expr),
             },
+            tuber::Command::Metrics(expr) => Command::Metrics {
+                expr: Expr::from(expr),
+            },
+            tuber::Command::TypeOf(expr) => Command::TypeOf {
+                expr: Expr::from(expr),
+            },
         }
     }
 }
@@ -64,15 +76,19 @@ impl From<Command> for tuber::Command {
             Command::ReduceTail { count, expr } => tuber::Command::EvalTail(count, expr.into()),
             Command::Query { identifier } => tuber::Command::Query(identifier.into()),
             Command::Context => tuber::Command::Context,
+            Command::Import { module } => tuber::Command::Import(module),
+            Command::Data { data } => tuber::Command::Data(data.into()),
             Command::Unlambda { level, expr } => tuber::Command::Unlambda(level, expr.into()),
+            Command::Metrics { expr } => tuber::Command::Metrics(expr.into()),
+            Command::TypeOf { expr } => tuber::Command::TypeOf(expr.into()),
         }
     }
 }
 
 #[wasm_bindgen(js_name = parseCommand)]
-pub fn parse_command(input: &str) -> Result<Command, JsError> {
+pub fn parse_command(input: &str) -> Result<Command, ParseError> {
     match tuber::parse_command(input) {
         Ok(command) => Ok(command.into()),
-        Err(err) => Err(JsError::new(&err.to_string())),
+        Err(err) => Err(err.into()),
     }
 }
@@ -5,29 +5,57 @@ use std::collections::HashMap;
 use tsify_next::Tsify;
 use wasm_bindgen::prelude::*;
 
+/// 関数定義に加えて、読み込み済みのモジュールと、どの関数をどのモジュールから import したかを持つ
+///
+/// import した関数を上書きしようとした場合のエラーや `bool::TRUE` のような修飾した名前は、
+/// この情報を tuber::Context に戻して扱う
 #[derive(Tsify, Serialize, Deserialize)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
-pub struct Context(HashMap<Identifier, Func>);
+pub struct Context {
+    funcs: HashMap<Identifier, Func>,
+    modules: Vec<String>,
+    imported: HashMap<Identifier, String>,
+}
 
 impl From<tuber::Context> for Context {
     fn from(tuber_context: tuber::Context) -> Context {
-        Context(
-            tuber_context
+        let modules = tuber_context.module_names().map(String::from).collect();
+        let imported = tuber_context
+            .iter()
+            .filter_map(|(id, _)| {
+                let module = tuber_context.imported_from(id)?;
+                Some((id.as_str().into(), module.to_string()))
+            })
+            .collect();
+        Context {
+            funcs: tuber_context
                 .into_iter()
                 .map(|(id, func)| (id.as_str().into(), func.into()))
                 .collect::<HashMap<Identifier, Func>>(),
-        )
+            modules,
+            imported,
+        }
     }
 }
 
 impl From<Context> for tuber::Context {
     fn from(ski_context: Context) -> tuber::Context {
-        ski_context
-            .0
+        let mut context: tuber::Context = ski_context
+            .funcs
             .into_iter()
             .map(|(id, func)| (id.into(), func.into()))
             .collect::<HashMap<tuber::Identifier, tuber::Func>>()
-            .into()
+            .into();
+
+        // JS 側で不明なモジュール名に書き換えられていた場合は、関数定義だけを使う
+        let _ = context.restore_imports(
+            ski_context.modules,
+            ski_context
+                .imported
+                .into_iter()
+                .map(|(id, module)| (id.into(), module)),
+        );
+        context
     }
 }
 
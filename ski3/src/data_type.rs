@@ -0,0 +1,103 @@
+use crate::identifier::Identifier;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct DataType {
+    name: Identifier,
+    encoding: Encoding,
+    constructors: Vec<Constructor>,
+}
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct Constructor(/* name   */ Identifier, /* fields */ Vec<Field>);
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct Field(
+    /* name */ Identifier,
+    /* type */ Option<Identifier>,
+);
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum Encoding {
+    Church,
+    Scott,
+    Parigot,
+}
+
+impl From<tuber::DataType> for DataType {
+    fn from(tuber_data: tuber::DataType) -> DataType {
+        DataType {
+            name: tuber_data.name().to_string(),
+            encoding: tuber_data.encoding().into(),
+            constructors: tuber_data
+                .constructors()
+                .iter()
+                .map(|constructor| {
+                    Constructor(
+                        constructor.name().to_string(),
+                        constructor
+                            .fields()
+                            .iter()
+                            .map(|field| {
+                                Field(
+                                    field.name().to_string(),
+                                    field.ty().map(|ty| ty.to_string()),
+                                )
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<DataType> for tuber::DataType {
+    fn from(ski_data: DataType) -> tuber::DataType {
+        tuber::DataType::new(
+            ski_data.name.into(),
+            ski_data.encoding.into(),
+            ski_data
+                .constructors
+                .into_iter()
+                .map(|Constructor(name, fields)| {
+                    tuber::Constructor::new(
+                        name.into(),
+                        fields
+                            .into_iter()
+                            .map(|Field(name, ty)| {
+                                tuber::Field::new(name.into(), ty.map(Into::into))
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<Encoding> for tuber::Encoding {
+    fn from(ski_encoding: Encoding) -> tuber::Encoding {
+        match ski_encoding {
+            Encoding::Church => tuber::Encoding::Church,
+            Encoding::Scott => tuber::Encoding::Scott,
+            Encoding::Parigot => tuber::Encoding::Parigot,
+        }
+    }
+}
+
+impl From<tuber::Encoding> for Encoding {
+    fn from(tuber_encoding: tuber::Encoding) -> Encoding {
+        match tuber_encoding {
+            tuber::Encoding::Church => Encoding::Church,
+            tuber::Encoding::Scott => Encoding::Scott,
+            tuber::Encoding::Parigot => Encoding::Parigot,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::aliases::Aliases;
+use crate::command::Command;
+use crate::context::Context;
+use crate::display_style::DisplayStyle;
+use crate::expression::Expr;
+use crate::function::Func;
+use crate::metrics::Comparison;
+use crate::reducer::Termination;
+use crate::reduction_strategy::ReductionStrategy;
+use serde::Serialize;
+use tsify_next::Tsify;
+use tuber::Format;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct Engine {
+    engine: tuber::Engine,
+    display_style: tuber::DisplayStyle,
+}
+
+#[wasm_bindgen]
+impl Engine {
+    #[wasm_bindgen(constructor)]
+    pub fn new(context: Context, aliases: Aliases) -> Self {
+        Self {
+            engine: tuber::Engine::new(context.into(), aliases.into()),
+            display_style: tuber::DisplayStyle::EcmaScript,
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn context(&self) -> Context {
+        self.engine.context().clone().into()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn aliases(&self) -> Aliases {
+        self.engine.aliases().clone().into()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_strategy(&mut self, strategy: ReductionStrategy) {
+        self.engine.set_strategy(strategy.into());
+    }
+
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = maxSteps)]
+    pub fn set_max_steps(&mut self, maxSteps: usize) {
+        self.engine.set_max_steps(maxSteps);
+    }
+
+    /// エラーメッセージ中の式の表示形式
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = displayStyle)]
+    pub fn set_display_style(&mut self, displayStyle: DisplayStyle) {
+        self.display_style = displayStyle.into();
+    }
+
+    pub fn run(&mut self, command: Command) -> Result<Output, JsError> {
+        match self.engine.run(command.into()) {
+            Ok(output) => Ok(output.into()),
+            Err(err) => match err.downcast_ref::<tuber::TypeError>() {
+                Some(err) => Err(JsError::new(&err.format(&self.display_style))),
+                None => Err(JsError::new(&err.to_string())),
+            },
+        }
+    }
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(tag = "type")]
+pub enum Output {
+    Delete { identifier: String },
+    Update { func: Func },
+    Reduce { reduction: Reduction },
+    ReduceLast { reduction: Reduction },
+    ReduceHead { reduction: Reduction },
+    ReduceTail { reduction: Reduction },
+    QueryDefined { func: Func },
+    QueryUndefined { identifier: String },
+    Context { funcs: Vec<Func> },
+    Import { module: String, funcs: Vec<Func> },
+    Data { name: String, funcs: Vec<Func> },
+    Unlambda { expr: Expr, result: Expr },
+    Metrics { comparison: Comparison },
+    TypeOf { expr: Expr, inferred: String },
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct Reduction {
+    expr: Expr,
+    steps: Vec<ReductionStep>,
+    termination: Termination,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct ReductionStep {
+    step: usize,
+    expr: Expr,
+}
+
+impl From<tuber::Output> for Output {
+    fn from(tuber_output: tuber::Output) -> Output {
+        match tuber_output {
+            tuber::Output::Del(id) => Output::Delete {
+                identifier: id.as_ref().to_string(),
+            },
+            tuber::Output::Update(func) => Output::Update { func: func.into() },
+            tuber::Output::Eval(reduction) => Output::Reduce {
+                reduction: reduction.into(),
+            },
+            tuber::Output::EvalLast(reduction) => Output::ReduceLast {
+                reduction: reduction.into(),
+            },
+            tuber::Output::EvalHead(reduction) => Output::ReduceHead {
+                reduction: reduction.into(),
+            },
+            tuber::Output::EvalTail(reduction) => Output::ReduceTail {
+                reduction: reduction.into(),
+            },
+            tuber::Output::QueryDefined(func) => Output::QueryDefined { func: func.into() },
+            tuber::Output::QueryUndefined(id) => Output::QueryUndefined {
+                identifier: id.as_ref().to_string(),
+            },
+            tuber::Output::Context(funcs) => Output::Context {
+                funcs: funcs.into_iter().map(Func::from).collect(),
+            },
+            tuber::Output::Import { module, funcs } => Output::Import {
+                module,
+                funcs: funcs.into_iter().map(Func::from).collect(),
+            },
+            tuber::Output::Data { name, funcs } => Output::Data {
+                name: name.as_str().to_string(),
+                funcs: funcs.into_iter().map(Func::from).collect(),
+            },
+            tuber::Output::Unlambda { expr, result } => Output::Unlambda {
+                expr: expr.into(),
+                result: result.into(),
+            },
+            tuber::Output::Metrics(comparison) => Output::Metrics {
+                comparison: comparison.into(),
+            },
+            tuber::Output::TypeOf { expr, ty } => Output::TypeOf {
+                expr: expr.into(),
+                inferred: ty.to_string(),
+            },
+        }
+    }
+}
+
+impl From<tuber::Reduction> for Reduction {
+    fn from(tuber_reduction: tuber::Reduction) -> Reduction {
+        Reduction {
+            expr: tuber_reduction.expr.into(),
+            steps: tuber_reduction
+                .steps
+                .into_iter()
+                .map(|result| ReductionStep {
+                    step: result.step,
+                    expr: result.expr.into(),
+                })
+                .collect(),
+            termination: (&tuber_reduction.termination).into(),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::expression::Expr;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum LazyKSyntax {
+    Combinator,
+    Unlambda,
+    Iota,
+    Jot,
+}
+
+impl From<LazyKSyntax> for tuber::LazyKSyntax {
+    fn from(ski_syntax: LazyKSyntax) -> tuber::LazyKSyntax {
+        match ski_syntax {
+            LazyKSyntax::Combinator => tuber::LazyKSyntax::Combinator,
+            LazyKSyntax::Unlambda => tuber::LazyKSyntax::Unlambda,
+            LazyKSyntax::Iota => tuber::LazyKSyntax::Iota,
+            LazyKSyntax::Jot => tuber::LazyKSyntax::Jot,
+        }
+    }
+}
+
+/// s, k, i, ι だけからなる式を Lazy K のプログラムとして書き出す
+#[wasm_bindgen(js_name = exportLazyK)]
+pub fn export_lazy_k(expr: Expr, syntax: LazyKSyntax) -> Result<String, JsError> {
+    let tuber_expr = tuber::Expr::from(expr);
+    tuber::export(&tuber_expr, syntax.into()).map_err(|err| JsError::new(&err.to_string()))
+}
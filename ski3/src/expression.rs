@@ -1,5 +1,7 @@
 use crate::display_style::DisplayStyle;
 use crate::identifier::Identifier;
+use crate::parse_error::ParseError;
+use crate::types::Type;
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
 use tuber::{self, Format};
@@ -12,6 +14,10 @@ pub enum Expr {
     /* Symbol   */ S(Identifier),
     /* Apply    */ A(Box<Expr>, Box<Expr>),
     /* Lambda   */ L(Identifier, Box<Expr>),
+    /* Lambda with a type annotation */ LT(Identifier, Box<Type>, Box<Expr>),
+    /* TypeLambda */ TL(Identifier, Box<Expr>),
+    /* TypeApply  */ TA(Box<Expr>, Box<Type>),
+    /* Let (recursive, name, value, body) */ LE(bool, Identifier, Box<Expr>, Box<Expr>),
 }
 
 impl From<tuber::Expr> for Expr {
@@ -23,9 +29,37 @@ impl From<tuber::Expr> for Expr {
                 Box::new((*lhs).clone().into()),
                 Box::new((*rhs).clone().into()),
             ),
-            tuber::Expr::Lambda { param, body } => {
-                Expr::L(param.as_ref().to_string(), Box::new((*body).clone().into()))
+            tuber::Expr::Lambda {
+                param,
+                ty: None,
+                body,
+            } => Expr::L(param.as_ref().to_string(), Box::new((*body).clone().into())),
+            tuber::Expr::Lambda {
+                param,
+                ty: Some(ty),
+                body,
+            } => Expr::LT(
+                param.as_ref().to_string(),
+                Box::new((*ty).into()),
+                Box::new((*body).clone().into()),
+            ),
+            tuber::Expr::TypeLambda { param, body } => {
+                Expr::TL(param.as_ref().to_string(), Box::new((*body).clone().into()))
+            }
+            tuber::Expr::TypeApply { lhs, ty } => {
+                Expr::TA(Box::new((*lhs).clone().into()), Box::new((*ty).into()))
             }
+            tuber::Expr::Let {
+                recursive,
+                name,
+                value,
+                body,
+            } => Expr::LE(
+                recursive,
+                name.as_ref().to_string(),
+                Box::new((*value).into()),
+                Box::new((*body).into()),
+            ),
         }
     }
 }
@@ -41,6 +75,26 @@ impl From<Expr> for tuber::Expr {
             },
             Expr::L(param, body) => tuber::Expr::Lambda {
                 param: param.into(),
+                ty: None,
+                body: Box::new((*body).into()),
+            },
+            Expr::LT(param, ty, body) => tuber::Expr::Lambda {
+                param: param.into(),
+                ty: Some(Box::new((*ty).into())),
+                body: Box::new((*body).into()),
+            },
+            Expr::TL(param, body) => tuber::Expr::TypeLambda {
+                param: param.into(),
+                body: Box::new((*body).into()),
+            },
+            Expr::TA(lhs, ty) => tuber::Expr::TypeApply {
+                lhs: Box::new((*lhs).into()),
+                ty: Box::new((*ty).into()),
+            },
+            Expr::LE(recursive, name, value, body) => tuber::Expr::Let {
+                recursive,
+                name: name.into(),
+                value: Box::new((*value).into()),
                 body: Box::new((*body).into()),
             },
         }
@@ -48,10 +102,10 @@ impl From<Expr> for tuber::Expr {
 }
 
 #[wasm_bindgen(js_name = parseExpr)]
-pub fn parse_expr(input: &str) -> Result<Expr, JsError> {
+pub fn parse_expr(input: &str) -> Result<Expr, ParseError> {
     match tuber::parse_expr(input) {
         Ok(expr) => Ok(expr.into()),
-        Err(err) => Err(JsError::new(&err.to_string())),
+        Err(err) => Err(err.into()),
     }
 }
 
@@ -0,0 +1,140 @@
+use crate::aliases::Aliases;
+use crate::context::Context;
+use crate::display_style::DisplayStyle;
+use crate::expression::Expr;
+use crate::reducer::{
+    decode_expr, format_expr, FormedExpr, IteratorResult, ReduceResult, Termination,
+};
+use crate::reduction_strategy::ReductionStrategy;
+use crate::reduction_target::ReductionTarget;
+use wasm_bindgen::prelude::*;
+
+/// 簡約の経過を記録し、任意のステップに戻ったり別の評価戦略でやり直したりできる Reducer
+#[wasm_bindgen]
+pub struct ReductionHistory {
+    history: tuber::ReductionHistory,
+    display_style: tuber::DisplayStyle,
+}
+
+#[wasm_bindgen]
+impl ReductionHistory {
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        context: Context,
+        aliases: Aliases,
+        expr: Expr,
+        displayStyle: Option<DisplayStyle>,
+        strategy: Option<ReductionStrategy>,
+        target: Option<ReductionTarget>,
+    ) -> Self {
+        let tuber_strategy = strategy
+            .map(tuber::ReductionStrategy::from)
+            .unwrap_or_default();
+        let mut reducer =
+            tuber::Reducer::new(context.into(), aliases.into(), expr.into(), tuber_strategy);
+        if let Some(target) = target {
+            reducer.set_target(target.into());
+        }
+        let display_style = displayStyle
+            .map(tuber::DisplayStyle::from)
+            .unwrap_or(tuber::DisplayStyle::EcmaScript);
+        Self {
+            history: tuber::ReductionHistory::new(reducer),
+            display_style,
+        }
+    }
+
+    #[wasm_bindgen(getter = displayStyle)]
+    pub fn get_display_style(&self) -> DisplayStyle {
+        (&self.display_style).into()
+    }
+
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = displayStyle)]
+    pub fn set_display_style(&mut self, displayStyle: DisplayStyle) {
+        self.display_style = displayStyle.into();
+    }
+
+    /// 現在のステップ数
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> usize {
+        self.history.position()
+    }
+
+    /// 記録したステップ数
+    #[wasm_bindgen(getter = recordedSteps)]
+    pub fn recorded_steps(&self) -> usize {
+        self.history.recorded_steps()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn strategy(&self) -> ReductionStrategy {
+        self.history.reducer().strategy().into()
+    }
+
+    /// 現在の式が達している最も強い形
+    #[wasm_bindgen(getter)]
+    pub fn reached(&self) -> Option<ReductionTarget> {
+        self.history.reached().map(ReductionTarget::from)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn formed(&self) -> Result<FormedExpr, JsError> {
+        let reducible_path = self.history.reducible_path();
+        format_expr(
+            self.history.expr(),
+            &reducible_path,
+            &self.display_style,
+            decode_expr(self.history.reducer(), self.history.expr(), &reducible_path),
+        )
+    }
+
+    #[wasm_bindgen(getter = hasNext)]
+    pub fn has_next(&self) -> bool {
+        self.history.reducible_path().is_some() && self.history.termination().is_none()
+    }
+
+    #[wasm_bindgen(js_name = next)]
+    pub fn js_next(&mut self) -> Result<IteratorResult, JsError> {
+        let tuber_reduce_result = self.history.next();
+        let reducible_path = self.history.reducible_path();
+
+        let ski_reduce_result = match tuber_reduce_result {
+            Some(result) => {
+                let decoded = decode_expr(self.history.reducer(), &result.expr, &reducible_path);
+                Some(ReduceResult::new(
+                    result.step,
+                    result.expr,
+                    result.reduced_path,
+                    result.reached,
+                    &reducible_path,
+                    &self.display_style,
+                    decoded,
+                )?)
+            }
+            None => None,
+        };
+
+        Ok(IteratorResult {
+            done: ski_reduce_result.is_none(),
+            value: ski_reduce_result,
+            termination: self.history.termination().map(Termination::from),
+        })
+    }
+
+    /// 1ステップ戻る (最初の式にいれば false を返す)
+    pub fn back(&mut self) -> bool {
+        self.history.back()
+    }
+
+    /// step 番目の式に移動し、移動した先のステップ数を返す
+    pub fn seek(&mut self, step: usize) -> usize {
+        self.history.seek(step)
+    }
+
+    /// step 番目の式から別の評価戦略で簡約をやり直す
+    pub fn branch(&mut self, step: usize, strategy: ReductionStrategy) {
+        self.history.branch(step, strategy.into());
+    }
+}
@@ -0,0 +1,46 @@
+use crate::context::Context;
+use crate::expression::Expr;
+use serde::Serialize;
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+/// 式を Lazy K のプログラムとして、input を入力に実行する
+///
+/// 途中で失敗した場合も、それまでに得られた出力を返す
+#[allow(non_snake_case)]
+#[wasm_bindgen(js_name = runLazyK)]
+pub fn run_lazy_k(
+    context: Context,
+    program: Expr,
+    input: String,
+    maxSteps: Option<usize>,
+) -> LazyKOutput {
+    let mut lazy_k = tuber::LazyK::new(context.into(), program.into(), input.bytes());
+    lazy_k.set_max_steps(maxSteps);
+
+    let mut bytes = Vec::new();
+    let mut error = None;
+    for result in lazy_k.by_ref() {
+        match result {
+            Ok(byte) => bytes.push(byte),
+            Err(err) => error = Some(err.to_string()),
+        }
+    }
+
+    LazyKOutput {
+        output: String::from_utf8_lossy(&bytes).into_owned(),
+        exit_code: lazy_k.exit_code(),
+        steps: lazy_k.steps(),
+        error,
+    }
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct LazyKOutput {
+    output: String,
+    exit_code: Option<usize>,
+    steps: usize,
+    error: Option<String>,
+}
@@ -1,18 +1,42 @@
 mod aliases;
 mod command;
 mod context;
+mod data_type;
 mod display_style;
+mod engine;
+mod export;
 mod expression;
 mod function;
+mod history;
 mod identifier;
+mod lazy_k;
+mod metrics;
+mod parse_error;
 mod reducer;
+mod reduction_backend;
+mod reduction_strategy;
+mod reduction_target;
+mod types;
 mod unlambda;
+mod unlambda_trace;
 mod utils;
 
 pub use aliases::Aliases;
 pub use command::{parse_command, Command};
 pub use context::{default_context, Context};
+pub use data_type::{Constructor, DataType, Encoding, Field};
 pub use display_style::DisplayStyle;
+pub use engine::{Engine, Output};
+pub use export::{export_lazy_k, LazyKSyntax};
 pub use expression::{parse_expr, render_expr, Expr};
 pub use function::Func;
+pub use history::ReductionHistory;
+pub use lazy_k::{run_lazy_k, LazyKOutput};
+pub use metrics::Comparison;
+pub use parse_error::ParseError;
+pub use reduction_backend::ReductionBackend;
+pub use reduction_strategy::ReductionStrategy;
+pub use reduction_target::ReductionTarget;
+pub use types::{infer_type, Type, TypeError};
+pub use unlambda_trace::{UnlambdaStrategy, UnlambdaTrace};
 pub use utils::set_panic_hook;
@@ -0,0 +1,71 @@
+use crate::expression::Expr;
+use crate::unlambda_trace::UnlambdaStrategy;
+use serde::Serialize;
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+/// ラムダ抽象の除去の方法ごとに結果の式を比較した結果 (Engine の metrics コマンドの出力)
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct Comparison {
+    expr: Expr,
+    samples: Vec<Vec<Expr>>,
+    reports: Vec<StrategyMetrics>,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct StrategyMetrics {
+    strategy: UnlambdaStrategy,
+    result: Expr,
+    size: usize,
+    depth: usize,
+    combinators: Vec<CombinatorCount>,
+    steps: Vec<Option<usize>>,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct CombinatorCount {
+    name: String,
+    count: usize,
+}
+
+impl From<tuber::Comparison> for Comparison {
+    fn from(tuber_comparison: tuber::Comparison) -> Comparison {
+        Comparison {
+            expr: tuber_comparison.expr.into(),
+            samples: tuber_comparison
+                .samples
+                .into_iter()
+                .map(|args| args.into_iter().map(Expr::from).collect())
+                .collect(),
+            reports: tuber_comparison
+                .reports
+                .into_iter()
+                .map(StrategyMetrics::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<tuber::StrategyMetrics> for StrategyMetrics {
+    fn from(tuber_report: tuber::StrategyMetrics) -> StrategyMetrics {
+        StrategyMetrics {
+            strategy: (&tuber_report.strategy).into(),
+            result: tuber_report.result.into(),
+            size: tuber_report.metrics.size,
+            depth: tuber_report.metrics.depth,
+            combinators: tuber_report
+                .metrics
+                .combinators
+                .into_iter()
+                .map(|(id, count)| CombinatorCount {
+                    name: id.as_ref().to_string(),
+                    count,
+                })
+                .collect(),
+            steps: tuber_report.steps,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Serialize;
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseError {
+    message: String,
+    span: Span,
+    char_span: Span,
+    unexpected: Option<String>,
+    expected: Vec<String>,
+    grammars: Vec<Grammar>,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct Span {
+    start: usize,
+    end: usize,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub enum Grammar {
+    EcmaScript,
+    LazyK,
+    Combinator,
+    Iota,
+    Jot,
+}
+
+impl From<tuber::ParseError> for ParseError {
+    fn from(tuber_error: tuber::ParseError) -> ParseError {
+        ParseError {
+            message: tuber_error.to_string(),
+            span: tuber_error.span.into(),
+            char_span: tuber_error.char_span.into(),
+            unexpected: tuber_error.unexpected,
+            expected: tuber_error.expected,
+            grammars: tuber_error.grammars.into_iter().map(Grammar::from).collect(),
+        }
+    }
+}
+
+impl From<tuber::Span> for Span {
+    fn from(tuber_span: tuber::Span) -> Span {
+        Span {
+            start: tuber_span.start,
+            end: tuber_span.end,
+        }
+    }
+}
+
+impl From<tuber::Grammar> for Grammar {
+    fn from(tuber_grammar: tuber::Grammar) -> Grammar {
+        match tuber_grammar {
+            tuber::Grammar::EcmaScript => Grammar::EcmaScript,
+            tuber::Grammar::LazyK => Grammar::LazyK,
+            tuber::Grammar::Combinator => Grammar::Combinator,
+            tuber::Grammar::Iota => Grammar::Iota,
+            tuber::Grammar::Jot => Grammar::Jot,
+        }
+    }
+}
@@ -2,6 +2,9 @@ use crate::aliases::Aliases;
 use crate::context::Context;
 use crate::display_style::DisplayStyle;
 use crate::expression::Expr;
+use crate::reduction_backend::ReductionBackend;
+use crate::reduction_strategy::ReductionStrategy;
+use crate::reduction_target::ReductionTarget;
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
 use tuber::{self, ecmascript_format, lazy_k_format, Tag};
@@ -23,11 +26,20 @@ impl Reducer {
         aliases: Aliases,
         expr: Expr,
         displayStyle: Option<DisplayStyle>,
+        strategy: Option<ReductionStrategy>,
+        target: Option<ReductionTarget>,
     ) -> Self {
         let tuber_context = context.into();
         let tuber_aliases = aliases.into();
         let tuber_expr = expr.into();
-        let reducer = tuber::Reducer::new(tuber_context, tuber_aliases, tuber_expr);
+        let tuber_strategy = strategy
+            .map(tuber::ReductionStrategy::from)
+            .unwrap_or_default();
+        let mut reducer =
+            tuber::Reducer::new(tuber_context, tuber_aliases, tuber_expr, tuber_strategy);
+        if let Some(target) = target {
+            reducer.set_target(target.into());
+        }
         let display_style = displayStyle
             .map(tuber::DisplayStyle::from)
             .unwrap_or(tuber::DisplayStyle::EcmaScript);
@@ -52,12 +64,80 @@ impl Reducer {
     #[wasm_bindgen(getter)]
     pub fn formed(&self) -> Result<FormedExpr, JsError> {
         let expr = self.reducer.expr();
-        format_expr(&expr, &self.reducible_path, &self.display_style)
+        format_expr(
+            &expr,
+            &self.reducible_path,
+            &self.display_style,
+            decode_expr(&self.reducer, &expr, &self.reducible_path),
+        )
+    }
+
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = maxSteps)]
+    pub fn set_max_steps(&mut self, maxSteps: Option<usize>) {
+        self.reducer.set_max_steps(maxSteps);
+        self.reducible_path = self.reducer.reducible_path();
+    }
+
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = maxSize)]
+    pub fn set_max_size(&mut self, maxSize: Option<usize>) {
+        self.reducer.set_max_size(maxSize);
+        self.reducible_path = self.reducer.reducible_path();
+    }
+
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = detectCycles)]
+    pub fn set_detect_cycles(&mut self, detectCycles: bool) {
+        self.reducer.set_cycle_detection(detectCycles);
+        self.reducible_path = self.reducer.reducible_path();
     }
 
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = strongNormalization)]
+    pub fn set_strong_normalization(&mut self, strongNormalization: bool) {
+        self.reducer.set_strong_normalization(strongNormalization);
+        self.reducible_path = self.reducer.reducible_path();
+    }
+
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(setter = etaReduction)]
+    pub fn set_eta_reduction(&mut self, etaReduction: bool) {
+        self.reducer.set_eta_reduction(etaReduction);
+        self.reducible_path = self.reducer.reducible_path();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn target(&self) -> ReductionTarget {
+        self.reducer.target().into()
+    }
+
+    /// 現在の式が達している最も強い形
+    #[wasm_bindgen(getter)]
+    pub fn reached(&self) -> Option<ReductionTarget> {
+        self.reducer.reached().map(ReductionTarget::from)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn strategy(&self) -> ReductionStrategy {
+        self.reducer.strategy().into()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn backend(&self) -> ReductionBackend {
+        self.reducer.backend().into()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_backend(&mut self, backend: ReductionBackend) {
+        self.reducer.set_backend(backend.into());
+        self.reducible_path = self.reducer.reducible_path();
+    }
+
+    /// 簡約基が残っていても、上限や循環で簡約列が終了していれば false
     #[wasm_bindgen(getter = hasNext)]
     pub fn has_next(&self) -> bool {
-        self.reducible_path.is_some()
+        self.reducible_path.is_some() && self.reducer.termination().is_none()
     }
 
     #[wasm_bindgen(js_name = next)]
@@ -66,19 +146,25 @@ impl Reducer {
         self.reducible_path = self.reducer.reducible_path();
 
         let ski_reduce_result = match tuber_reduce_result {
-            Some(result) => Some(ReduceResult::new(
-                result.step,
-                result.expr.clone(),
-                result.reduced_path,
-                &self.reducible_path,
-                &self.display_style,
-            )?),
+            Some(result) => {
+                let decoded = decode_expr(&self.reducer, &result.expr, &self.reducible_path);
+                Some(ReduceResult::new(
+                    result.step,
+                    result.expr,
+                    result.reduced_path,
+                    result.reached,
+                    &self.reducible_path,
+                    &self.display_style,
+                    decoded,
+                )?)
+            }
             None => None,
         };
 
         Ok(IteratorResult {
             done: ski_reduce_result.is_none(),
             value: ski_reduce_result,
+            termination: self.reducer.termination().map(Termination::from),
         })
     }
 }
@@ -86,13 +172,51 @@ impl Reducer {
 #[derive(Tsify, Serialize)]
 #[tsify(into_wasm_abi)]
 pub struct IteratorResult {
-    done: bool,
-    value: Option<ReduceResult>,
+    pub(crate) done: bool,
+    pub(crate) value: Option<ReduceResult>,
+    pub(crate) termination: Option<Termination>,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum Termination {
+    NormalForm,
+    TargetReached { target: ReductionTarget },
+    StepLimit { max_steps: usize },
+    SizeLimit { step: usize, size: usize },
+    Cycle { step: usize, since: usize },
+}
+
+impl From<&tuber::Termination> for Termination {
+    fn from(tuber_termination: &tuber::Termination) -> Termination {
+        match *tuber_termination {
+            tuber::Termination::NormalForm => Termination::NormalForm,
+            tuber::Termination::TargetReached(target) => Termination::TargetReached {
+                target: target.into(),
+            },
+            tuber::Termination::StepLimit { max_steps } => Termination::StepLimit { max_steps },
+            tuber::Termination::SizeLimit { step, size } => Termination::SizeLimit { step, size },
+            tuber::Termination::Cycle { step, since } => Termination::Cycle { step, since },
+        }
+    }
 }
 
 #[derive(Tsify, Serialize, Deserialize)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
-struct ExprRange(std::ops::Range<usize>);
+pub(crate) struct ExprRange(std::ops::Range<usize>);
+
+// 簡約が終わった式だけを読み取る (読み取りのために式を何度も簡約するので、途中の式では行わない)
+pub(crate) fn decode_expr(
+    reducer: &tuber::Reducer,
+    expr: &tuber::Expr,
+    reducible_path: &Option<tuber::Path>,
+) -> Option<String> {
+    if reducible_path.is_some() {
+        return None;
+    }
+    tuber::decode(reducer.context(), reducer.aliases(), expr).map(|decoded| decoded.to_string())
+}
 
 // ========================================================================== //
 
@@ -102,12 +226,14 @@ struct ExprRange(std::ops::Range<usize>);
 pub struct FormedExpr {
     expr: String,
     reducible_range: Option<ReducibleRange>,
+    decoded: Option<String>,
 }
 
-fn format_expr(
+pub(crate) fn format_expr(
     expr: &tuber::Expr,
     reducible_path: &Option<tuber::Path>,
     display_style: &tuber::DisplayStyle,
+    decoded: Option<String>,
 ) -> Result<FormedExpr, JsError> {
     let mut paths: Vec<&tuber::Path> = Vec::new();
     if let Some(reducible_path) = reducible_path {
@@ -130,6 +256,7 @@ fn format_expr(
     Ok(FormedExpr {
         expr: formed.expr,
         reducible_range,
+        decoded,
     })
 }
 
@@ -138,25 +265,30 @@ fn format_expr(
 #[derive(Tsify, Serialize)]
 #[tsify(into_wasm_abi)]
 #[serde(rename_all = "camelCase")]
-struct ReduceResult {
+pub(crate) struct ReduceResult {
     step: usize,
     expr: Expr,
     formed: FormedReducedExpr,
+    reached: Option<ReductionTarget>,
 }
 
 impl ReduceResult {
-    fn new(
+    pub(crate) fn new(
         step: usize,
         expr: tuber::Expr,
         reduced_path: tuber::Path,
+        reached: Option<tuber::ReductionTarget>,
         reducible_path: &Option<tuber::Path>,
         display_style: &tuber::DisplayStyle,
+        decoded: Option<String>,
     ) -> Result<Self, JsError> {
-        let formed = format_reduced_expr(&expr, &reduced_path, reducible_path, display_style)?;
+        let formed =
+            format_reduced_expr(&expr, &reduced_path, reducible_path, display_style, decoded)?;
         Ok(Self {
             step,
             expr: expr.into(),
             formed,
+            reached: reached.map(ReductionTarget::from),
         })
     }
 }
@@ -168,6 +300,7 @@ struct FormedReducedExpr {
     expr: String,
     reduced_range: ExprRange,
     reducible_range: Option<ReducibleRange>,
+    decoded: Option<String>,
 }
 
 impl From<std::ops::Range<usize>> for ExprRange {
@@ -195,6 +328,7 @@ fn format_reduced_expr(
     reduced_path: &tuber::Path,
     reducible_path: &Option<tuber::Path>,
     display_style: &tuber::DisplayStyle,
+    decoded: Option<String>,
 ) -> Result<FormedReducedExpr, JsError> {
     let mut paths = vec![reduced_path];
     if let Some(reducible_path) = reducible_path {
@@ -219,10 +353,14 @@ fn format_reduced_expr(
         expr: formed.expr,
         reduced_range,
         reducible_range,
+        decoded,
     })
 }
 
-fn reduced_path_to_range(mapping: &[Tag], path: &tuber::Path) -> Result<ExprRange, JsError> {
+pub(crate) fn reduced_path_to_range(
+    mapping: &[Tag],
+    path: &tuber::Path,
+) -> Result<ExprRange, JsError> {
     match path.range(mapping) {
         Some(range) => Ok(range.into()),
         None => Err(JsError::new("InvalidRange")),
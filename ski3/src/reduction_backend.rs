@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum ReductionBackend {
+    Tree,
+    Graph,
+}
+
+impl From<ReductionBackend> for tuber::ReductionBackend {
+    fn from(ski_backend: ReductionBackend) -> tuber::ReductionBackend {
+        match ski_backend {
+            ReductionBackend::Tree => tuber::ReductionBackend::Tree,
+            ReductionBackend::Graph => tuber::ReductionBackend::Graph,
+        }
+    }
+}
+
+impl From<tuber::ReductionBackend> for ReductionBackend {
+    fn from(tuber_backend: tuber::ReductionBackend) -> ReductionBackend {
+        match tuber_backend {
+            tuber::ReductionBackend::Tree => ReductionBackend::Tree,
+            tuber::ReductionBackend::Graph => ReductionBackend::Graph,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum ReductionStrategy {
+    NormalOrder,
+    ApplicativeOrder,
+    CallByName,
+    CallByValue,
+}
+
+impl From<ReductionStrategy> for tuber::ReductionStrategy {
+    fn from(ski_strategy: ReductionStrategy) -> tuber::ReductionStrategy {
+        match ski_strategy {
+            ReductionStrategy::NormalOrder => tuber::ReductionStrategy::NormalOrder,
+            ReductionStrategy::ApplicativeOrder => tuber::ReductionStrategy::ApplicativeOrder,
+            ReductionStrategy::CallByName => tuber::ReductionStrategy::CallByName,
+            ReductionStrategy::CallByValue => tuber::ReductionStrategy::CallByValue,
+        }
+    }
+}
+
+impl From<tuber::ReductionStrategy> for ReductionStrategy {
+    fn from(tuber_strategy: tuber::ReductionStrategy) -> ReductionStrategy {
+        match tuber_strategy {
+            tuber::ReductionStrategy::NormalOrder => ReductionStrategy::NormalOrder,
+            tuber::ReductionStrategy::ApplicativeOrder => ReductionStrategy::ApplicativeOrder,
+            tuber::ReductionStrategy::CallByName => ReductionStrategy::CallByName,
+            tuber::ReductionStrategy::CallByValue => ReductionStrategy::CallByValue,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum ReductionTarget {
+    WeakHeadNormalForm,
+    HeadNormalForm,
+    NormalForm,
+}
+
+impl From<ReductionTarget> for tuber::ReductionTarget {
+    fn from(ski_target: ReductionTarget) -> tuber::ReductionTarget {
+        match ski_target {
+            ReductionTarget::WeakHeadNormalForm => tuber::ReductionTarget::WeakHeadNormalForm,
+            ReductionTarget::HeadNormalForm => tuber::ReductionTarget::HeadNormalForm,
+            ReductionTarget::NormalForm => tuber::ReductionTarget::NormalForm,
+        }
+    }
+}
+
+impl From<tuber::ReductionTarget> for ReductionTarget {
+    fn from(tuber_target: tuber::ReductionTarget) -> ReductionTarget {
+        match tuber_target {
+            tuber::ReductionTarget::WeakHeadNormalForm => ReductionTarget::WeakHeadNormalForm,
+            tuber::ReductionTarget::HeadNormalForm => ReductionTarget::HeadNormalForm,
+            tuber::ReductionTarget::NormalForm => ReductionTarget::NormalForm,
+        }
+    }
+}
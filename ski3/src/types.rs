@@ -0,0 +1,104 @@
+use crate::context::Context;
+use crate::display_style::DisplayStyle;
+use crate::expression::Expr;
+use crate::identifier::Identifier;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use tuber::Format;
+use wasm_bindgen::prelude::*;
+
+/// 式の主要型を推論し、`a -> b -> a` のような文字列で返す
+///
+/// 型が付かない場合のメッセージ中の部分式は displayStyle の文法で表示する
+#[allow(non_snake_case)]
+#[wasm_bindgen(js_name = inferType)]
+pub fn infer_type(
+    context: Context,
+    expr: Expr,
+    displayStyle: Option<DisplayStyle>,
+) -> Result<String, TypeError> {
+    let tuber_context = tuber::Context::from(context);
+    let tuber_expr = tuber::Expr::from(expr);
+    let display_style = displayStyle
+        .map(tuber::DisplayStyle::from)
+        .unwrap_or(tuber::DisplayStyle::EcmaScript);
+    tuber::infer_type(&tuber_context, &tuber_expr)
+        .map(|ty| ty.to_string())
+        .map_err(|err| TypeError::new(&err, &display_style))
+}
+
+#[derive(Tsify, Serialize, Deserialize, Debug)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum Type {
+    /* Var    */ V(usize),
+    /* Named  */ N(Identifier),
+    /* Arrow  */ A(Box<Type>, Box<Type>),
+    /* Forall */ F(Identifier, Box<Type>),
+}
+
+impl From<tuber::Type> for Type {
+    fn from(tuber_type: tuber::Type) -> Type {
+        match tuber_type {
+            tuber::Type::Var(n) => Type::V(n),
+            tuber::Type::Named(name) => Type::N(name.as_ref().to_string()),
+            tuber::Type::Arrow(param, result) => {
+                Type::A(Box::new((*param).into()), Box::new((*result).into()))
+            }
+            tuber::Type::Forall(param, body) => {
+                Type::F(param.as_ref().to_string(), Box::new((*body).into()))
+            }
+        }
+    }
+}
+
+impl From<Type> for tuber::Type {
+    fn from(ski_type: Type) -> tuber::Type {
+        match ski_type {
+            Type::V(n) => tuber::Type::Var(n),
+            Type::N(name) => tuber::Type::Named(name.into()),
+            Type::A(param, result) => tuber::Type::arrow((*param).into(), (*result).into()),
+            Type::F(param, body) => tuber::Type::forall(param, (*body).into()),
+        }
+    }
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct TypeError {
+    message: String,
+
+    /// 型の付かなかった部分式の位置
+    path: Vec<Direction>,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub enum Direction {
+    Lhs,
+    Rhs,
+    Body,
+    Value,
+}
+
+impl TypeError {
+    pub(crate) fn new(
+        tuber_error: &tuber::TypeError,
+        display_style: &tuber::DisplayStyle,
+    ) -> TypeError {
+        TypeError {
+            message: tuber_error.format(display_style),
+            path: tuber_error.path().iter().map(Direction::from).collect(),
+        }
+    }
+}
+
+impl From<&tuber::Direction> for Direction {
+    fn from(tuber_direction: &tuber::Direction) -> Direction {
+        match tuber_direction {
+            tuber::Direction::Lhs => Direction::Lhs,
+            tuber::Direction::Rhs => Direction::Rhs,
+            tuber::Direction::Body => Direction::Body,
+            tuber::Direction::Value => Direction::Value,
+        }
+    }
+}
@@ -28,3 +28,17 @@ pub fn unlambda_iota(context: Context, expr: Expr) -> Expr {
     let tuber_expr = tuber::Expr::from(expr);
     tuber::unlambda_iota(&tuber_context, tuber_expr).into()
 }
+
+#[wasm_bindgen(js_name = unlambdaTurner)]
+pub fn unlambda_turner(context: Context, expr: Expr) -> Expr {
+    let tuber_context = tuber::Context::from(context);
+    let tuber_expr = tuber::Expr::from(expr);
+    tuber::unlambda_turner(&tuber_context, tuber_expr).into()
+}
+
+#[wasm_bindgen(js_name = unlambdaKiselyov)]
+pub fn unlambda_kiselyov(context: Context, expr: Expr) -> Expr {
+    let tuber_context = tuber::Context::from(context);
+    let tuber_expr = tuber::Expr::from(expr);
+    tuber::unlambda_kiselyov(&tuber_context, tuber_expr).into()
+}
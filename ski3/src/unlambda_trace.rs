@@ -0,0 +1,152 @@
+use crate::context::Context;
+use crate::display_style::DisplayStyle;
+use crate::expression::Expr;
+use crate::reducer::{reduced_path_to_range, ExprRange};
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use tuber::{self, ecmascript_format, lazy_k_format};
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum UnlambdaStrategy {
+    SKI,
+    SK,
+    Iota,
+    Turner,
+    Kiselyov,
+}
+
+impl From<UnlambdaStrategy> for tuber::RecursiveStrategy {
+    fn from(ski_strategy: UnlambdaStrategy) -> tuber::RecursiveStrategy {
+        match ski_strategy {
+            UnlambdaStrategy::SKI => tuber::RecursiveStrategy::SKI,
+            UnlambdaStrategy::SK => tuber::RecursiveStrategy::SK,
+            UnlambdaStrategy::Iota => tuber::RecursiveStrategy::Iota,
+            UnlambdaStrategy::Turner => tuber::RecursiveStrategy::Turner,
+            UnlambdaStrategy::Kiselyov => tuber::RecursiveStrategy::Kiselyov,
+        }
+    }
+}
+
+impl From<&tuber::RecursiveStrategy> for UnlambdaStrategy {
+    fn from(tuber_strategy: &tuber::RecursiveStrategy) -> UnlambdaStrategy {
+        match tuber_strategy {
+            tuber::RecursiveStrategy::SKI | tuber::RecursiveStrategy::SKIWith { .. } => {
+                UnlambdaStrategy::SKI
+            }
+            tuber::RecursiveStrategy::SK | tuber::RecursiveStrategy::SKWith { .. } => {
+                UnlambdaStrategy::SK
+            }
+            tuber::RecursiveStrategy::Iota | tuber::RecursiveStrategy::IotaWith { .. } => {
+                UnlambdaStrategy::Iota
+            }
+            tuber::RecursiveStrategy::Turner => UnlambdaStrategy::Turner,
+            tuber::RecursiveStrategy::Kiselyov => UnlambdaStrategy::Kiselyov,
+        }
+    }
+}
+
+/// ラムダ抽象の除去を1ステップずつ行う
+///
+/// Turner と Kiselyov の戦略には対応しておらず、コンストラクタがエラーを返す
+#[wasm_bindgen]
+pub struct UnlambdaTrace {
+    trace: tuber::UnlambdaTrace,
+    display_style: tuber::DisplayStyle,
+}
+
+#[wasm_bindgen]
+impl UnlambdaTrace {
+    #[allow(non_snake_case)]
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        context: Context,
+        expr: Expr,
+        strategy: Option<UnlambdaStrategy>,
+        displayStyle: Option<DisplayStyle>,
+    ) -> Result<UnlambdaTrace, JsError> {
+        let tuber_strategy = strategy
+            .map(tuber::RecursiveStrategy::from)
+            .unwrap_or(tuber::RecursiveStrategy::SKI);
+        let trace = tuber::UnlambdaTrace::new(context.into(), tuber_strategy, expr.into())
+            .map_err(|err| JsError::new(&err.to_string()))?;
+        let display_style = displayStyle
+            .map(tuber::DisplayStyle::from)
+            .unwrap_or(tuber::DisplayStyle::EcmaScript);
+        Ok(Self {
+            trace,
+            display_style,
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn formed(&self) -> String {
+        let expr = self.trace.expr();
+        match self.display_style {
+            tuber::DisplayStyle::EcmaScript => ecmascript_format(&expr, &[]).expr,
+            tuber::DisplayStyle::LazyK => lazy_k_format(&expr).expr,
+        }
+    }
+
+    #[wasm_bindgen(js_name = next)]
+    pub fn js_next(&mut self) -> Result<UnlambdaIteratorResult, JsError> {
+        let value = match self.trace.next() {
+            Some(step) => Some(UnlambdaStep::new(step, &self.display_style)?),
+            None => None,
+        };
+
+        Ok(UnlambdaIteratorResult {
+            done: value.is_none(),
+            value,
+        })
+    }
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct UnlambdaIteratorResult {
+    done: bool,
+    value: Option<UnlambdaStep>,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlambdaStep {
+    step: usize,
+    rule: String,
+    expr: Expr,
+    formed: FormedUnlambdaExpr,
+}
+
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct FormedUnlambdaExpr {
+    expr: String,
+    rewritten_range: ExprRange,
+}
+
+impl UnlambdaStep {
+    fn new(
+        step: tuber::UnlambdaStep,
+        display_style: &tuber::DisplayStyle,
+    ) -> Result<Self, JsError> {
+        let formed = match display_style {
+            tuber::DisplayStyle::EcmaScript => ecmascript_format(&step.expr, &[&step.path]),
+            tuber::DisplayStyle::LazyK => lazy_k_format(&step.expr),
+        };
+        let rewritten_range = reduced_path_to_range(&formed.mapping, &step.path)?;
+
+        Ok(Self {
+            step: step.step,
+            rule: step.rule.to_string(),
+            expr: step.expr.into(),
+            formed: FormedUnlambdaExpr {
+                expr: formed.expr,
+                rewritten_range,
+            },
+        })
+    }
+}
@@ -0,0 +1,123 @@
+use std::ops::Range;
+use tuber::{ecmascript_format, lazy_k_format, DisplayStyle, Expr, Format, Path, Tag};
+
+const RESET: &str = "\x1b[0m";
+const CALLEE: &str = "\x1b[4;31m";
+const ARGUMENTS: [&str; 2] = ["\x1b[4;32m", "\x1b[4;34m"];
+
+/// 式を文字列にし、簡約基の関数部分と引数を色付けする
+///
+/// Web UI と同じく関数部分を赤、引数を緑と青の交互で表示する
+pub fn highlight(expr: &Expr, reducible_path: Option<&Path>, style: &DisplayStyle) -> String {
+    let Some(path) = reducible_path else {
+        return expr.format(style);
+    };
+
+    let formed = match style {
+        DisplayStyle::EcmaScript => ecmascript_format(expr, &[path]),
+        DisplayStyle::LazyK => lazy_k_format(expr),
+    };
+
+    match ranges(&formed.mapping, path) {
+        Some(ranges) => paint(&formed.expr, &ranges),
+        None => formed.expr,
+    }
+}
+
+// 簡約基の関数部分と各引数の範囲 (文字単位)
+fn ranges(mapping: &[Tag], path: &Path) -> Option<Vec<(Range<usize>, &'static str)>> {
+    let mut callee_path = path.clone();
+    callee_path.set_arity(0);
+
+    let mut ranges = vec![(callee_path.range(mapping)?, CALLEE)];
+    for index in 0..path.get_arity() {
+        let mut arg_path = path.clone();
+        arg_path.set_arity(index + 1);
+        arg_path.last_arg();
+        ranges.push((arg_path.range(mapping)?, ARGUMENTS[index % 2]));
+    }
+
+    Some(ranges)
+}
+
+fn paint(s: &str, ranges: &[(Range<usize>, &str)]) -> String {
+    let mut painted = String::new();
+    let mut current: Option<&str> = None;
+
+    for (index, c) in s.chars().enumerate() {
+        let color = ranges
+            .iter()
+            .find(|(range, _)| range.contains(&index))
+            .map(|(_, color)| *color);
+
+        if color != current {
+            if current.is_some() {
+                painted.push_str(RESET);
+            }
+            if let Some(color) = color {
+                painted.push_str(color);
+            }
+            current = color;
+        }
+        painted.push(c);
+    }
+
+    if current.is_some() {
+        painted.push_str(RESET);
+    }
+
+    painted
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tuber::{parse_expr, Aliases, Context, Reducer, ReductionStrategy};
+
+    fn reducible_path(expr: &Expr) -> Option<Path> {
+        Reducer::new(
+            Context::default(),
+            Aliases::new(),
+            expr.clone(),
+            ReductionStrategy::NormalOrder,
+        )
+        .reducible_path()
+    }
+
+    #[test]
+    fn test_highlight() {
+        let expr = parse_expr("k(x, y)").unwrap();
+        let path = reducible_path(&expr);
+
+        assert_eq!(
+            highlight(&expr, path.as_ref(), &DisplayStyle::EcmaScript),
+            format!(
+                "{CALLEE}k{RESET}({}x{RESET}, {}y{RESET})",
+                ARGUMENTS[0], ARGUMENTS[1]
+            )
+        );
+    }
+
+    #[test]
+    fn test_highlight_irreducible() {
+        let expr = parse_expr("x(y)").unwrap();
+        let path = reducible_path(&expr);
+
+        assert_eq!(path, None);
+        assert_eq!(
+            highlight(&expr, path.as_ref(), &DisplayStyle::EcmaScript),
+            "x(y)"
+        );
+    }
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(
+            paint("abcd", &[(1..3, CALLEE)]),
+            format!("a{CALLEE}bc{RESET}d")
+        );
+        assert_eq!(paint("abcd", &[]), "abcd");
+    }
+}
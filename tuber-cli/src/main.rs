@@ -0,0 +1,198 @@
+mod highlight;
+mod repl;
+mod session;
+
+use anyhow::{Context as _, Result};
+use clap::{Parser, ValueEnum};
+use session::Session;
+use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use tuber::{
+    Context, DisplayStyle, LazyKSyntax, ReductionStrategy, ReductionTarget, DEFAULT_MAX_STEPS,
+};
+
+/// λ計算の式を 1 ステップずつ簡約するインタプリタ
+#[derive(Parser)]
+#[command(name = "tuber", version)]
+struct Args {
+    /// 実行するスクリプトファイル
+    file: Option<PathBuf>,
+
+    /// 実行するコマンド (複数指定できる)
+    #[arg(short, long = "eval", value_name = "COMMAND")]
+    eval: Vec<String>,
+
+    /// スクリプトやコマンドを実行した後に REPL を起動する
+    #[arg(short, long)]
+    interactive: bool,
+
+    /// 式の表示形式
+    #[arg(short, long, value_enum, default_value_t = Style::EcmaScript)]
+    style: Style,
+
+    /// 評価戦略
+    #[arg(long, value_enum, default_value_t = Strategy::NormalOrder)]
+    strategy: Strategy,
+
+    /// 関数定義を展開し、ラムダ抽象の内側まで簡約して β正規形を求める
+    #[arg(long)]
+    strong: bool,
+
+    /// --strong と併せて η簡約も行う
+    #[arg(long, requires = "strong")]
+    eta: bool,
+
+    /// どの形に達したら簡約をやめるか
+    #[arg(long, value_enum, default_value_t = Target::Nf)]
+    target: Target,
+
+    /// 1つのコマンドで行う簡約ステップ数の上限
+    #[arg(long, default_value_t = DEFAULT_MAX_STEPS)]
+    max_steps: usize,
+
+    /// 組み込みの関数定義を読み込まない
+    #[arg(long)]
+    no_default_context: bool,
+
+    /// 簡約の経過に現れる Church 数を数字で表示する
+    #[arg(long)]
+    numerals: bool,
+
+    /// 正規形に達した式を Church 数やリストなどとして読み取って表示する
+    #[arg(long)]
+    decode: bool,
+
+    /// ラムダ抽象を除去した結果を Lazy K のプログラムとしても出力する
+    #[arg(long, value_enum, value_name = "SYNTAX")]
+    export: Option<Export>,
+
+    /// 簡約基を色付けするかどうか
+    #[arg(long, value_enum, default_value_t = Color::Auto)]
+    color: Color,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Style {
+    #[value(name = "ecmascript")]
+    EcmaScript,
+    #[value(name = "lazy-k")]
+    LazyK,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Strategy {
+    NormalOrder,
+    ApplicativeOrder,
+    CallByName,
+    CallByValue,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Target {
+    Whnf,
+    Hnf,
+    Nf,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Export {
+    Combinator,
+    Unlambda,
+    Iota,
+    Jot,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{:#}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    let context = if args.no_default_context {
+        Context::new()
+    } else {
+        Context::default()
+    };
+
+    let color = match args.color {
+        Color::Auto => io::stdout().is_terminal(),
+        Color::Always => true,
+        Color::Never => false,
+    };
+
+    let style = match args.style {
+        Style::EcmaScript => DisplayStyle::EcmaScript,
+        Style::LazyK => DisplayStyle::LazyK,
+    };
+
+    let mut session = Session::new(context, style, color);
+    session.set_max_steps(args.max_steps);
+    session.set_numerals(args.numerals);
+    session.set_decode(args.decode);
+    session.set_export(args.export.map(|export| match export {
+        Export::Combinator => LazyKSyntax::Combinator,
+        Export::Unlambda => LazyKSyntax::Unlambda,
+        Export::Iota => LazyKSyntax::Iota,
+        Export::Jot => LazyKSyntax::Jot,
+    }));
+    session.set_strategy(match args.strategy {
+        Strategy::NormalOrder => ReductionStrategy::NormalOrder,
+        Strategy::ApplicativeOrder => ReductionStrategy::ApplicativeOrder,
+        Strategy::CallByName => ReductionStrategy::CallByName,
+        Strategy::CallByValue => ReductionStrategy::CallByValue,
+    });
+    session.set_strong_normalization(args.strong);
+    session.set_eta_reduction(args.eta);
+    session.set_target(match args.target {
+        Target::Whnf => ReductionTarget::WeakHeadNormalForm,
+        Target::Hnf => ReductionTarget::HeadNormalForm,
+        Target::Nf => ReductionTarget::NormalForm,
+    });
+
+    let mut batch = false;
+
+    if let Some(file) = &args.file {
+        let script = std::fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        print(session.run_script(&script)?);
+        batch = true;
+    }
+
+    for command in &args.eval {
+        print(session.run_command(command)?);
+        batch = true;
+    }
+
+    if !batch && !io::stdin().is_terminal() {
+        // パイプから与えられた入力をスクリプトとして実行する
+        let mut script = String::new();
+        io::stdin().read_to_string(&mut script)?;
+        print(session.run_script(&script)?);
+        batch = true;
+    }
+
+    if !batch || args.interactive {
+        repl::start(&mut session)?;
+    }
+
+    Ok(())
+}
+
+fn print(lines: Vec<String>) {
+    for line in lines {
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::session::Session;
+use anyhow::Result;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::path::PathBuf;
+
+const PROMPT: &str = "> ";
+
+/// 対話的にコマンドを読み込んで実行する
+///
+/// 入力の履歴は ~/.tuber_history に保存する
+pub fn start(session: &mut Session) -> Result<()> {
+    let mut editor = DefaultEditor::new()?;
+
+    let history = history_path();
+    if let Some(history) = &history {
+        // 初回起動時は履歴ファイルが存在しない
+        let _ = editor.load_history(history);
+    }
+
+    loop {
+        match editor.readline(PROMPT) {
+            Ok(line) => {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                editor.add_history_entry(line)?;
+
+                match session.run_script(line) {
+                    Ok(lines) => {
+                        for line in lines {
+                            println!("{}", line);
+                        }
+                    }
+                    Err(err) => eprintln!("{:#}", err),
+                }
+            }
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    if let Some(history) = &history {
+        editor.save_history(history)?;
+    }
+
+    Ok(())
+}
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".tuber_history"))
+}
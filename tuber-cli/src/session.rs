@@ -0,0 +1,489 @@
+use crate::highlight::highlight;
+use anyhow::{anyhow, Result};
+use tuber::{
+    decode, export, infer_type, parse_command, parse_script, Aliases, Command, Comparison, Context,
+    DisplayStyle, Engine, Expr, Format, Func, LazyKSyntax, Output, ParseError, Path, Reducer,
+    Reduction, ReductionStrategy, ReductionTarget, Termination, TypeError,
+};
+
+/// Engine を保持し、コマンドの実行結果を表示用の行に変換する
+pub struct Session {
+    engine: Engine,
+    strategy: ReductionStrategy,
+    strong_normalization: bool,
+    eta_reduction: bool,
+    target: ReductionTarget,
+    style: DisplayStyle,
+    color: bool,
+    numerals: bool,
+    decode: bool,
+    export: Option<LazyKSyntax>,
+}
+
+impl Session {
+    pub fn new(context: Context, style: DisplayStyle, color: bool) -> Self {
+        Self {
+            engine: Engine::new(context, Aliases::new()),
+            strategy: ReductionStrategy::default(),
+            strong_normalization: false,
+            eta_reduction: false,
+            target: ReductionTarget::default(),
+            style,
+            color,
+            numerals: false,
+            decode: false,
+            export: None,
+        }
+    }
+
+    pub fn set_strategy(&mut self, strategy: ReductionStrategy) {
+        self.strategy = strategy;
+        self.engine.set_strategy(strategy);
+    }
+
+    /// 関数定義を展開し、ラムダ抽象の内側まで簡約するかどうかを設定する
+    pub fn set_strong_normalization(&mut self, enabled: bool) {
+        self.strong_normalization = enabled;
+        self.engine.set_strong_normalization(enabled);
+    }
+
+    /// 強正規化の際に η簡約も行うかどうかを設定する
+    pub fn set_eta_reduction(&mut self, enabled: bool) {
+        self.eta_reduction = enabled;
+        self.engine.set_eta_reduction(enabled);
+    }
+
+    /// どの形に達したら簡約をやめるかを設定する
+    pub fn set_target(&mut self, target: ReductionTarget) {
+        self.target = target;
+        self.engine.set_target(target);
+    }
+
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        self.engine.set_max_steps(max_steps);
+    }
+
+    /// 簡約の経過に現れる Church 数を数字で表示するかどうかを設定する
+    pub fn set_numerals(&mut self, numerals: bool) {
+        self.numerals = numerals;
+    }
+
+    /// 正規形に達した式を Church 数やリストなどとして読み取って表示するかどうかを設定する
+    pub fn set_decode(&mut self, decode: bool) {
+        self.decode = decode;
+    }
+
+    /// ラムダ抽象を除去した結果を、Lazy K のプログラムとしても出力するかどうかを設定する
+    pub fn set_export(&mut self, export: Option<LazyKSyntax>) {
+        self.export = export;
+    }
+
+    /// 1つのコマンドを解析して実行する
+    pub fn run_command(&mut self, input: &str) -> Result<Vec<String>> {
+        let command = parse_command(input).map_err(|err| describe(input, err))?;
+        self.run(command)
+    }
+
+    /// スクリプトを解析し、文を順に実行する
+    pub fn run_script(&mut self, input: &str) -> Result<Vec<String>> {
+        let statements = parse_script(input).map_err(|err| describe(input, err))?;
+
+        let mut lines = Vec::new();
+        for statement in statements {
+            lines.extend(self.run(statement.command)?);
+        }
+        Ok(lines)
+    }
+
+    fn run(&mut self, command: Command) -> Result<Vec<String>> {
+        // 簡約後は `_` などの別名がずれるので、実行前の状態で簡約基を探す
+        let aliases = self.engine.aliases().clone();
+        let output = self.engine.run(command).map_err(|err| {
+            // 型エラーの部分式は入力と同じ文法で表示する
+            match err.downcast::<TypeError>() {
+                Ok(err) => anyhow!(err.format(&self.style)),
+                Err(err) => err,
+            }
+        })?;
+
+        let lines = match output {
+            Output::Del(id) | Output::QueryUndefined(id) => vec![format!("{} = {}", id, id)],
+            Output::Update(func) => vec![func.format(&self.style)],
+            Output::QueryDefined(func) => vec![self.func(&func)],
+            Output::Context(funcs) | Output::Data { funcs, .. } => {
+                funcs.iter().map(|f| self.func(f)).collect()
+            }
+            Output::Import { module, funcs } => {
+                let names: Vec<_> = funcs.iter().map(|f| f.name()).collect();
+                vec![format!("import {}: {}", module, names.join(", "))]
+            }
+            Output::Eval(reduction) | Output::EvalHead(reduction) => {
+                self.reduction(&aliases, &reduction, false)
+            }
+            Output::EvalLast(reduction) | Output::EvalTail(reduction) => {
+                self.reduction(&aliases, &reduction, true)
+            }
+            Output::Unlambda { expr, result } => {
+                let mut lines = vec![
+                    expr.format(&self.style),
+                    format!("~> {}", result.format(&self.style)),
+                ];
+                if let Some(syntax) = self.export {
+                    lines.push(export(&result, syntax)?);
+                }
+                lines
+            }
+            Output::Metrics(comparison) => self.comparison(&comparison),
+            Output::TypeOf { expr, ty } => vec![format!("{} : {}", expr.format(&self.style), ty)],
+        };
+
+        Ok(lines)
+    }
+
+    // 定義済み関数を表示する。型が付く場合は、その型をコメントとして添える
+    fn func(&self, func: &Func) -> String {
+        let line = func.format(&self.style);
+        match infer_type(self.engine.context(), &Expr::Variable(func.name().into())) {
+            Ok(ty) => format!("{}  # {}", line, ty),
+            Err(_) => line,
+        }
+    }
+
+    fn reduction(&self, aliases: &Aliases, reduction: &Reduction, omitted: bool) -> Vec<String> {
+        let mut lines = vec![self.step(aliases, 0, &reduction.expr)];
+
+        if let Some(first) = reduction.steps.first() {
+            if omitted && 1 < first.step {
+                lines.push("     ……".to_string());
+            }
+        }
+
+        for result in &reduction.steps {
+            lines.push(self.step(aliases, result.step, &result.expr));
+        }
+
+        match &reduction.termination {
+            Termination::NormalForm => {
+                if self.decode {
+                    let last = reduction.steps.last().map_or(&reduction.expr, |r| &r.expr);
+                    if let Some(decoded) = decode(self.engine.context(), aliases, last) {
+                        lines.push(format!("     = {}", decoded));
+                    }
+                }
+            }
+            Termination::TargetReached(target) => {
+                let form = match target {
+                    ReductionTarget::WeakHeadNormalForm => "weak head normal form",
+                    ReductionTarget::HeadNormalForm => "head normal form",
+                    ReductionTarget::NormalForm => "normal form",
+                };
+                lines.push(format!("(reached {})", form));
+            }
+            Termination::StepLimit { max_steps } => {
+                lines.push(format!("(stopped after {} steps)", max_steps));
+            }
+            Termination::SizeLimit { step, size } => {
+                lines.push(format!("(stopped at step {}: size {})", step, size));
+            }
+            Termination::Cycle { step, since } => {
+                lines.push(format!("(cycle detected: step {} = step {})", step, since));
+            }
+        }
+
+        lines
+    }
+
+    fn comparison(&self, comparison: &Comparison) -> Vec<String> {
+        let mut lines = vec![comparison.expr.format(&self.style)];
+        for args in &comparison.samples {
+            let args: Vec<_> = args.iter().map(|arg| arg.format(&self.style)).collect();
+            lines.push(format!("  sample: ({})", args.join(", ")));
+        }
+
+        for report in &comparison.reports {
+            let steps: Vec<_> = report
+                .steps
+                .iter()
+                .map(|steps| steps.map_or("-".to_string(), |steps| steps.to_string()))
+                .collect();
+            let combinators: Vec<_> = report
+                .metrics
+                .combinators
+                .iter()
+                .map(|(id, count)| format!("{}:{}", id, count))
+                .collect();
+            lines.push(format!(
+                "  {:<8} size {:>4}  depth {:>4}  steps {:>5}  {}",
+                format!("{:?}", report.strategy),
+                report.metrics.size,
+                report.metrics.depth,
+                steps.join(", "),
+                combinators.join(" ")
+            ));
+        }
+
+        lines
+    }
+
+    fn step(&self, aliases: &Aliases, step: usize, expr: &Expr) -> String {
+        // Church 数は正規形なので、置き換えても簡約基の位置は変わらない
+        let shown = if self.numerals {
+            expr.resugar_numerals()
+        } else {
+            expr.clone()
+        };
+
+        let expr = if self.color {
+            let path = self.reducible_path(aliases, expr);
+            highlight(&shown, path.as_ref(), &self.style)
+        } else {
+            shown.format(&self.style)
+        };
+        format!("{:>4}: {}", step, expr)
+    }
+
+    fn reducible_path(&self, aliases: &Aliases, expr: &Expr) -> Option<Path> {
+        let mut reducer = Reducer::new(
+            self.engine.context().clone(),
+            aliases.clone(),
+            expr.clone(),
+            self.strategy,
+        );
+        reducer.set_strong_normalization(self.strong_normalization);
+        reducer.set_eta_reduction(self.eta_reduction);
+        reducer.set_target(self.target);
+        reducer.reducible_path()
+    }
+}
+
+// 入力とエラー箇所を示す行を添えたエラーにする
+fn describe(input: &str, err: ParseError) -> anyhow::Error {
+    let line_start = input[..err.span.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[err.span.start..]
+        .find('\n')
+        .map_or(input.len(), |i| err.span.start + i);
+    let column = input[line_start..err.span.start].chars().count();
+
+    anyhow::anyhow!(
+        "{}\n{}\n{}^",
+        err,
+        &input[line_start..line_end],
+        " ".repeat(column)
+    )
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        Session::new(Context::default(), DisplayStyle::EcmaScript, false)
+    }
+
+    #[test]
+    fn test_run_command() {
+        let mut session = session();
+
+        assert_eq!(
+            session.run_command("s(k, k, :a)").unwrap(),
+            vec!["   0: s(k, k, :a)", "   1: k(:a, k(:a))", "   2: :a"]
+        );
+        assert_eq!(
+            session.run_command("! s(k, k, :a)").unwrap(),
+            vec!["   0: s(k, k, :a)", "     ……", "   2: :a"]
+        );
+        assert_eq!(session.run_command("? f").unwrap(), vec!["f = f"]);
+    }
+
+    #[test]
+    fn test_numerals() {
+        let mut session = session();
+        session.set_numerals(true);
+
+        assert_eq!(
+            session.run_command("k(21, 0)").unwrap(),
+            vec!["   0: k(21, 0)", "   1: 21"]
+        );
+
+        session.set_numerals(false);
+        assert_eq!(
+            session.run_command("k(1, 0)").unwrap(),
+            vec![
+                "   0: k((f, x) => f(x), (f, x) => x)",
+                "   1: (f, x) => f(x)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_target() {
+        let mut session = session();
+        session.set_target(ReductionTarget::WeakHeadNormalForm);
+
+        assert_eq!(
+            session.run_command("k(x => i(x), :b)").unwrap(),
+            vec![
+                "   0: k(x => i(x), :b)",
+                "   1: x => i(x)",
+                "(reached weak head normal form)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut session = session();
+        session.set_decode(true);
+
+        assert_eq!(
+            session.run_command("! ADD(2, 3)").unwrap(),
+            vec![
+                "   0: ADD((f, x) => f(f(x)), (f, x) => f(f(f(x))))",
+                "     ……",
+                "   5: (f, x) => f(f(f(f(f(x)))))",
+                "     = 5"
+            ]
+        );
+        assert_eq!(
+            session
+                .run_command("! CONS(:a, CONS(TRUE, NIL))")
+                .unwrap()
+                .last()
+                .unwrap(),
+            "     = [:a, TRUE]"
+        );
+        assert_eq!(
+            session.run_command("s(k, k, :a)").unwrap().last().unwrap(),
+            "     = :a"
+        );
+        assert_eq!(session.run_command("x(y)").unwrap(), vec!["   0: x(y)"]);
+    }
+
+    #[test]
+    fn test_export() {
+        let mut session = session();
+        session.set_export(Some(LazyKSyntax::Unlambda));
+
+        assert_eq!(
+            session.run_command("~~ x => x(x)").unwrap(),
+            vec!["x => x(x)", "~> s(i, i)", "``sii"]
+        );
+
+        let err = session.run_command("~~ x => :a(x)").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot export: only s, k, i and ι may appear, but found `:a`"
+        );
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut session = session();
+
+        let lines = session.run_command("metrics x => y => y(x)").unwrap();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "(x, y) => y(x)");
+        assert_eq!(lines[1], "  sample: (:a, :b)");
+        assert_eq!(
+            lines[2],
+            "  SKI      size    9  depth    5  steps     5  i:1 k:2 s:2"
+        );
+    }
+
+    #[test]
+    fn test_type_of() {
+        let mut session = session();
+
+        assert_eq!(
+            session.run_command(":t s(k)").unwrap(),
+            vec!["s(k) : (a -> b) -> a -> a"]
+        );
+        assert_eq!(
+            session.run_command("? k").unwrap(),
+            vec!["k(x, y) = x  # a -> b -> a"]
+        );
+
+        // 型の付かない関数には型を添えない
+        assert_eq!(
+            session.run_command("? Y").unwrap(),
+            vec!["Y(f) = (x => f(x(x)))(x => f(x(x)))"]
+        );
+        assert_eq!(
+            session.run_command(":t x => x(x)").unwrap_err().to_string(),
+            "cannot construct the infinite type a = a -> b in x(x)"
+        );
+    }
+
+    #[test]
+    fn test_import() {
+        let mut session = Session::new(Context::new(), DisplayStyle::EcmaScript, false);
+
+        assert_eq!(
+            session.run_command("import list").unwrap(),
+            vec!["import list: CONS, CAR, CDR, NIL, IS_NIL"]
+        );
+        assert_eq!(
+            session.run_command("! CAR(CONS(:a, :b))").unwrap(),
+            vec!["   0: CAR(CONS(:a, :b))", "     ……", "   6: :a"]
+        );
+        assert!(session.run_command("CAR(x) = x").is_err());
+    }
+
+    #[test]
+    fn test_data() {
+        let mut session = Session::new(Context::new(), DisplayStyle::EcmaScript, false);
+
+        let nothing = "NOTHING = (n, j) => n  # a -> b -> a";
+        let just = "JUST(v) = (n, j) => j(v)  # a -> b -> (a -> c) -> c";
+        let case = "CASE_MAYBE(x, n, j) = x(n, j)  # (a -> b -> c) -> a -> b -> c";
+        let fold = "FOLD_MAYBE(x, n, j) = x(n, j)  # (a -> b -> c) -> a -> b -> c";
+        assert_eq!(
+            session
+                .run_command("data MAYBE = NOTHING | JUST v")
+                .unwrap(),
+            vec![nothing, just, case, fold]
+        );
+
+        // 生成した関数はデータ型の名前でまとめて表示・削除できる
+        assert_eq!(
+            session.run_command("? MAYBE").unwrap(),
+            vec![case, fold, just, nothing]
+        );
+        assert_eq!(
+            session.run_command("MAYBE = MAYBE").unwrap(),
+            vec!["MAYBE = MAYBE"]
+        );
+        assert_eq!(session.run_command("? JUST").unwrap(), vec!["JUST = JUST"]);
+    }
+
+    #[test]
+    fn test_run_script() {
+        let mut session = session();
+
+        let lines = session
+            .run_script("f(x) = x(x) # 自己適用\n!3 f(f)")
+            .unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                "f(x) = x(x)",
+                "   0: f(f)",
+                "   1: f(f)",
+                "   2: f(f)",
+                "   3: f(f)",
+                "(stopped after 3 steps)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = session().run_command("f(x))").unwrap_err();
+        let message = err.to_string();
+        let lines: Vec<_> = message.lines().collect();
+
+        assert_eq!(&lines[1..], ["f(x))", "    ^"]);
+    }
+}
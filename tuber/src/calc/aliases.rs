@@ -17,6 +17,21 @@ impl Aliases {
     pub fn has(&self, id: &Identifier) -> bool {
         self.0.contains_key(id)
     }
+
+    /// 式を `_` として登録する
+    ///
+    /// それまでの `_`, `_0`, ..., `_8` は `_0`, `_1`, ..., `_9` にずらし、それより古いものは捨てる
+    pub fn push(&mut self, expr: Expr) {
+        for i in (0..9).rev() {
+            if let Some(e) = self.0.remove(&format!("_{}", i).into()) {
+                self.0.insert(format!("_{}", i + 1).into(), e);
+            }
+        }
+        if let Some(e) = self.0.remove(&"_".into()) {
+            self.0.insert("_0".into(), e);
+        }
+        self.0.insert("_".into(), expr);
+    }
 }
 
 impl From<HashMap<Identifier, Expr>> for Aliases {
@@ -33,3 +48,24 @@ impl IntoIterator for Aliases {
         self.0.into_iter()
     }
 }
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_push() {
+        let mut aliases = Aliases::new();
+        for i in 0..12 {
+            aliases.push(expr::s(format!("a{}", i)));
+        }
+
+        assert_eq!(aliases.get(&"_".into()), Some(&expr::s("a11")));
+        assert_eq!(aliases.get(&"_0".into()), Some(&expr::s("a10")));
+        assert_eq!(aliases.get(&"_9".into()), Some(&expr::s("a1")));
+        assert_eq!(aliases.0.len(), 11);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::calc::aliases::Aliases;
 use crate::context::Context;
-use crate::expr::Expr;
+use crate::expr::{self, Expr};
 use anyhow::{anyhow, Result};
 
 pub fn apply(context: &Context, aliases: &Aliases, expr: &mut Expr, args: Vec<Expr>) -> Result<()> {
@@ -14,12 +14,29 @@ pub fn apply(context: &Context, aliases: &Aliases, expr: &mut Expr, args: Vec<Ex
     }
 
     match expr {
-        Expr::Lambda { param, body } => {
+        Expr::Lambda { param, body, .. } => {
             body.substitute(param, &args[0]);
             *expr = *body.clone();
             Ok(())
         }
 
+        // letrec f = M in N は N の中の f を letrec f = M in M に置き換える
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            let value = if *recursive {
+                expr::letrec(name.clone(), value.as_ref().clone(), value.as_ref().clone())
+            } else {
+                value.as_ref().clone()
+            };
+            body.substitute(name, &value);
+            *expr = *body.clone();
+            Ok(())
+        }
+
         Expr::Variable(id) => {
             if let Some(alias) = aliases.get(id) {
                 *expr = alias.to_owned();
@@ -42,6 +59,7 @@ pub fn apply(context: &Context, aliases: &Aliases, expr: &mut Expr, args: Vec<Ex
 fn arity(context: &Context, aliases: &Aliases, expr: &Expr) -> Option<usize> {
     match expr {
         Expr::Lambda { .. } => Some(1),
+        Expr::Let { .. } => Some(0),
         Expr::Variable(id) => {
             if aliases.has(id) {
                 return Some(0);
@@ -5,6 +5,7 @@ use crate::expr::Expr;
 pub fn arity(context: &Context, aliases: &Aliases, expr: &Expr) -> Option<usize> {
     match expr {
         Expr::Lambda { .. } => Some(1),
+        Expr::Let { .. } => Some(0),
         Expr::Variable(id) => {
             if aliases.has(id) {
                 Some(0)
@@ -0,0 +1,12 @@
+/// 簡約に用いる式の内部表現
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ReductionBackend {
+    /// 式を木として保持し、関数適用のたびに引数を複製する
+    #[default]
+    Tree,
+
+    /// 式を部分式を共有するグラフとして保持し、簡約基をその場で書き換える
+    ///
+    /// 同じ引数が何度も参照される計算 (チャーチ数の冪など) で時間と記憶領域を節約できる
+    Graph,
+}
@@ -0,0 +1,393 @@
+use super::{Aliases, Reducer, ReductionStrategy, Termination};
+use crate::context::Context;
+use crate::expr::{self, Expr, Identifier};
+use std::fmt::Display;
+
+/// 式を探る1回の簡約で行うステップ数の上限
+const MAX_STEPS: usize = 10000;
+
+/// 式を探る簡約で許す式の大きさの上限
+const MAX_SIZE: usize = 100000;
+
+/// 読み取るリストの長さと入れ子の深さの上限 (無限リストで止まらなくなるのを防ぐ)
+const MAX_LENGTH: usize = 256;
+const MAX_DEPTH: usize = 16;
+
+// 利用者が書く式と衝突しないよう、パースできない名前のシンボルで式を探る
+const PROBE_A: &str = "#a";
+const PROBE_B: &str = "#b";
+
+/// よく知られたエンコーディングで表されたデータ
+#[derive(Clone, Debug, PartialEq)]
+pub enum Decoded {
+    /// Church 真偽値 (`FALSE` は Church 数の 0 や `NIL` と同じ式になるが、真偽値として読む)
+    Boolean(bool),
+
+    /// Church 数
+    Numeral(usize),
+
+    /// シンボル
+    Symbol(Identifier),
+
+    /// `CONS` で作った対 (`NIL` で終わらないもの)
+    Pair(Box<Decoded>, Box<Decoded>),
+
+    /// `CONS` と `NIL` で作ったリスト, または Scott エンコーディングのリスト
+    List(Vec<Decoded>),
+
+    /// Church 数で表した文字の Scott エンコーディングのリスト
+    String(String),
+
+    /// 読み取れなかった式
+    Term(Expr),
+}
+
+impl Display for Decoded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Decoded::Boolean(true) => write!(f, "TRUE"),
+            Decoded::Boolean(false) => write!(f, "FALSE"),
+            Decoded::Numeral(n) => write!(f, "{}", n),
+            Decoded::Symbol(id) => write!(f, ":{}", id),
+            Decoded::Pair(car, cdr) => write!(f, "({}, {})", car, cdr),
+            Decoded::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Decoded::String(s) => write!(f, "{:?}", s),
+            Decoded::Term(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+/// 式が Church 数・Church 真偽値・`CONS` と `NIL` によるリスト・Scott エンコーディングのリストの
+/// いずれかであれば、その値を読み取る
+///
+/// 式に探りを入れるための引数を与えて簡約し、その結果から値を判定する
+/// そのため、ラムダ抽象の内側が簡約されていない式や、Context で定義された名前を含む式も読み取れる
+pub fn decode(context: &Context, aliases: &Aliases, expr: &Expr) -> Option<Decoded> {
+    let decoder = Decoder { context, aliases };
+    match decoder.decode(expr, 0) {
+        Decoded::Term(_) => None,
+        decoded => Some(decoded),
+    }
+}
+
+// ========================================================================== //
+
+struct Decoder<'a> {
+    context: &'a Context,
+    aliases: &'a Aliases,
+}
+
+impl Decoder<'_> {
+    fn decode(&self, expr: &Expr, depth: usize) -> Decoded {
+        if depth > MAX_DEPTH {
+            return Decoded::Term(expr.clone());
+        }
+
+        if let Some(Expr::Symbol(id)) = self.normalize(expr.clone()) {
+            return Decoded::Symbol(id);
+        }
+
+        match self.apply2(expr) {
+            Some(Probed::A) => return Decoded::Boolean(true),
+            Some(Probed::B) => return Decoded::Boolean(false),
+            Some(Probed::Numeral(n)) => return Decoded::Numeral(n),
+            Some(Probed::ScottCons(head, tail)) => {
+                if let Some(decoded) = self.scott_list(head, tail, depth) {
+                    return decoded;
+                }
+            }
+            None => {}
+        }
+
+        if let Some((car, cdr)) = self.church_pair(expr) {
+            return self.church_list(car, cdr, depth);
+        }
+
+        Decoded::Term(expr.clone())
+    }
+
+    // CONS と NIL によるリスト (NIL で終わらなければ対として読む)
+    fn church_list(&self, car: Expr, cdr: Expr, depth: usize) -> Decoded {
+        let mut items = vec![car];
+        let mut rest = cdr;
+
+        while items.len() <= MAX_LENGTH {
+            if let Some(Probed::B) = self.apply2(&rest) {
+                let items = items.iter().map(|e| self.decode(e, depth + 1)).collect();
+                return Decoded::List(items);
+            }
+
+            match self.church_pair(&rest) {
+                Some((car, cdr)) => {
+                    items.push(car);
+                    rest = cdr;
+                }
+                None => break,
+            }
+        }
+
+        // リストになっていない場合は、最初の対だけを読む
+        let mut items = items.into_iter();
+        let car = items.next().unwrap();
+        let cdr = match items.next() {
+            Some(cadr) => {
+                let mut cdr = rest;
+                for item in items.rev() {
+                    cdr = expr::l("f", expr::a(expr::a("f", item), cdr));
+                }
+                expr::l("f", expr::a(expr::a("f", cadr), cdr))
+            }
+            None => rest,
+        };
+        Decoded::Pair(
+            Box::new(self.decode(&car, depth + 1)),
+            Box::new(self.decode(&cdr, depth + 1)),
+        )
+    }
+
+    // Scott エンコーディングのリスト (要素がすべて文字であれば文字列として読む)
+    fn scott_list(&self, head: Expr, tail: Expr, depth: usize) -> Option<Decoded> {
+        let mut items = vec![head];
+        let mut rest = tail;
+
+        loop {
+            if items.len() > MAX_LENGTH {
+                return None;
+            }
+            match self.apply2(&rest)? {
+                Probed::A => break,
+                Probed::ScottCons(head, tail) => {
+                    items.push(head);
+                    rest = tail;
+                }
+                _ => return None,
+            }
+        }
+
+        let items: Vec<Decoded> = items.iter().map(|e| self.decode(e, depth + 1)).collect();
+
+        let chars: Option<String> = items
+            .iter()
+            .map(|item| match item {
+                Decoded::Numeral(n) => u32::try_from(*n).ok().and_then(char::from_u32),
+                _ => None,
+            })
+            .collect();
+
+        match chars {
+            Some(s) => Some(Decoded::String(s)),
+            None => Some(Decoded::List(items)),
+        }
+    }
+
+    // `expr(#a, #b)` を簡約した結果を分類する
+    fn apply2(&self, expr: &Expr) -> Option<Probed> {
+        let probed = expr::a(expr::a(expr.clone(), expr::s(PROBE_A)), expr::s(PROBE_B));
+        let result = self.normalize(probed)?;
+
+        if result == expr::s(PROBE_A) {
+            return Some(Probed::A);
+        }
+        if result == expr::s(PROBE_B) {
+            // FALSE と Church 数の 0 は同じ式なので、真偽値として扱う
+            return Some(Probed::B);
+        }
+
+        // Church 数: #a(#a(…#a(#b)…))
+        let mut n = 0;
+        let mut body = &result;
+        while let Expr::Apply { lhs, rhs } = body {
+            if lhs.as_ref() != &expr::s(PROBE_A) {
+                break;
+            }
+            n += 1;
+            body = rhs;
+        }
+        if body == &expr::s(PROBE_B) {
+            return Some(Probed::Numeral(n));
+        }
+
+        // Scott エンコーディングの cons: #b(head, tail)
+        if let Expr::Apply { lhs, rhs: tail } = &result {
+            if let Expr::Apply { lhs, rhs: head } = lhs.as_ref() {
+                if lhs.as_ref() == &expr::s(PROBE_B) && !self.mentions_probe(head, tail) {
+                    return Some(Probed::ScottCons(*head.clone(), *tail.clone()));
+                }
+            }
+        }
+
+        None
+    }
+
+    // `expr(#a)` を簡約して #a(car, cdr) になれば CONS で作った対
+    fn church_pair(&self, expr: &Expr) -> Option<(Expr, Expr)> {
+        let result = self.normalize(expr::a(expr.clone(), expr::s(PROBE_A)))?;
+
+        let Expr::Apply { lhs, rhs: cdr } = result else {
+            return None;
+        };
+        let Expr::Apply { lhs, rhs: car } = *lhs else {
+            return None;
+        };
+        if *lhs != expr::s(PROBE_A) || self.mentions_probe(&car, &cdr) {
+            return None;
+        }
+
+        Some((*car, *cdr))
+    }
+
+    fn normalize(&self, expr: Expr) -> Option<Expr> {
+        let mut reducer = Reducer::new(
+            self.context.clone(),
+            self.aliases.clone(),
+            expr,
+            ReductionStrategy::NormalOrder,
+        );
+        reducer.set_max_steps(Some(MAX_STEPS));
+        reducer.set_max_size(Some(MAX_SIZE));
+        reducer.by_ref().for_each(drop);
+
+        match reducer.termination() {
+            Some(Termination::NormalForm) => Some(reducer.expr()),
+            _ => None,
+        }
+    }
+
+    fn mentions_probe(&self, lhs: &Expr, rhs: &Expr) -> bool {
+        contains_probe(lhs) || contains_probe(rhs)
+    }
+}
+
+fn contains_probe(expr: &Expr) -> bool {
+    match expr {
+        Expr::Symbol(id) => id.as_str() == PROBE_A || id.as_str() == PROBE_B,
+        Expr::Variable(_) => false,
+        Expr::Apply { lhs, rhs } => contains_probe(lhs) || contains_probe(rhs),
+        Expr::Lambda { body, .. }
+        | Expr::TypeLambda { body, .. }
+        | Expr::TypeApply { lhs: body, .. } => contains_probe(body),
+        Expr::Let { value, body, .. } => contains_probe(value) || contains_probe(body),
+    }
+}
+
+enum Probed {
+    A,
+    B,
+    Numeral(usize),
+    ScottCons(Expr, Expr),
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{church_numeral, scott_string};
+    use crate::parser::parse_expr;
+
+    fn decode_str(s: &str) -> Option<Decoded> {
+        decode(
+            &Context::default(),
+            &Aliases::new(),
+            &parse_expr(s).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_numeral() {
+        assert_eq!(decode_str("5"), Some(Decoded::Numeral(5)));
+        // ラムダ抽象の内側が簡約されていなくても読み取れる
+        assert_eq!(decode_str("ADD(2, 3)"), Some(Decoded::Numeral(5)));
+        assert_eq!(decode_str("SUCC(20)"), Some(Decoded::Numeral(21)));
+    }
+
+    #[test]
+    fn test_boolean() {
+        assert_eq!(decode_str("TRUE"), Some(Decoded::Boolean(true)));
+        assert_eq!(decode_str("k"), Some(Decoded::Boolean(true)));
+        assert_eq!(decode_str("IS_ZERO(1)"), Some(Decoded::Boolean(false)));
+        assert_eq!(decode_str("0"), Some(Decoded::Boolean(false)));
+    }
+
+    #[test]
+    fn test_list() {
+        assert_eq!(
+            decode_str("CONS(1, CONS(:a, NIL))"),
+            Some(Decoded::List(vec![
+                Decoded::Numeral(1),
+                Decoded::Symbol("a".into())
+            ]))
+        );
+        assert_eq!(
+            decode_str("CONS(TRUE, 2)"),
+            Some(Decoded::Pair(
+                Box::new(Decoded::Boolean(true)),
+                Box::new(Decoded::Numeral(2))
+            ))
+        );
+        assert_eq!(
+            decode_str("CONS(1, CONS(2, 3))"),
+            Some(Decoded::Pair(
+                Box::new(Decoded::Numeral(1)),
+                Box::new(Decoded::Pair(
+                    Box::new(Decoded::Numeral(2)),
+                    Box::new(Decoded::Numeral(3))
+                ))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_scott() {
+        let context = Context::default();
+        let aliases = Aliases::new();
+
+        assert_eq!(
+            decode(&context, &aliases, &scott_string("tuber")),
+            Some(Decoded::String("tuber".to_string()))
+        );
+
+        // 空の Scott リストは TRUE と同じ式になる
+        assert_eq!(
+            decode(&context, &aliases, &scott_string("")),
+            Some(Decoded::Boolean(true))
+        );
+
+        let cons = |h: Expr, t: Expr| expr::l("n", expr::l("c", expr::a(expr::a("c", h), t)));
+        let list = cons(
+            expr::s("a"),
+            cons(church_numeral(3), expr::l("n", expr::l("c", "n"))),
+        );
+        assert_eq!(
+            decode(&context, &aliases, &list),
+            Some(Decoded::List(vec![
+                Decoded::Symbol("a".into()),
+                Decoded::Numeral(3)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_undecodable() {
+        assert_eq!(decode_str("s"), None);
+        assert_eq!(decode_str(":a(:b)"), None);
+        assert_eq!(decode_str("x => x(x)"), None);
+        assert_eq!(decode_str("Y(i)"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let decoded = Decoded::List(vec![
+            Decoded::Numeral(1),
+            Decoded::Boolean(false),
+            Decoded::Pair(
+                Box::new(Decoded::Symbol("a".into())),
+                Box::new(Decoded::String("b".to_string())),
+            ),
+        ]);
+        assert_eq!(decoded.to_string(), r#"[1, FALSE, (:a, "b")]"#);
+    }
+}
@@ -22,10 +22,32 @@ fn expand_(context: &Context, expr: Expr, bound_vars: &mut BoundVars) -> Expr {
             expand_(context, *lhs, bound_vars),
             expand_(context, *rhs, bound_vars),
         ),
-        Expr::Lambda { param, body } => {
+        Expr::Lambda { param, body, .. } => {
             bound_vars.insert(param.clone());
             expr::l(param, expand_(context, *body, bound_vars))
         }
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            expand_(context, *body, bound_vars)
+        }
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            if recursive {
+                bound_vars.insert(name.clone());
+            }
+            let value = expand_(context, *value, bound_vars);
+            bound_vars.insert(name.clone());
+            Expr::Let {
+                recursive,
+                name,
+                value: Box::new(value),
+                body: Box::new(expand_(context, *body, bound_vars)),
+            }
+        }
     }
 }
 
@@ -0,0 +1,923 @@
+use super::normalization::Normalization;
+use super::strategy::ReductionStrategy;
+use super::target::ReductionTarget;
+use crate::calc::aliases::Aliases;
+use crate::context::Context;
+use crate::expr::{self, BoundVars, Identifier, Path};
+use std::collections::{HashMap, HashSet};
+
+type NodeId = usize;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Variable(Identifier),
+    Symbol(Identifier),
+    Apply(NodeId, NodeId),
+    Lambda(Identifier, NodeId),
+
+    /// let 式 (再帰的かどうか, 名前, 値, 本体)
+    Let(bool, Identifier, NodeId, NodeId),
+
+    /// 簡約済みのノード、簡約結果のノードを指す
+    Indirection(NodeId),
+}
+
+/// 部分式を共有するグラフとして式を表現する
+///
+/// 関数適用の際に引数は複製されず、同じノードが参照される
+/// 簡約基のノードは簡約結果を指す間接ノードで置き換えられるので
+/// 共有された部分式の簡約は一度しか行われない
+///
+/// 根から辿れなくなったノードは、ノード数が前回の回収時の倍を超えるたびに回収する
+#[derive(Clone, Debug, PartialEq)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    root: NodeId,
+    live: usize,
+
+    /// save_root で退避した根 (回収の対象にしない)
+    saved_root: Option<NodeId>,
+}
+
+// これより少ないノード数ではゴミを回収しない
+const MIN_COLLECTION: usize = 1024;
+
+impl Graph {
+    /// 共有を解いて木構造の式に戻す
+    ///
+    /// 共有された部分式はその都度複製されるので、必要な時だけ呼ぶ
+    pub fn expr(&self) -> expr::Expr {
+        self.to_expr(self.root)
+    }
+
+    /// 共有を解いた場合の式の大きさ (expr::Expr::size と同じ数え方)
+    pub fn size(&self) -> usize {
+        self.size_of(self.root, &mut HashMap::new())
+    }
+
+    // 評価戦略に従って簡約基に至る経路を返す
+    pub fn reducible_path(
+        &self,
+        context: &Context,
+        aliases: &Aliases,
+        strategy: &ReductionStrategy,
+        normalization: &Normalization,
+    ) -> Option<Path> {
+        let mut search = Search {
+            context,
+            aliases,
+            strategy,
+            normalization,
+            irreducible: HashSet::new(),
+        };
+        self.find(self.root, &mut search, &[])
+    }
+
+    pub fn reduce(&mut self, context: &Context, aliases: &Aliases, reducible_path: &Path) -> Path {
+        let mut id = self.root;
+        let mut path = reducible_path;
+        loop {
+            match path {
+                Path::Arg(index, next) => {
+                    let (_, args) = self.unapply(id);
+                    id = args[index - 1];
+                    path = next;
+                }
+                Path::Body(next) => {
+                    let (callee, _) = self.unapply(id);
+                    let Node::Lambda(_, body) = self.node(callee) else {
+                        unreachable!();
+                    };
+                    id = *body;
+                    path = next;
+                }
+                Path::Callee(_) => break,
+            }
+        }
+
+        let (callee, args) = self.unapply(id);
+        let arity = reducible_path.get_arity();
+
+        // callee に arity 個の引数を与えている適用のノード
+        // 引数を伴わない場合は callee 自身が簡約基になる
+        let redex = self.apply_node(id, args.len() - arity);
+
+        let result = match self.node(callee).clone() {
+            // 引数を伴わないラムダ抽象への経路は η簡約を表す
+            Node::Lambda(_, body) if arity == 0 => match self.node(body) {
+                Node::Apply(lhs, _) => self.resolve(*lhs),
+                _ => unreachable!(),
+            },
+
+            Node::Lambda(param, body) => {
+                let mut subst = Subst::new(vec![(param, args[0])]);
+                self.substitute(body, &mut subst, true)
+            }
+
+            // letrec f = M in N は N の中の f を letrec f = M in M に置き換える
+            Node::Let(recursive, name, value, body) => {
+                let value = if recursive {
+                    self.alloc(Node::Let(true, name.clone(), value, value))
+                } else {
+                    value
+                };
+                let mut subst = Subst::new(vec![(name, value)]);
+                self.substitute(body, &mut subst, true)
+            }
+
+            Node::Variable(id) => match aliases.get(&id) {
+                Some(alias) => {
+                    let mut subst = Subst::new(Vec::new());
+                    self.build(alias, &mut subst)
+                }
+                // 引数の足りない関数定義はラムダ抽象に展開する
+                None if context.get(&id).unwrap().arity() != arity => {
+                    let lambda = context.get(&id).unwrap().clone().into();
+                    let mut subst = Subst::new(Vec::new());
+                    self.build(&lambda, &mut subst)
+                }
+                None => {
+                    let func = context.get(&id).unwrap();
+                    let env = func.params().iter().cloned().zip(args).collect();
+                    let mut subst = Subst::new(env);
+                    self.build(func.body(), &mut subst)
+                }
+            },
+
+            _ => unreachable!(),
+        };
+
+        self.nodes[redex] = Node::Indirection(result);
+
+        let (_, args) = self.unapply(result);
+        let mut reduced_path = reducible_path.clone();
+        reduced_path.set_arity(args.len());
+
+        if self.nodes.len() > 2 * self.live.max(MIN_COLLECTION) {
+            self.collect_garbage();
+        }
+
+        reduced_path
+    }
+
+    /// 根から辿れないノードを取り除き、間接ノードを飛ばして詰め直す
+    pub fn collect_garbage(&mut self) {
+        let mut nodes = Vec::new();
+        let mut moved = HashMap::new();
+        self.root = self.move_node(self.root, &mut nodes, &mut moved);
+        self.saved_root = self
+            .saved_root
+            .map(|id| self.move_node(id, &mut nodes, &mut moved));
+        self.nodes = nodes;
+        self.live = self.nodes.len();
+    }
+
+    // ---------------------------------------------------------------------- //
+    // 以下は LazyK が簡約の合間にグラフを直接操作するのに使う
+
+    /// 根の先頭の項が変数かシンボルであれば、その項と引数の個数を返す
+    pub(crate) fn head(&self) -> Option<(expr::Expr, usize)> {
+        let (callee, args) = self.unapply(self.root);
+        match self.node(callee) {
+            Node::Variable(id) => Some((expr::Expr::Variable(id.clone()), args.len())),
+            Node::Symbol(id) => Some((expr::Expr::Symbol(id.clone()), args.len())),
+            _ => None,
+        }
+    }
+
+    /// 根を index 番目 (1 始まり) の引数に付け替える
+    pub(crate) fn select_arg(&mut self, index: usize) {
+        let (_, args) = self.unapply(self.root);
+        self.root = args[index - 1];
+    }
+
+    /// 根に arg を適用した式を新しい根にする
+    pub(crate) fn apply(&mut self, arg: &expr::Expr) {
+        let mut subst = Subst::new(Vec::new());
+        let arg = self.build(arg, &mut subst);
+        self.root = self.alloc(Node::Apply(self.root, arg));
+    }
+
+    /// 今の根を退避する (退避した根から辿れるノードも回収されない)
+    pub(crate) fn save_root(&mut self) {
+        self.saved_root = Some(self.root);
+    }
+
+    /// save_root で退避した根に戻す
+    pub(crate) fn restore_root(&mut self) {
+        if let Some(id) = self.saved_root.take() {
+            self.root = id;
+        }
+    }
+
+    /// 自由変数 id をすべて expr で置き換える (id はどのラムダ抽象でも束縛されていない名前に限る)
+    ///
+    /// 変数のノードを expr を指す間接ノードにするので、置き換えた先は共有される
+    pub(crate) fn replace_free(&mut self, id: &Identifier, expr: &expr::Expr) {
+        let vars: Vec<NodeId> = (0..self.nodes.len())
+            .filter(|i| matches!(&self.nodes[*i], Node::Variable(var) if var == id))
+            .collect();
+        let mut subst = Subst::new(Vec::new());
+        let replacement = self.build(expr, &mut subst);
+        for var in vars {
+            self.nodes[var] = Node::Indirection(replacement);
+        }
+    }
+
+    // ---------------------------------------------------------------------- //
+
+    fn alloc(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    fn resolve(&self, mut id: NodeId) -> NodeId {
+        while let Node::Indirection(next) = self.nodes[id] {
+            id = next;
+        }
+        id
+    }
+
+    fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[self.resolve(id)]
+    }
+
+    // 関数適用の左側をたどって、先頭の項と (左から順に並べた) 引数を返す
+    fn unapply(&self, id: NodeId) -> (NodeId, Vec<NodeId>) {
+        let mut callee = self.resolve(id);
+        let mut args = Vec::new();
+
+        while let Node::Apply(lhs, rhs) = self.nodes[callee] {
+            args.push(rhs);
+            callee = self.resolve(lhs);
+        }
+
+        args.reverse();
+        (callee, args)
+    }
+
+    // 関数適用の左側を depth 回たどった先のノードを返す
+    fn apply_node(&self, id: NodeId, depth: usize) -> NodeId {
+        let mut id = self.resolve(id);
+        for _ in 0..depth {
+            if let Node::Apply(lhs, _) = self.nodes[id] {
+                id = self.resolve(lhs);
+            }
+        }
+        id
+    }
+
+    fn arity(&self, context: &Context, aliases: &Aliases, callee: NodeId) -> Option<usize> {
+        match self.node(callee) {
+            Node::Lambda(..) => Some(1),
+            Node::Let(..) => Some(0),
+            Node::Variable(id) => {
+                if aliases.has(id) {
+                    Some(0)
+                } else {
+                    context.get(id).map(|f| f.arity())
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // bound はラムダ抽象の内側を探す際に、外側のラムダ抽象で束縛された変数
+    fn find(&self, id: NodeId, search: &mut Search, bound: &[Identifier]) -> Option<Path> {
+        let id = self.resolve(id);
+        if search.irreducible.contains(&id) {
+            return None;
+        }
+
+        let (callee, args) = self.unapply(id);
+
+        // エイリアスと let 式は引数を伴わなくても簡約する
+        // 束縛された変数は同名の関数定義やエイリアスを指さない
+        let is_nullary = match self.node(callee) {
+            Node::Variable(id) => search.aliases.has(id),
+            Node::Let(..) => true,
+            _ => false,
+        };
+        let is_bound = matches!(self.node(callee), Node::Variable(id) if bound.contains(id));
+        let arity = self
+            .arity(search.context, search.aliases, callee)
+            .filter(|arity| !is_bound && (is_nullary || !args.is_empty()) && *arity <= args.len());
+
+        let path = if let Some(arity) = arity {
+            let path = if search.strategy.reduces_args_first() {
+                self.find_in_args(&args[..arity], search, bound)
+            } else {
+                None
+            };
+            path.or(Some(Path::Callee(arity)))
+        } else {
+            let normalization = search.normalization;
+            match normalization.target {
+                // 先頭が簡約できなければ弱頭部正規形に達している
+                ReductionTarget::WeakHeadNormalForm => None,
+                // 頭部正規形は先頭のラムダ抽象の内側の先頭も簡約できない形
+                ReductionTarget::HeadNormalForm => self.find_in_head(callee, &args, search, bound),
+                // 正規形は頭部正規形より強いので、先頭は頭部正規形と同じく簡約する
+                // 強正規化のときは評価戦略によらず引数の中も簡約する
+                ReductionTarget::NormalForm => {
+                    let reduces_args = normalization.strong || search.strategy.reduces_stuck_args();
+                    let mut path = None;
+                    if reduces_args && search.strategy.reduces_args_first() {
+                        path = self.find_in_args(&args, search, bound);
+                    }
+                    if path.is_none() {
+                        path = self.find_in_head(callee, &args, search, bound);
+                    }
+                    if path.is_none() && reduces_args {
+                        path = self.find_in_args(&args, search, bound);
+                    }
+                    path
+                }
+            }
+        };
+
+        if path.is_none() {
+            search.irreducible.insert(id);
+        }
+        path
+    }
+
+    // 引数を与えても簡約できない先頭の項の中の簡約基に至る経路を返す
+    //
+    // 引数を伴わないラムダ抽象は本体の中を簡約し、強正規化の際は引数の足りない関数定義も展開する
+    fn find_in_head(
+        &self,
+        callee: NodeId,
+        args: &[NodeId],
+        search: &mut Search,
+        bound: &[Identifier],
+    ) -> Option<Path> {
+        match self.node(callee) {
+            Node::Variable(id)
+                if search.normalization.strong
+                    && !bound.contains(id)
+                    && search.context.get(id).is_some() =>
+            {
+                Some(Path::Callee(0))
+            }
+            Node::Lambda(param, body) if args.is_empty() => {
+                if search.normalization.eta && self.is_eta_redex(param, *body) {
+                    return Some(Path::Callee(0));
+                }
+
+                let mut bound = bound.to_vec();
+                bound.push(param.clone());
+                self.find(*body, search, &bound)
+                    .map(|path| Path::Body(Box::new(path)))
+            }
+            _ => None,
+        }
+    }
+
+    fn find_in_args(
+        &self,
+        args: &[NodeId],
+        search: &mut Search,
+        bound: &[Identifier],
+    ) -> Option<Path> {
+        args.iter().enumerate().find_map(|(index, arg)| {
+            self.find(*arg, search, bound)
+                .map(|path| Path::Arg(index + 1, Box::new(path)))
+        })
+    }
+
+    // param => body が η簡約できるかどうか
+    fn is_eta_redex(&self, param: &Identifier, body: NodeId) -> bool {
+        match self.node(body) {
+            Node::Apply(lhs, rhs) => {
+                matches!(self.node(*rhs), Node::Variable(var) if var == param)
+                    && !self.free_vars(*lhs, &mut HashMap::new()).contains(param)
+            }
+            _ => false,
+        }
+    }
+
+    // ノードとそこから辿れるノードを nodes に移し、移した先の番号を返す
+    fn move_node(
+        &self,
+        id: NodeId,
+        nodes: &mut Vec<Node>,
+        moved: &mut HashMap<NodeId, NodeId>,
+    ) -> NodeId {
+        let id = self.resolve(id);
+        if let Some(new_id) = moved.get(&id) {
+            return *new_id;
+        }
+
+        let node = match self.nodes[id].clone() {
+            Node::Apply(lhs, rhs) => {
+                let lhs = self.move_node(lhs, nodes, moved);
+                let rhs = self.move_node(rhs, nodes, moved);
+                Node::Apply(lhs, rhs)
+            }
+            Node::Lambda(param, body) => Node::Lambda(param, self.move_node(body, nodes, moved)),
+            Node::Let(recursive, name, value, body) => {
+                let value = self.move_node(value, nodes, moved);
+                let body = self.move_node(body, nodes, moved);
+                Node::Let(recursive, name, value, body)
+            }
+            node => node,
+        };
+
+        nodes.push(node);
+        let new_id = nodes.len() - 1;
+        moved.insert(id, new_id);
+        new_id
+    }
+
+    fn size_of(&self, id: NodeId, memo: &mut HashMap<NodeId, usize>) -> usize {
+        let id = self.resolve(id);
+        if let Some(size) = memo.get(&id) {
+            return *size;
+        }
+
+        let size = match &self.nodes[id] {
+            Node::Variable(_) | Node::Symbol(_) => 1,
+            Node::Apply(lhs, rhs) => 1usize
+                .saturating_add(self.size_of(*lhs, memo))
+                .saturating_add(self.size_of(*rhs, memo)),
+            Node::Lambda(_, body) => 1usize.saturating_add(self.size_of(*body, memo)),
+            Node::Let(_, _, value, body) => 1usize
+                .saturating_add(self.size_of(*value, memo))
+                .saturating_add(self.size_of(*body, memo)),
+            Node::Indirection(_) => unreachable!(),
+        };
+        memo.insert(id, size);
+        size
+    }
+
+    fn to_expr(&self, id: NodeId) -> expr::Expr {
+        match self.node(id) {
+            Node::Variable(id) => expr::Expr::Variable(id.clone()),
+            Node::Symbol(id) => expr::Expr::Symbol(id.clone()),
+            Node::Apply(lhs, rhs) => expr::a(self.to_expr(*lhs), self.to_expr(*rhs)),
+            Node::Lambda(param, body) => expr::l(param.clone(), self.to_expr(*body)),
+            Node::Let(recursive, name, value, body) => expr::Expr::Let {
+                recursive: *recursive,
+                name: name.clone(),
+                value: Box::new(self.to_expr(*value)),
+                body: Box::new(self.to_expr(*body)),
+            },
+            Node::Indirection(_) => unreachable!(),
+        }
+    }
+
+    fn free_vars(
+        &self,
+        id: NodeId,
+        memo: &mut HashMap<NodeId, HashSet<Identifier>>,
+    ) -> HashSet<Identifier> {
+        let id = self.resolve(id);
+        if let Some(vars) = memo.get(&id) {
+            return vars.clone();
+        }
+
+        let vars = match &self.nodes[id] {
+            Node::Variable(var) => HashSet::from([var.clone()]),
+            Node::Symbol(_) => HashSet::new(),
+            Node::Apply(lhs, rhs) => {
+                let mut vars = self.free_vars(*lhs, memo);
+                vars.extend(self.free_vars(*rhs, memo));
+                vars
+            }
+            Node::Lambda(param, body) => {
+                let mut vars = self.free_vars(*body, memo);
+                vars.remove(param);
+                vars
+            }
+            Node::Let(recursive, name, value, body) => {
+                let mut vars = self.free_vars(*body, memo);
+                if *recursive {
+                    vars.extend(self.free_vars(*value, memo));
+                    vars.remove(name);
+                } else {
+                    vars.remove(name);
+                    vars.extend(self.free_vars(*value, memo));
+                }
+                vars
+            }
+            Node::Indirection(_) => unreachable!(),
+        };
+
+        memo.insert(id, vars.clone());
+        vars
+    }
+
+    // 引数のノード中の自由変数、束縛変数のリネーム時に避けるべき名前
+    fn avoid<'a>(&self, subst: &'a mut Subst) -> &'a HashSet<Identifier> {
+        if subst.avoid.is_none() {
+            let mut memo = HashMap::new();
+            let mut vars = HashSet::new();
+            for (_, arg) in subst.env.iter() {
+                vars.extend(self.free_vars(*arg, &mut memo));
+            }
+            subst.avoid = Some(vars);
+        }
+        subst.avoid.as_ref().unwrap()
+    }
+
+    // 式をグラフに変換する、その際 subst に従って変数をノードに置き換える
+    fn build(&mut self, expr: &expr::Expr, subst: &mut Subst) -> NodeId {
+        match expr {
+            expr::Expr::Variable(id) => match subst.lookup(id) {
+                Some(node) => node,
+                None => self.alloc(Node::Variable(id.clone())),
+            },
+            expr::Expr::Symbol(id) => self.alloc(Node::Symbol(id.clone())),
+            expr::Expr::Apply { lhs, rhs } => {
+                let lhs = self.build(lhs, subst);
+                let rhs = self.build(rhs, subst);
+                self.alloc(Node::Apply(lhs, rhs))
+            }
+            expr::Expr::Lambda { param, body, .. } => {
+                let param = self.enter(param, subst);
+                let body = self.build(body, subst);
+                subst.leave();
+                self.alloc(Node::Lambda(param, body))
+            }
+            // 型は取り除く
+            expr::Expr::TypeLambda { body, .. } | expr::Expr::TypeApply { lhs: body, .. } => {
+                self.build(body, subst)
+            }
+            expr::Expr::Let {
+                recursive,
+                name,
+                value,
+                body,
+            } => {
+                // letrec の値は name の内側、let の値は外側にある
+                let outer_value = if *recursive {
+                    None
+                } else {
+                    Some(self.build(value, subst))
+                };
+                let name = self.enter(name, subst);
+                let value = match outer_value {
+                    Some(value) => value,
+                    None => self.build(value, subst),
+                };
+                let body = self.build(body, subst);
+                subst.leave();
+                self.alloc(Node::Let(*recursive, name, value, body))
+            }
+        }
+    }
+
+    // ノードに対して subst に従った置換を行う
+    // 置換が起きない部分はノードを複製せずにそのまま共有する
+    fn substitute(&mut self, id: NodeId, subst: &mut Subst, memoize: bool) -> NodeId {
+        let id = self.resolve(id);
+        if memoize {
+            if let Some(result) = subst.memo.get(&id) {
+                return *result;
+            }
+        }
+
+        let result = match self.nodes[id].clone() {
+            Node::Variable(var) => subst.lookup(&var).unwrap_or(id),
+            Node::Symbol(_) => id,
+            Node::Apply(lhs, rhs) => {
+                let new_lhs = self.substitute(lhs, subst, memoize);
+                let new_rhs = self.substitute(rhs, subst, memoize);
+                if new_lhs == self.resolve(lhs) && new_rhs == self.resolve(rhs) {
+                    id
+                } else {
+                    self.alloc(Node::Apply(new_lhs, new_rhs))
+                }
+            }
+            Node::Lambda(param, body) => {
+                let new_param = self.enter(&param, subst);
+                let new_body = self.substitute(body, subst, false);
+                subst.leave();
+                if new_param == param && new_body == self.resolve(body) {
+                    id
+                } else {
+                    self.alloc(Node::Lambda(new_param, new_body))
+                }
+            }
+            Node::Let(recursive, name, value, body) => {
+                let outer_value = if recursive {
+                    None
+                } else {
+                    Some(self.substitute(value, subst, memoize))
+                };
+                let new_name = self.enter(&name, subst);
+                let new_value = match outer_value {
+                    Some(value) => value,
+                    None => self.substitute(value, subst, false),
+                };
+                let new_body = self.substitute(body, subst, false);
+                subst.leave();
+                if new_name == name
+                    && new_value == self.resolve(value)
+                    && new_body == self.resolve(body)
+                {
+                    id
+                } else {
+                    self.alloc(Node::Let(recursive, new_name, new_value, new_body))
+                }
+            }
+            Node::Indirection(_) => unreachable!(),
+        };
+
+        if memoize {
+            subst.memo.insert(id, result);
+        }
+        result
+    }
+
+    // ラムダ抽象の内側に入る際の置換の更新
+    // 引数の中の自由変数と衝突する束縛変数はリネームする (α変換)
+    fn enter(&mut self, param: &Identifier, subst: &mut Subst) -> Identifier {
+        if !subst.is_active() || !self.avoid(subst).contains(param) {
+            subst.enter(param.clone(), None);
+            return param.clone();
+        }
+
+        let mut vars = BoundVars::new();
+        for var in self
+            .avoid(subst)
+            .clone()
+            .into_iter()
+            .chain(subst.bound.clone())
+        {
+            vars.insert(var);
+        }
+        let new_param = param.rename(&vars);
+
+        let node = self.alloc(Node::Variable(new_param.clone()));
+        subst.enter(new_param.clone(), Some((param.clone(), node)));
+        new_param
+    }
+}
+
+impl From<expr::Expr> for Graph {
+    fn from(expr: expr::Expr) -> Self {
+        let mut graph = Graph {
+            nodes: Vec::new(),
+            root: 0,
+            live: 0,
+            saved_root: None,
+        };
+        let mut subst = Subst::new(Vec::new());
+        graph.root = graph.build(&expr, &mut subst);
+        graph.live = graph.nodes.len();
+        graph
+    }
+}
+
+// ========================================================================== //
+
+// 簡約基を探す際に持ち回る設定と、簡約基を含まないと分かったノード
+struct Search<'a> {
+    context: &'a Context,
+    aliases: &'a Aliases,
+    strategy: &'a ReductionStrategy,
+    normalization: &'a Normalization,
+    irreducible: HashSet<NodeId>,
+}
+
+// 変数からノードへの置換
+struct Subst {
+    env: Vec<(Identifier, NodeId)>,
+    avoid: Option<HashSet<Identifier>>,
+    bound: Vec<Identifier>,
+    scopes: Vec<ScopeEntry>,
+    memo: HashMap<NodeId, NodeId>,
+}
+
+// ラムダ抽象の内側に入る際に env に加えた変更、外側に出る際に元に戻す
+struct ScopeEntry {
+    shadowed: Vec<(Identifier, NodeId)>,
+    renamed: bool,
+}
+
+impl Subst {
+    fn new(env: Vec<(Identifier, NodeId)>) -> Self {
+        Self {
+            env,
+            avoid: None,
+            bound: Vec::new(),
+            scopes: Vec::new(),
+            memo: HashMap::new(),
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        !self.env.is_empty()
+    }
+
+    fn lookup(&self, id: &Identifier) -> Option<NodeId> {
+        self.env
+            .iter()
+            .rev()
+            .find(|(var, _)| var == id)
+            .map(|(_, node)| *node)
+    }
+
+    fn enter(&mut self, param: Identifier, renamed: Option<(Identifier, NodeId)>) {
+        // 束縛変数と同名の変数は置換の対象から外れる
+        let shadowed_by = renamed
+            .as_ref()
+            .map(|(old, _)| old)
+            .unwrap_or(&param)
+            .clone();
+        let (shadowed, env): (Vec<_>, Vec<_>) =
+            self.env.drain(..).partition(|(var, _)| *var == shadowed_by);
+        self.env = env;
+
+        let is_renamed = renamed.is_some();
+        if let Some(entry) = renamed {
+            self.env.push(entry);
+        }
+
+        self.bound.push(param);
+        self.scopes.push(ScopeEntry {
+            shadowed,
+            renamed: is_renamed,
+        });
+    }
+
+    fn leave(&mut self) {
+        let scope = self.scopes.pop().unwrap();
+        self.bound.pop();
+        if scope.renamed {
+            self.env.pop();
+        }
+        self.env.extend(scope.shadowed);
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+    use crate::func;
+
+    fn setup() -> (Context, Aliases) {
+        let i = func::new("i", vec!["x"], "x");
+        let k = func::new("k", vec!["x", "y"], "x");
+        let s = func::new(
+            "s",
+            vec!["x", "y", "z"],
+            expr::a(expr::a("x", "z"), expr::a("y", "z")),
+        );
+
+        (Context::from(vec![i, k, s]), Aliases::new())
+    }
+
+    #[test]
+    fn test_from_expr() {
+        let expr = expr::a(expr::l("x", expr::a("x", ":a")), expr::a("i", ":b"));
+        let graph = Graph::from(expr.clone());
+        assert_eq!(graph.expr(), expr);
+    }
+
+    #[test]
+    fn test_reduce_shares_argument() {
+        let (context, aliases) = setup();
+        let strategy = ReductionStrategy::NormalOrder;
+
+        // `^x.`xx`i:a
+        let expr = expr::a(expr::l("x", expr::a("x", "x")), expr::a("i", ":a"));
+        let mut graph = Graph::from(expr);
+
+        let path = graph
+            .reducible_path(&context, &aliases, &strategy, &Normalization::default())
+            .unwrap();
+        graph.reduce(&context, &aliases, &path);
+        assert_eq!(
+            graph.expr(),
+            expr::a(expr::a("i", ":a"), expr::a("i", ":a"))
+        );
+
+        // 共有された `i:a は一度の簡約で両方とも :a になる
+        let path = graph
+            .reducible_path(&context, &aliases, &strategy, &Normalization::default())
+            .unwrap();
+        let reduced_path = graph.reduce(&context, &aliases, &path);
+        assert_eq!(graph.expr(), expr::a(":a", ":a"));
+        assert_eq!(Vec::<usize>::from(&reduced_path), vec![0]);
+
+        assert_eq!(
+            graph.reducible_path(&context, &aliases, &strategy, &Normalization::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reduce_avoids_capture() {
+        let (context, aliases) = setup();
+        let strategy = ReductionStrategy::NormalOrder;
+
+        // `^x.^y.`xy y => ^Y.`yY
+        let expr = expr::a(expr::l("x", expr::l("y", expr::a("x", "y"))), "y");
+        let mut graph = Graph::from(expr);
+
+        let path = graph
+            .reducible_path(&context, &aliases, &strategy, &Normalization::default())
+            .unwrap();
+        graph.reduce(&context, &aliases, &path);
+        assert_eq!(graph.expr(), expr::l("Y", expr::a("y", "Y")));
+    }
+
+    #[test]
+    fn test_reduce_func() {
+        let (context, aliases) = setup();
+        let strategy = ReductionStrategy::NormalOrder;
+
+        // ```s:a:b:c => ``:a:c`:b:c
+        let expr = expr::a(expr::a(expr::a("s", ":a"), ":b"), ":c");
+        let mut graph = Graph::from(expr);
+
+        let path = graph
+            .reducible_path(&context, &aliases, &strategy, &Normalization::default())
+            .unwrap();
+        assert_eq!(Vec::<usize>::from(&path), vec![3]);
+
+        let reduced_path = graph.reduce(&context, &aliases, &path);
+        assert_eq!(
+            graph.expr(),
+            expr::a(expr::a(":a", ":c"), expr::a(":b", ":c"))
+        );
+        assert_eq!(Vec::<usize>::from(&reduced_path), vec![2]);
+    }
+
+    #[test]
+    fn test_collect_garbage() {
+        let (context, aliases) = setup();
+        let strategy = ReductionStrategy::NormalOrder;
+        let normalization = Normalization::default();
+
+        // (x => x(x))(x => x(x)) は簡約しても同じ式に戻るので、回収すればノード数は増え続けない
+        let omega = || expr::l("x", expr::a("x", "x"));
+        let mut graph = Graph::from(expr::a(omega(), omega()));
+        for _ in 0..10000 {
+            let path = graph
+                .reducible_path(&context, &aliases, &strategy, &normalization)
+                .unwrap();
+            graph.reduce(&context, &aliases, &path);
+            assert!(graph.nodes.len() <= 2 * MIN_COLLECTION);
+        }
+        assert_eq!(graph.expr(), expr::a(omega(), omega()));
+
+        // 共有された部分式は回収後も共有されたまま
+        let expr = expr::a(expr::l("x", expr::a("x", "x")), expr::a("i", ":a"));
+        let mut graph = Graph::from(expr);
+        let path = graph
+            .reducible_path(&context, &aliases, &strategy, &normalization)
+            .unwrap();
+        graph.reduce(&context, &aliases, &path);
+        graph.collect_garbage();
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(
+            graph.expr(),
+            expr::a(expr::a("i", ":a"), expr::a("i", ":a"))
+        );
+        assert_eq!(graph.size(), graph.expr().size());
+    }
+
+    #[test]
+    fn test_save_root() {
+        // 退避した根は回収されず、書き換えた変数は退避した側からも見える
+        let mut graph = Graph::from(expr::a(expr::a("x", ":a"), "x"));
+        graph.save_root();
+        graph.select_arg(2);
+        assert_eq!(graph.head(), Some((expr::v("x"), 0)));
+        graph.apply(&expr::s("b"));
+        graph.collect_garbage();
+        assert_eq!(graph.expr(), expr::a("x", ":b"));
+
+        graph.replace_free(&"x".into(), &expr::s("f"));
+        assert_eq!(graph.head(), Some((expr::s("f"), 1)));
+        graph.restore_root();
+        graph.collect_garbage();
+        assert_eq!(graph.expr(), expr::a(expr::a(":f", ":a"), ":f"));
+    }
+
+    #[test]
+    fn test_size() {
+        // 共有された部分式は共有を解いた式と同じく重複して数える
+        let expr = expr::a(expr::l("x", expr::a("x", "x")), expr::l("y", ":a"));
+        let mut graph = Graph::from(expr);
+        let (context, aliases) = setup();
+        let path = graph
+            .reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+            )
+            .unwrap();
+        graph.reduce(&context, &aliases, &path);
+        assert_eq!(graph.size(), graph.expr().size());
+        assert_eq!(graph.size(), 5);
+    }
+}
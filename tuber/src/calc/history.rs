@@ -0,0 +1,290 @@
+use super::reducer::{ReduceResult, Reducer};
+use super::strategy::ReductionStrategy;
+use super::target::ReductionTarget;
+use super::termination::Termination;
+use crate::expr::{Expr, Path};
+
+/// 簡約の経過を記録し、任意のステップの式に戻れるようにした Reducer
+///
+/// 各ステップは式全体ではなく、書き換えた部分式とその経路の差分として記録する
+/// 記録した範囲を越えて進むと Reducer で簡約を続ける
+pub struct ReductionHistory {
+    reducer: Reducer,
+    diffs: Vec<Diff>,
+    expr: Expr,
+    position: usize,
+    reached: Option<ReductionTarget>,
+}
+
+// 1ステップの簡約で書き換えた部分式
+struct Diff {
+    // 簡約前の式の中で簡約基を指す経路と簡約基
+    reducible_path: Path,
+    redex: Expr,
+    // 簡約後の式の中で簡約結果を指す経路と簡約結果
+    reduced_path: Path,
+    reduced: Expr,
+    reached: Option<ReductionTarget>,
+}
+
+impl ReductionHistory {
+    /// 簡約を始める前の Reducer から作る
+    pub fn new(reducer: Reducer) -> Self {
+        Self {
+            expr: reducer.expr(),
+            reached: reducer.reached().filter(|_| reducer.reports_reached()),
+            reducer,
+            diffs: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// 現在のステップの式
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// 現在のステップ数
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// 記録したステップ数
+    pub fn recorded_steps(&self) -> usize {
+        self.diffs.len()
+    }
+
+    /// 簡約を続ける Reducer (記録した最後のステップの式を保持している)
+    pub fn reducer(&self) -> &Reducer {
+        &self.reducer
+    }
+
+    /// 現在のステップの式の中で、次に簡約する簡約基に至る経路
+    pub fn reducible_path(&self) -> Option<Path> {
+        match self.diffs.get(self.position) {
+            Some(diff) => Some(diff.reducible_path.clone()),
+            None => self.reducer.reducible_path(),
+        }
+    }
+
+    /// 現在のステップの式の中で、直前のステップで簡約した位置
+    pub fn reduced_path(&self) -> Option<&Path> {
+        let index = self.position.checked_sub(1)?;
+        Some(&self.diffs[index].reduced_path)
+    }
+
+    /// 現在のステップの式が達している最も強い形
+    ///
+    /// Reducer の ReduceResult と同様に、目標を指定した場合のみ記録する
+    pub fn reached(&self) -> Option<ReductionTarget> {
+        match self.position.checked_sub(1) {
+            Some(index) => self.diffs[index].reached,
+            None => self.reached,
+        }
+    }
+
+    /// 簡約列が終了した理由
+    ///
+    /// 記録した最後のステップにいて、簡約列が終了しているときのみ返す
+    pub fn termination(&self) -> Option<&Termination> {
+        if self.position == self.diffs.len() {
+            self.reducer.termination()
+        } else {
+            None
+        }
+    }
+
+    /// 1ステップ戻る
+    ///
+    /// 最初の式にいて戻れなければ false を返す
+    pub fn back(&mut self) -> bool {
+        let Some(index) = self.position.checked_sub(1) else {
+            return false;
+        };
+
+        let diff = &self.diffs[index];
+        *self.expr.subexpr_mut(&diff.reduced_path).unwrap() = diff.redex.clone();
+        self.position = index;
+        true
+    }
+
+    /// step 番目の式に移動する
+    ///
+    /// 記録した範囲を越える場合は簡約を進める
+    /// 簡約列が step 番目より前に終了した場合は、最後のステップに移動する
+    ///
+    /// 移動した先のステップ数を返す
+    pub fn seek(&mut self, step: usize) -> usize {
+        while step < self.position && self.back() {}
+        while self.position < step && self.next().is_some() {}
+        self.position
+    }
+
+    /// step 番目の式から、別の評価戦略で簡約をやり直す
+    ///
+    /// step 番目より後の記録は捨てる
+    pub fn branch(&mut self, step: usize, strategy: ReductionStrategy) {
+        self.seek(step);
+        self.diffs.truncate(self.position);
+        self.reducer.restart(self.expr.clone(), self.position);
+        self.reducer.set_strategy(strategy);
+    }
+
+    // Reducer で1ステップ簡約し、その差分を記録する
+    //
+    // 簡約基は Reducer を進める前に、記録している式から取り出しておく
+    fn record(&mut self) -> Option<ReduceResult> {
+        let redex = self.reducer.peek_path().map(|path| {
+            let redex = self
+                .expr
+                .subexpr(&path)
+                .expect("reducible path must point into the recorded expression")
+                .clone();
+            (path, redex)
+        });
+
+        let result = self.reducer.next()?;
+        let (reducible_path, redex) =
+            redex.expect("reducer must not reduce without a reducible path");
+        let reduced = result
+            .expr
+            .subexpr(&result.reduced_path)
+            .expect("reduced path must point into the reduced expression")
+            .clone();
+
+        self.diffs.push(Diff {
+            reducible_path,
+            redex,
+            reduced_path: result.reduced_path.clone(),
+            reduced,
+            reached: result.reached,
+        });
+        self.expr = result.expr.clone();
+        self.position += 1;
+        Some(result)
+    }
+}
+
+impl Iterator for ReductionHistory {
+    type Item = ReduceResult;
+
+    /// 1ステップ進める
+    ///
+    /// 記録した範囲では記録した差分を当てはめ、その先では簡約を進める
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(diff) = self.diffs.get(self.position) else {
+            return self.record();
+        };
+
+        *self.expr.subexpr_mut(&diff.reducible_path).unwrap() = diff.reduced.clone();
+        self.position += 1;
+        Some(ReduceResult {
+            step: self.position,
+            expr: self.expr.clone(),
+            reduced_path: diff.reduced_path.clone(),
+            reached: diff.reached,
+        })
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{Aliases, ReductionBackend};
+    use crate::context::Context;
+    use crate::expr;
+
+    fn new_history(expr: Expr, backend: ReductionBackend) -> ReductionHistory {
+        let mut reducer = Reducer::new(
+            Context::default(),
+            Aliases::new(),
+            expr,
+            ReductionStrategy::NormalOrder,
+        );
+        reducer.set_backend(backend);
+        reducer.set_target(ReductionTarget::NormalForm);
+        ReductionHistory::new(reducer)
+    }
+
+    // s(k, k, i(:a)) と、正規順序で簡約した経過
+    fn skk() -> (Expr, Vec<Expr>) {
+        let expr = expr::a(expr::a(expr::a("s", "k"), "k"), expr::a("i", ":a"));
+        let steps = vec![
+            expr::a(
+                expr::a("k", expr::a("i", ":a")),
+                expr::a("k", expr::a("i", ":a")),
+            ),
+            expr::a("i", ":a"),
+            expr::s("a"),
+        ];
+        (expr, steps)
+    }
+
+    #[test]
+    fn test_seek_and_back() {
+        for backend in [ReductionBackend::Tree, ReductionBackend::Graph] {
+            let (expr, steps) = skk();
+            let mut history = new_history(expr.clone(), backend);
+
+            let exprs: Vec<_> = history.by_ref().map(|result| result.expr).collect();
+            assert_eq!(exprs, steps);
+            assert_eq!(history.recorded_steps(), 3);
+            assert_eq!(history.termination(), Some(&Termination::NormalForm));
+
+            assert!(history.back());
+            assert_eq!(history.expr(), &steps[1]);
+            assert_eq!(history.termination(), None);
+
+            assert_eq!(history.seek(0), 0);
+            assert_eq!(history.expr(), &expr);
+            assert_eq!(history.reduced_path(), None);
+            assert!(!history.back());
+
+            // 記録した差分を当てはめて進む
+            let result = history.next().unwrap();
+            assert_eq!(result.step, 1);
+            assert_eq!(result.expr, steps[0]);
+            assert_eq!(history.seek(10), 3);
+            assert_eq!(history.expr(), &steps[2]);
+            assert_eq!(history.reached(), Some(ReductionTarget::NormalForm));
+        }
+    }
+
+    #[test]
+    fn test_seek_beyond_recorded() {
+        let (expr, steps) = skk();
+        let mut history = new_history(expr, ReductionBackend::Tree);
+
+        assert_eq!(history.seek(2), 2);
+        assert_eq!(history.recorded_steps(), 2);
+        assert_eq!(history.expr(), &steps[1]);
+        assert_eq!(history.reducible_path(), Some(Path::Callee(1)));
+    }
+
+    #[test]
+    fn test_branch() {
+        for backend in [ReductionBackend::Tree, ReductionBackend::Graph] {
+            let (expr, steps) = skk();
+            let mut history = new_history(expr, backend);
+            history.seek(3);
+
+            // 1ステップ目から値呼びでやり直すと、引数を先に簡約する
+            history.branch(1, ReductionStrategy::CallByValue);
+            assert_eq!(history.recorded_steps(), 1);
+            assert_eq!(history.expr(), &steps[0]);
+
+            let results: Vec<_> = history.by_ref().collect();
+            assert_eq!(results[0].step, 2);
+            assert_eq!(
+                results[0].expr,
+                expr::a(expr::a("k", ":a"), expr::a("k", expr::a("i", ":a")))
+            );
+            assert_eq!(results.last().unwrap().expr, expr::s("a"));
+
+            assert_eq!(history.seek(0), 0);
+            assert_eq!(history.seek(10), 1 + results.len());
+        }
+    }
+}
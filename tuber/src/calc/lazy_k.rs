@@ -0,0 +1,379 @@
+use super::{Aliases, Reducer, ReductionBackend, ReductionStrategy, ReductionTarget, Termination};
+use crate::context::Context;
+use crate::expr::{self, church_numeral, Expr, Identifier};
+use std::fmt::Display;
+
+// 入力のうちまだ読み込んでいない残りのリストを表す変数 (利用者が書く式と衝突しないよう、パースできない名前にする)
+const INPUT: &str = "#input";
+
+// 出力の Church 数を読み取るためのシンボル
+const PROBE_F: &str = "#f";
+const PROBE_X: &str = "#x";
+
+/// 入力が尽きた後に続く値
+const EOF: usize = 256;
+
+/// Lazy K のプログラムの実行に失敗した理由
+#[derive(Clone, Debug, PartialEq)]
+pub enum LazyKError {
+    /// 簡約ステップ数の合計が上限に達した
+    StepLimit { max_steps: usize },
+
+    /// 簡約中の式の大きさが上限を超えた
+    SizeLimit { size: usize },
+
+    /// 出力リストの要素が Church 数ではなかった
+    NotNumeral(Expr),
+}
+
+impl Display for LazyKError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LazyKError::StepLimit { max_steps } => {
+                write!(f, "stopped after {} reduction steps", max_steps)
+            }
+            LazyKError::SizeLimit { size } => {
+                write!(f, "stopped because the expression grew to size {}", size)
+            }
+            LazyKError::NotNumeral(expr) => {
+                write!(f, "an element of the output is not a numeral: {}", expr)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LazyKError {}
+
+/// Lazy K のプログラムを実行し、出力をバイト列として1つずつ返す
+///
+/// プログラムは入力のバイト列を Church 数のリスト (`CONS` と同じ `f => f(x, y)` による対) として受け取り、
+/// 出力のリストを返す関数として扱う
+/// 入力は出力を読み取るのに必要になった時点で1バイトずつ読み込み、尽きた後は 256 が無限に続く
+/// 出力のリストの要素が 256 以上になると実行を終え、その値から 256 を引いたものを終了コードとする
+pub struct LazyK<I: Iterator<Item = u8>> {
+    /// 実行全体を通して1つのグラフを簡約し、出力の要素の間で部分式の共有を保つ
+    ///
+    /// グラフの根は出力のうちまだ読み取っていない残りのリストで、
+    /// 要素を読み取る間だけ退避しておき、読み取り終えたら戻す
+    reducer: Reducer,
+    input: I,
+
+    /// 読み取った要素を除くため、残りのリストに `x => y => y` を与える必要があるかどうか
+    pending_cdr: bool,
+
+    steps: usize,
+    max_steps: Option<usize>,
+    exit_code: Option<usize>,
+    failed: bool,
+}
+
+impl<I: Iterator<Item = u8>> LazyK<I> {
+    /// program の中の s, k, i, ι などは context の定義に従って簡約する
+    pub fn new(context: Context, mut program: Expr, input: I) -> Self {
+        // 型は実行に関わらないので取り除く
+        program.erase_types();
+        let output = expr::a(program, expr::v(INPUT));
+        let mut reducer = Reducer::new(
+            context,
+            Aliases::new(),
+            output,
+            ReductionStrategy::CallByName,
+        );
+        reducer.set_backend(ReductionBackend::Graph);
+        reducer.set_target(ReductionTarget::WeakHeadNormalForm);
+        Self {
+            reducer,
+            input,
+            pending_cdr: false,
+            steps: 0,
+            max_steps: None,
+            exit_code: None,
+            failed: false,
+        }
+    }
+
+    /// 実行全体で行う簡約ステップ数の上限を設定する
+    pub fn set_max_steps(&mut self, max_steps: Option<usize>) {
+        self.max_steps = max_steps;
+        self.reducer.set_max_steps(max_steps);
+    }
+
+    /// 簡約中の式の大きさの上限を設定する
+    pub fn set_max_size(&mut self, max_size: Option<usize>) {
+        self.reducer.set_max_size(max_size);
+    }
+
+    /// これまでに行った簡約ステップ数
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// 実行を終えていれば、その終了コード
+    pub fn exit_code(&self) -> Option<usize> {
+        self.exit_code
+    }
+
+    // 出力のリストの先頭の要素を読み取る
+    //
+    // 射影には Context によらないよう、組み込みのラムダ抽象を使う
+    fn next_byte(&mut self) -> Result<Option<u8>, LazyKError> {
+        if self.pending_cdr {
+            self.modify(|graph| graph.apply(&expr::l("x", expr::l("y", "y"))));
+            self.reduce()?;
+            self.pending_cdr = false;
+        }
+
+        self.modify(|graph| {
+            graph.save_root();
+            graph.apply(&expr::l("x", expr::l("y", "x")));
+        });
+        let n = self.numeral();
+        self.modify(|graph| graph.restore_root());
+        let n = n?;
+
+        self.pending_cdr = true;
+        if n < EOF {
+            Ok(Some(n as u8))
+        } else {
+            self.exit_code = Some(n - EOF);
+            Ok(None)
+        }
+    }
+
+    // 根の Church 数を読み取る
+    //
+    // `#f(#f(…#f(#x)…))` の形を先頭から1つずつ簡約しながら #f の個数を数える
+    fn numeral(&mut self) -> Result<usize, LazyKError> {
+        self.modify(|graph| {
+            graph.apply(&expr::s(PROBE_F));
+            graph.apply(&expr::s(PROBE_X));
+        });
+        self.reduce()?;
+
+        let probe_f = expr::s(PROBE_F);
+        let probe_x = expr::s(PROBE_X);
+        let mut n = 0;
+        loop {
+            let graph = self.reducer.graph().expect("LazyK reduces a graph");
+            match graph.head() {
+                Some((head, 0)) if head == probe_x => return Ok(n),
+                Some((head, 1)) if head == probe_f => {
+                    n += 1;
+                    self.modify(|graph| graph.select_arg(1));
+                    self.reduce()?;
+                }
+                _ => return Err(LazyKError::NotNumeral(graph.expr())),
+            }
+        }
+    }
+
+    // 根を先頭が簡約できなくなるまで簡約する (引数の中は必要になるまで簡約しない)
+    //
+    // 入力の残りを表す変数が先頭に現れて簡約が止まった場合は、
+    // 入力を1バイト読み進めてグラフ全体の変数を置き換え、簡約を続ける
+    fn reduce(&mut self) -> Result<(), LazyKError> {
+        loop {
+            self.steps += self.reducer.run();
+            match self.reducer.termination() {
+                Some(Termination::StepLimit { .. }) => {
+                    return Err(LazyKError::StepLimit {
+                        max_steps: self.max_steps.unwrap_or(self.steps),
+                    })
+                }
+                Some(Termination::SizeLimit { size, .. }) => {
+                    return Err(LazyKError::SizeLimit { size: *size })
+                }
+                _ => {}
+            }
+
+            let graph = self.reducer.graph().expect("LazyK reduces a graph");
+            if graph.head().map(|(head, _)| head) != Some(expr::v(INPUT)) {
+                return Ok(());
+            }
+
+            let input = self.read();
+            self.modify(|graph| graph.replace_free(&Identifier::from(INPUT), &input));
+        }
+    }
+
+    // グラフを書き換え、書き換えた後の式から簡約を続ける
+    fn modify(&mut self, f: impl FnOnce(&mut super::graph::Graph)) {
+        self.reducer.modify_graph(f).expect("LazyK reduces a graph");
+    }
+
+    // 入力を1バイト読み、その値と残りの入力からなる対を返す
+    fn read(&mut self) -> Expr {
+        let n = self.input.next().map_or(EOF, usize::from);
+        expr::l(
+            "f",
+            expr::a(expr::a("f", church_numeral(n)), expr::v(INPUT)),
+        )
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for LazyK<I> {
+    type Item = Result<u8, LazyKError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.exit_code.is_some() {
+            return None;
+        }
+
+        match self.next_byte() {
+            Ok(byte) => byte.map(Ok),
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::unlambda_recursive;
+    use crate::export::{export, LazyKSyntax};
+    use crate::parser::{parse_expr, parse_expr_with_combinator_style, parse_expr_with_iota_style};
+
+    fn run(program: Expr, input: &str) -> (Result<Vec<u8>, LazyKError>, Option<usize>) {
+        let mut lazy_k = LazyK::new(Context::default(), program, input.bytes());
+        lazy_k.set_max_steps(Some(1_000_000));
+        let output = lazy_k.by_ref().collect();
+        (output, lazy_k.exit_code())
+    }
+
+    // 入力を逆順に出力する
+    fn reverse() -> Expr {
+        // ISEOF(n): n 回 CDR を適用して、256 番目にだけ TRUE を置いたリストを調べる
+        let is_eof = parse_expr("n => CAR(n(CDR, 256(CONS(FALSE), CONS(TRUE, NIL))))").unwrap();
+        let rev = parse_expr("Y(r => l => a => IF(ISEOF(CAR(l)), a, r(CDR(l), CONS(CAR(l), a))))")
+            .unwrap();
+        let mut program = parse_expr("x => REV(x, CONS(256, NIL))").unwrap();
+        program.substitute(&"REV".into(), &rev);
+        program.substitute(&"ISEOF".into(), &is_eof);
+        program
+    }
+
+    #[test]
+    fn test_echo() {
+        assert_eq!(
+            run(parse_expr("i").unwrap(), "Hello"),
+            (Ok(b"Hello".to_vec()), Some(0))
+        );
+        assert_eq!(run(parse_expr("i").unwrap(), ""), (Ok(vec![]), Some(0)));
+
+        // Unlambda 記法での Lazy K の echo
+        assert_eq!(
+            run(parse_expr("``skk").unwrap(), "abc"),
+            (Ok(b"abc".to_vec()), Some(0))
+        );
+    }
+
+    #[test]
+    fn test_constant() {
+        // 入力を読まずに "hi" を出力し、終了コード 3 で終わる
+        let program = parse_expr("k(CONS(104, CONS(105, CONS(259, NIL))))").unwrap();
+        assert_eq!(run(program, ""), (Ok(b"hi".to_vec()), Some(3)));
+    }
+
+    #[test]
+    fn test_reverse() {
+        assert_eq!(run(reverse(), "abc"), (Ok(b"cba".to_vec()), Some(0)));
+
+        // s, k, i だけで書いたプログラムでも同じ結果になる
+        let program = unlambda_recursive(&Context::default(), reverse());
+        assert_eq!(run(program, "tuber"), (Ok(b"rebut".to_vec()), Some(0)));
+    }
+
+    #[test]
+    fn test_lazy_k_sources() {
+        // Lazy K の各記法で書いた echo
+        for program in [
+            parse_expr_with_combinator_style("I").unwrap(),
+            parse_expr_with_combinator_style("SKK").unwrap(),
+            parse_expr("``skk").unwrap(),
+            parse_expr_with_iota_style("*ii").unwrap(),
+        ] {
+            assert_eq!(run(program, "echo"), (Ok(b"echo".to_vec()), Some(0)));
+        }
+
+        // 256 = SII(SII(S(S(KS)K)I)) (2 を2回自乗する) だけを返すリストを出力して、何も出力せずに終わる
+        let program = parse_expr_with_combinator_style("K(K(SII(SII(S(S(KS)K)I))))").unwrap();
+        assert_eq!(run(program, "ignored"), (Ok(vec![]), Some(0)));
+        let program = parse_expr("`k`k```sii```sii``s``s`kski").unwrap();
+        assert_eq!(run(program, "ignored"), (Ok(vec![]), Some(0)));
+
+        // 4 だけを出力する: 対は S(SI(K a))(K b)
+        let program = parse_expr_with_combinator_style(
+            "K(S(SI(K(SII(S(S(KS)K)I))))(K(K(SII(SII(S(S(KS)K)I))))))",
+        )
+        .unwrap();
+        assert_eq!(run(program, ""), (Ok(vec![4]), Some(0)));
+
+        // s, k, i だけにした reverse を各記法のソースとして書き出し、読み直して実行する
+        // 構文解析は括弧の入れ子の深さだけ再帰するので、大きめのスタックで行う
+        let program = unlambda_recursive(&Context::default(), reverse());
+        let sources: Vec<_> = [LazyKSyntax::Combinator, LazyKSyntax::Unlambda]
+            .into_iter()
+            .map(|syntax| export(&program, syntax).unwrap())
+            .collect();
+        let parsed = std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(move || {
+                vec![
+                    parse_expr_with_combinator_style(&sources[0]).unwrap(),
+                    parse_expr(&sources[1]).unwrap(),
+                ]
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        for program in parsed {
+            assert_eq!(run(program, "ab"), (Ok(b"ba".to_vec()), Some(0)));
+        }
+    }
+
+    #[test]
+    fn test_without_comb() {
+        // 射影は組み込みのラムダ抽象なので、s, k, i が定義されていなくても動く
+        let run = |program: &str, input: &str| {
+            let mut lazy_k =
+                LazyK::new(Context::new(), parse_expr(program).unwrap(), input.bytes());
+            let output: Result<Vec<u8>, _> = lazy_k.by_ref().collect();
+            (output, lazy_k.exit_code())
+        };
+        assert_eq!(run("x => x", "Hello"), (Ok(b"Hello".to_vec()), Some(0)));
+        assert_eq!(
+            run("(_, f) => f(104, g => g(259, x => x))", ""),
+            (Ok(b"h".to_vec()), Some(3))
+        );
+    }
+
+    #[test]
+    fn test_lazy_input() {
+        // echo は出力に必要な分だけ入力を読む
+        let input = b"abc".iter().copied().inspect(|&b| assert_ne!(b, b'c'));
+        let mut lazy_k = LazyK::new(Context::default(), parse_expr("i").unwrap(), input);
+        assert_eq!(lazy_k.next(), Some(Ok(b'a')));
+        assert_eq!(lazy_k.next(), Some(Ok(b'b')));
+    }
+
+    #[test]
+    fn test_error() {
+        let (output, exit_code) = run(parse_expr("k(CONS(:a, NIL))").unwrap(), "");
+        assert!(matches!(output, Err(LazyKError::NotNumeral(_))));
+        assert_eq!(exit_code, None);
+
+        let omega = parse_expr("k(s(i, i, s(i, i)))").unwrap();
+        let mut lazy_k = LazyK::new(Context::default(), omega, "".bytes());
+        lazy_k.set_max_steps(Some(1000));
+        assert_eq!(
+            lazy_k.next(),
+            Some(Err(LazyKError::StepLimit { max_steps: 1000 }))
+        );
+        assert_eq!(lazy_k.next(), None);
+    }
+}
@@ -0,0 +1,224 @@
+use super::{
+    expand, unlambda_recursive_, Aliases, RecursiveStrategy, Reducer, ReductionStrategy,
+    Termination,
+};
+use crate::context::Context;
+use crate::expr::{self, Expr, Identifier};
+
+/// 比較するラムダ抽象の除去の方法
+pub const COMPARED_STRATEGIES: [RecursiveStrategy; 5] = [
+    RecursiveStrategy::SKI,
+    RecursiveStrategy::SK,
+    RecursiveStrategy::Iota,
+    RecursiveStrategy::Turner,
+    RecursiveStrategy::Kiselyov,
+];
+
+/// サンプルの入力を簡約するステップ数の上限
+const MAX_STEPS: usize = 10000;
+
+/// サンプルの入力を簡約するときに許す式の大きさの上限
+const MAX_SIZE: usize = 100000;
+
+/// サンプルの入力として与える引数の個数の上限
+const MAX_ARGS: usize = 8;
+
+/// 式の大きさ・深さと、現れる変数 (コンビネータ) の個数
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metrics {
+    /// 式を構成する項の個数
+    pub size: usize,
+
+    /// 式を木として見たときの深さ
+    pub depth: usize,
+
+    /// 変数ごとの出現回数 (名前順)
+    pub combinators: Vec<(Identifier, usize)>,
+}
+
+impl From<&Expr> for Metrics {
+    fn from(expr: &Expr) -> Self {
+        let mut combinators = Vec::new();
+        count_variables(expr, &mut combinators);
+        combinators.sort_by(|(a, _), (b, _): &(Identifier, usize)| a.as_str().cmp(b.as_str()));
+
+        Self {
+            size: expr.size(),
+            depth: depth(expr),
+            combinators,
+        }
+    }
+}
+
+fn depth(expr: &Expr) -> usize {
+    match expr {
+        Expr::Variable(_) | Expr::Symbol(_) => 1,
+        Expr::Apply { lhs, rhs } => 1 + depth(lhs).max(depth(rhs)),
+        Expr::Lambda { body, .. } => 1 + depth(body),
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => depth(body),
+        Expr::Let { value, body, .. } => 1 + depth(value).max(depth(body)),
+    }
+}
+
+fn count_variables(expr: &Expr, counts: &mut Vec<(Identifier, usize)>) {
+    match expr {
+        Expr::Variable(id) => match counts.iter_mut().find(|(other, _)| other == id) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((id.clone(), 1)),
+        },
+        Expr::Symbol(_) => {}
+        Expr::Apply { lhs, rhs } => {
+            count_variables(lhs, counts);
+            count_variables(rhs, counts);
+        }
+        Expr::Lambda { body, .. } => count_variables(body, counts),
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            count_variables(body, counts)
+        }
+        Expr::Let { value, body, .. } => {
+            count_variables(value, counts);
+            count_variables(body, counts);
+        }
+    }
+}
+
+/// 1つの方法でラムダ抽象を除去した結果とその評価
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrategyMetrics {
+    pub strategy: RecursiveStrategy,
+
+    /// ラムダ抽象を除去した式
+    pub result: Expr,
+
+    pub metrics: Metrics,
+
+    /// サンプルの入力ごとの、正規形までの簡約ステップ数 (上限までに正規形に達しなければ None)
+    pub steps: Vec<Option<usize>>,
+}
+
+/// COMPARED_STRATEGIES のそれぞれでラムダ抽象を除去し、結果の式を比較する
+///
+/// samples のそれぞれを引数として結果の式に与え、正規形までのステップ数を数える
+pub fn compare_unlambda(
+    context: &Context,
+    expr: &Expr,
+    samples: &[Vec<Expr>],
+) -> Vec<StrategyMetrics> {
+    COMPARED_STRATEGIES
+        .iter()
+        .map(|strategy| {
+            let result = unlambda_recursive_(strategy, context, expr.clone());
+            let steps = samples
+                .iter()
+                .map(|args| count_steps(context, &result, args))
+                .collect();
+            StrategyMetrics {
+                strategy: strategy.clone(),
+                metrics: Metrics::from(&result),
+                result,
+                steps,
+            }
+        })
+        .collect()
+}
+
+/// 式を展開したときの先頭のラムダ抽象の個数だけ、:a, :b, … をサンプルの入力として用意する
+pub fn sample_args(context: &Context, expr: &Expr) -> Vec<Expr> {
+    let mut expr = &expand(context, expr.clone());
+    let mut args = Vec::new();
+    while let Expr::Lambda { body, .. } = expr {
+        if args.len() == MAX_ARGS {
+            break;
+        }
+        args.push(expr::s(((b'a' + args.len() as u8) as char).to_string()));
+        expr = body;
+    }
+    args
+}
+
+/// 引数を与えた式を正規形まで簡約するのにかかるステップ数
+pub fn count_steps(context: &Context, expr: &Expr, args: &[Expr]) -> Option<usize> {
+    let expr = args
+        .iter()
+        .fold(expr.clone(), |e, arg| expr::a(e, arg.clone()));
+    let mut reducer = Reducer::new(
+        context.clone(),
+        Aliases::new(),
+        expr,
+        ReductionStrategy::NormalOrder,
+    );
+    reducer.set_max_steps(Some(MAX_STEPS));
+    reducer.set_max_size(Some(MAX_SIZE));
+
+    let steps = reducer.by_ref().count();
+    match reducer.termination() {
+        None | Some(Termination::NormalForm) => Some(steps),
+        Some(_) => None,
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_expr;
+
+    #[test]
+    fn test_metrics() {
+        let metrics = Metrics::from(&parse_expr("s(k(s), k, :a, s)").unwrap());
+        assert_eq!(metrics.size, 11);
+        assert_eq!(metrics.depth, 6);
+        assert_eq!(metrics.combinators, vec![("k".into(), 2), ("s".into(), 3)]);
+
+        let metrics = Metrics::from(&parse_expr("x => x").unwrap());
+        assert_eq!(metrics.size, 2);
+        assert_eq!(metrics.depth, 2);
+        assert_eq!(metrics.combinators, vec![("x".into(), 1)]);
+    }
+
+    #[test]
+    fn test_sample_args() {
+        let context = Context::default();
+        assert_eq!(
+            sample_args(&context, &parse_expr("TRUE").unwrap()),
+            vec![expr::s("a"), expr::s("b")]
+        );
+        assert_eq!(sample_args(&context, &parse_expr(":a").unwrap()), vec![]);
+    }
+
+    #[test]
+    fn test_count_steps() {
+        let context = Context::default();
+        let expr = parse_expr("s(k, k)").unwrap();
+        assert_eq!(count_steps(&context, &expr, &[expr::s("a")]), Some(2));
+
+        let omega = parse_expr("s(i, i, s(i, i))").unwrap();
+        assert_eq!(count_steps(&context, &omega, &[]), None);
+    }
+
+    #[test]
+    fn test_compare_unlambda() {
+        let mut context = Context::default();
+        context.import("turner").unwrap();
+        let expr = parse_expr("x => y => y(x)").unwrap();
+        let samples = vec![sample_args(&context, &expr)];
+        let reports = compare_unlambda(&context, &expr, &samples);
+
+        let strategies: Vec<_> = reports.iter().map(|r| r.strategy.clone()).collect();
+        assert_eq!(strategies, COMPARED_STRATEGIES.to_vec());
+
+        for report in &reports {
+            assert_eq!(report.metrics, Metrics::from(&report.result));
+            assert_eq!(report.steps.len(), 1);
+            assert!(report.steps[0].is_some(), "{:?}", report.strategy);
+        }
+
+        // Turner のコンビネータを使うと SKI より小さくなる
+        assert!(reports[3].metrics.size < reports[0].metrics.size);
+        assert_eq!(reports[3].result, parse_expr("C(i)").unwrap());
+
+        // ι だけで書かれた式は同じ入力を簡約するのにより多くのステップを要する
+        assert!(reports[2].steps[0] > reports[0].steps[0]);
+    }
+}
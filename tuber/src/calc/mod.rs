@@ -1,16 +1,35 @@
 mod aliases;
 mod apply;
 mod arity;
+mod backend;
+mod decode;
 mod expand;
+mod graph;
+mod history;
+mod lazy_k;
+mod metrics;
+mod normalization;
 mod reducer;
+mod strategy;
+mod target;
+mod termination;
 mod unlambda;
 
 pub use aliases::Aliases;
 pub use arity::arity;
+pub use backend::ReductionBackend;
+pub use decode::{decode, Decoded};
 pub use expand::expand;
+pub use history::ReductionHistory;
+pub use lazy_k::{LazyK, LazyKError};
+pub use metrics::{
+    compare_unlambda, count_steps, sample_args, Metrics, StrategyMetrics, COMPARED_STRATEGIES,
+};
 pub use reducer::{ReduceResult, Reducer};
+pub use strategy::ReductionStrategy;
+pub use target::ReductionTarget;
+pub use termination::Termination;
 pub use unlambda::{
-    unlambda_iota, unlambda_recursive, unlambda_recursive_, unlambda_recursive_sk,
-    unlambda_recursive_ski, unlambda_shallow, unlambda_shallow_, unlambda_shallow_sk,
-    unlambda_shallow_ski, RecursiveStrategy, ShallowStrategy,
+    unlambda_iota, unlambda_kiselyov, unlambda_recursive, unlambda_recursive_, unlambda_turner,
+    AbstractionRule, RecursiveStrategy, UnlambdaStep, UnlambdaTrace, UnlambdaTraceError,
 };
@@ -0,0 +1,42 @@
+use super::target::ReductionTarget;
+use crate::expr::{Expr, FreeVars, Identifier};
+
+/// 評価戦略とは別に、どこまで簡約するかを表現する
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Normalization {
+    /// 引数の足りない関数定義を展開し、引数を伴わないラムダ抽象の内側も簡約する
+    pub strong: bool,
+
+    /// `x => M(x)` を `M` に簡約する (strong のときのみ)
+    pub eta: bool,
+
+    /// どの形に達したら簡約をやめるか
+    pub target: ReductionTarget,
+}
+
+/// `param => body` が η簡約できるかどうか
+pub fn is_eta_redex(param: &Identifier, body: &Expr) -> bool {
+    match body {
+        Expr::Apply { lhs, rhs } => {
+            **rhs == Expr::Variable(param.clone()) && !FreeVars::from(lhs.as_ref()).contains(param)
+        }
+        _ => false,
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_is_eta_redex() {
+        assert!(is_eta_redex(&"x".into(), &expr::a("f", "x")));
+        assert!(is_eta_redex(&"x".into(), &expr::a(expr::a("f", "y"), "x")));
+        assert!(!is_eta_redex(&"x".into(), &expr::a(expr::a("f", "x"), "x")));
+        assert!(!is_eta_redex(&"x".into(), &expr::a("x", "y")));
+        assert!(!is_eta_redex(&"x".into(), &expr::v("x")));
+    }
+}
@@ -1,60 +1,420 @@
 use super::apply::apply;
+use super::backend::ReductionBackend;
+use super::graph::Graph;
+use super::normalization::{is_eta_redex, Normalization};
+use super::strategy::ReductionStrategy;
+use super::target::ReductionTarget;
+use super::termination::Termination;
 use crate::calc::{self, aliases::Aliases};
 use crate::context::Context;
-use crate::expr::{self, Path, PathBuilder};
+use crate::expr::{self, DeBruijn, Identifier, Path};
+use std::collections::HashMap;
 
 pub struct Reducer {
     context: Context,
     aliases: Aliases,
+    strategy: ReductionStrategy,
     step: usize,
-    expr: Expr,
+    state: State,
+    max_steps: Option<usize>,
+    max_size: Option<usize>,
+    history: Option<HashMap<DeBruijn, usize>>,
+    type_erasure: bool,
+    normalization: Normalization,
+    reports_reached: bool,
+    next_path: Option<Option<Path>>,
+    exceeded: Option<Termination>,
+    termination: Option<Termination>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct ReduceResult {
     pub step: usize,
     pub expr: expr::Expr,
     pub reduced_path: Path,
+    /// 簡約後の式が達している最も強い形
+    ///
+    /// 求めるには余分に式をたどるので、set_target で目標を指定した場合のみ求める
+    pub reached: Option<ReductionTarget>,
 }
 
 impl Reducer {
-    pub fn new(context: Context, aliases: Aliases, expr: expr::Expr) -> Self {
-        let expr = Expr::from(expr);
+    pub fn new(
+        context: Context,
+        aliases: Aliases,
+        expr: expr::Expr,
+        strategy: ReductionStrategy,
+    ) -> Self {
+        let state = State::Tree(Expr::from(expr));
         Self {
             context,
             aliases,
+            strategy,
             step: 0,
-            expr,
+            state,
+            max_steps: None,
+            max_size: None,
+            history: None,
+            type_erasure: false,
+            normalization: Normalization::default(),
+            reports_reached: false,
+            next_path: None,
+            exceeded: None,
+            termination: None,
+        }
+    }
+
+    /// 簡約ステップ数の上限を設定する
+    pub fn set_max_steps(&mut self, max_steps: Option<usize>) {
+        self.max_steps = max_steps;
+    }
+
+    /// 式の大きさ (Expr::size) の上限を設定する
+    pub fn set_max_size(&mut self, max_size: Option<usize>) {
+        self.max_size = max_size;
+    }
+
+    /// 以前に現れた式と α同値な式が再び現れたら簡約を打ち切るかどうかを設定する
+    pub fn set_cycle_detection(&mut self, enabled: bool) {
+        self.history = if enabled {
+            let mut history = HashMap::new();
+            history.insert(DeBruijn::from(self.expr()), self.step);
+            Some(history)
+        } else {
+            None
+        };
+    }
+
+    /// 型注釈、型抽象、型適用を取り除きながら簡約するかどうかを設定する
+    ///
+    /// 有効にするとその時点の式と、以降に展開する関数定義から型を取り除く
+    /// 型を残したままでは型抽象と型適用のところで簡約が止まる (Graph では常に取り除かれる)
+    pub fn set_type_erasure(&mut self, enabled: bool) {
+        self.type_erasure = enabled;
+        if enabled {
+            let mut expr = self.expr();
+            expr.erase_types();
+            self.next_path = None;
+            self.state = match self.backend() {
+                ReductionBackend::Tree => State::Tree(Expr::from(expr)),
+                ReductionBackend::Graph => State::Graph(Graph::from(expr)),
+            };
         }
     }
 
+    /// 引数の足りない関数定義を展開し、ラムダ抽象の内側まで簡約して β正規形を求めるかどうかを設定する
+    ///
+    /// 評価戦略は簡約基を選ぶ順序にのみ影響し、名前呼びや値呼びでも引数の中まで簡約する
+    pub fn set_strong_normalization(&mut self, enabled: bool) {
+        self.normalization.strong = enabled;
+        self.next_path = None;
+    }
+
+    /// 強正規化の際に η簡約 (`x => M(x)` を `M` に) も行うかどうかを設定する
+    pub fn set_eta_reduction(&mut self, enabled: bool) {
+        self.normalization.eta = enabled;
+        self.next_path = None;
+    }
+
+    /// どの形に達したら簡約をやめるかを設定する
+    ///
+    /// 設定すると、各ステップの ReduceResult に簡約後の式が達している形も含める
+    pub fn set_target(&mut self, target: ReductionTarget) {
+        self.normalization.target = target;
+        self.reports_reached = true;
+        self.next_path = None;
+    }
+
+    // ReduceResult に簡約後の式が達している形を含めるかどうか
+    pub(crate) fn reports_reached(&self) -> bool {
+        self.reports_reached
+    }
+
+    pub fn target(&self) -> ReductionTarget {
+        self.normalization.target
+    }
+
+    /// 簡約列が終了した理由
+    ///
+    /// next() が None を返すまでは None を返す
+    pub fn termination(&self) -> Option<&Termination> {
+        self.termination.as_ref()
+    }
+
     pub fn expr(&self) -> expr::Expr {
-        self.expr.clone().into()
+        match &self.state {
+            State::Tree(expr) => expr.clone().into(),
+            State::Graph(graph) => graph.expr(),
+        }
+    }
+
+    pub fn strategy(&self) -> ReductionStrategy {
+        self.strategy
+    }
+
+    /// 評価戦略を切り替える
+    ///
+    /// 簡約の途中で切り替えた場合は、その時点の式から新しい戦略で簡約を続ける
+    pub fn set_strategy(&mut self, strategy: ReductionStrategy) {
+        self.strategy = strategy;
+        self.next_path = None;
+    }
+
+    /// step 番目の式が expr であるものとして簡約をやり直す
+    ///
+    /// 簡約の設定は引き継ぎ、上限や循環の検査は step 番目から数え直す
+    pub fn restart(&mut self, expr: expr::Expr, step: usize) {
+        let mut expr = expr;
+        if self.type_erasure {
+            expr.erase_types();
+        }
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+            history.insert(DeBruijn::from(&expr), step);
+        }
+        self.state = match self.backend() {
+            ReductionBackend::Tree => State::Tree(Expr::from(expr)),
+            ReductionBackend::Graph => State::Graph(Graph::from(expr)),
+        };
+        self.step = step;
+        self.next_path = None;
+        self.exceeded = None;
+        self.termination = None;
+    }
+
+    // Graph で簡約している場合はそのグラフ
+    pub(crate) fn graph(&self) -> Option<&Graph> {
+        match &self.state {
+            State::Tree(_) => None,
+            State::Graph(graph) => Some(graph),
+        }
+    }
+
+    // Graph で簡約している場合に、そのグラフを書き換えてから簡約を続けられるようにする
+    //
+    // ステップ数と上限の設定は引き継ぐ
+    pub(crate) fn modify_graph<T>(&mut self, f: impl FnOnce(&mut Graph) -> T) -> Option<T> {
+        let State::Graph(graph) = &mut self.state else {
+            return None;
+        };
+        let result = f(graph);
+        self.next_path = None;
+        self.termination = None;
+        Some(result)
+    }
+
+    /// 簡約に用いる関数定義
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// 簡約に用いるエイリアス
+    pub fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    pub fn backend(&self) -> ReductionBackend {
+        match &self.state {
+            State::Tree(_) => ReductionBackend::Tree,
+            State::Graph(_) => ReductionBackend::Graph,
+        }
+    }
+
+    /// 簡約に用いる式の内部表現を切り替える
+    ///
+    /// 簡約の途中で切り替えた場合は、その時点の式から簡約を続ける
+    pub fn set_backend(&mut self, backend: ReductionBackend) {
+        if self.backend() == backend {
+            return;
+        }
+
+        let expr = self.expr();
+        self.next_path = None;
+        self.state = match backend {
+            ReductionBackend::Tree => State::Tree(Expr::from(expr)),
+            ReductionBackend::Graph => State::Graph(Graph::from(expr)),
+        };
     }
 
     pub fn reducible_path(&self) -> Option<Path> {
-        self.expr.reducible_path(&self.context, &self.aliases)
+        match &self.next_path {
+            Some(path) => path.clone(),
+            None => self.path_to(self.normalization.target),
+        }
+    }
+
+    // 次に簡約する簡約基に至る経路を求め、次のステップのために覚えておく
+    pub(crate) fn peek_path(&mut self) -> Option<Path> {
+        if self.next_path.is_none() {
+            self.next_path = Some(self.reducible_path());
+        }
+        self.next_path.clone().flatten()
+    }
+
+    /// 現在の式が達している最も強い形
+    pub fn reached(&self) -> Option<ReductionTarget> {
+        self.forms().0
+    }
+
+    // target を目標とした場合の簡約基に至る経路
+    fn path_to(&self, target: ReductionTarget) -> Option<Path> {
+        let normalization = Normalization {
+            target,
+            ..self.normalization
+        };
+        match &self.state {
+            State::Tree(expr) => expr.reducible_path(
+                &self.context,
+                &self.aliases,
+                &self.strategy,
+                &normalization,
+                &[],
+            ),
+            State::Graph(graph) => {
+                graph.reducible_path(&self.context, &self.aliases, &self.strategy, &normalization)
+            }
+        }
+    }
+
+    // 現在の式が達している最も強い形と、途中で求めた場合は目標とする形への経路を返す
+    //
+    // 弱い形から順に調べ、達していない形があればそれより強い形は調べない
+    fn forms(&self) -> (Option<ReductionTarget>, Option<Option<Path>>) {
+        let mut reached = None;
+        let mut next_path = None;
+        for target in ReductionTarget::ALL {
+            let path = self.path_to(target);
+            let irreducible = path.is_none();
+            if target == self.normalization.target {
+                next_path = Some(path);
+            }
+            if !irreducible {
+                break;
+            }
+            reached = Some(target);
+        }
+        (reached, next_path)
+    }
+
+    /// 途中の式を組み立てずに、簡約列が終了するまで簡約を進める
+    ///
+    /// 行った簡約ステップ数を返す
+    pub fn run(&mut self) -> usize {
+        let mut steps = 0;
+        while self.step().is_some() {
+            steps += 1;
+        }
+        steps
+    }
+
+    // 1ステップ簡約し、簡約した位置を返す
+    //
+    // 大きさや循環の検査のために式を組み立てた場合は、その式も返す
+    fn step(&mut self) -> Option<(Path, Option<expr::Expr>)> {
+        if self.termination.is_some() {
+            return None;
+        }
+
+        let next_path = match self.next_path.take() {
+            Some(path) => path,
+            None => self.reducible_path(),
+        };
+        let reducible_path = match next_path {
+            Some(path) => path,
+            None => {
+                self.termination = Some(match self.normalization.target {
+                    ReductionTarget::NormalForm => Termination::NormalForm,
+                    target => Termination::TargetReached(target),
+                });
+                return None;
+            }
+        };
+
+        // 直前のステップで上限を超えていたら、ここで打ち切る
+        if let Some(exceeded) = self.exceeded.take() {
+            self.termination = Some(exceeded);
+            return None;
+        }
+
+        if let Some(max_steps) = self.max_steps {
+            if max_steps <= self.step {
+                self.termination = Some(Termination::StepLimit { max_steps });
+                return None;
+            }
+        }
+
+        let reduced_path = match &mut self.state {
+            State::Tree(expr) => expr.reduce(
+                &self.context,
+                &self.aliases,
+                &reducible_path,
+                self.type_erasure,
+            ),
+            State::Graph(graph) => graph.reduce(&self.context, &self.aliases, &reducible_path),
+        };
+        self.step += 1;
+
+        // グラフは大きさを求めるためだけには木構造に戻さない
+        let mut expr = None;
+        if let Some(max_size) = self.max_size {
+            let size = match &self.state {
+                State::Tree(_) => expr.insert(self.expr()).size(),
+                State::Graph(graph) => graph.size(),
+            };
+            if max_size < size {
+                self.exceeded = Some(Termination::SizeLimit {
+                    step: self.step,
+                    size,
+                });
+            }
+        }
+
+        if self.history.is_some() && expr.is_none() {
+            expr = Some(self.expr());
+        }
+
+        if let (Some(history), Some(expr)) = (self.history.as_mut(), &expr) {
+            let step = self.step;
+            let since = *history.entry(DeBruijn::from(expr)).or_insert(step);
+            if since != step && self.exceeded.is_none() {
+                self.exceeded = Some(Termination::Cycle { step, since });
+            }
+        }
+
+        Some((reduced_path, expr))
     }
 }
 
 impl Iterator for Reducer {
     type Item = ReduceResult;
 
+    /// 1ステップ簡約し、簡約後の式を返す
+    ///
+    /// グラフでも式を返すために共有を解くので、途中の式が要らなければ run を使う
     fn next(&mut self) -> Option<Self::Item> {
-        let reducible_path = self.expr.reducible_path(&self.context, &self.aliases)?;
-
-        let reduced_path = self
-            .expr
-            .reduce(&self.context, &self.aliases, &reducible_path);
-        self.step += 1;
-
+        let (reduced_path, expr) = self.step()?;
+        let reached = if self.reports_reached {
+            let (reached, next_path) = self.forms();
+            self.next_path = next_path;
+            reached
+        } else {
+            None
+        };
         Some(ReduceResult {
             step: self.step,
-            expr: expr::Expr::from(self.expr.to_owned()),
+            expr: expr.unwrap_or_else(|| self.expr()),
             reduced_path,
+            reached,
         })
     }
 }
 
+#[derive(Clone, PartialEq, Debug)]
+enum State {
+    Tree(Expr),
+    Graph(Graph),
+}
+
 #[derive(Clone, PartialEq, Debug)]
 struct Expr {
     callee: expr::Expr,
@@ -67,16 +427,26 @@ impl Expr {
     }
 
     // self.callee に self.args のうちいくつかの項を与えて簡約可能かどうかを判定する
-    fn callable(&self, context: &Context, aliases: &Aliases) -> bool {
-        let is_alias = if let expr::Expr::Variable(identity) = &self.callee {
-            aliases.has(identity)
-        } else {
-            false
+    //
+    // bound はラムダ抽象の内側を簡約する際に、外側のラムダ抽象で束縛された変数
+    fn callable(&self, context: &Context, aliases: &Aliases, bound: &[Identifier]) -> bool {
+        // 束縛された変数は同名の関数定義やエイリアスを指さない
+        if let expr::Expr::Variable(id) = &self.callee {
+            if bound.contains(id) {
+                return false;
+            }
+        }
+
+        // エイリアスと let 式は引数を伴わなくても簡約する
+        let is_nullary = match &self.callee {
+            expr::Expr::Variable(identity) => aliases.has(identity),
+            expr::Expr::Let { .. } => true,
+            _ => false,
         };
 
         // self.callee が arity = 0 の関数であっても、引数を伴わない単項の式なら簡約したくない
         // そのため簡約可能であるためには少なくとも1つ以上の引数を持つべきだ
-        if !is_alias && self.args.len() == 0 {
+        if !is_nullary && self.args.is_empty() {
             return false;
         }
 
@@ -90,77 +460,176 @@ impl Expr {
         }
     }
 
-    // self.callee に限らず self.args も再帰的にたどって簡約基を含むかどうかを判定する
-    fn reducible(&self, context: &Context, aliases: &Aliases) -> bool {
-        // TODO: reducible() が呼び出されるたびに args を再帰的に辿っていくのが非効率かもしれない、計算結果をキャッシュする機構を考えたい
-        self.callable(context, aliases)
-            || self.args.iter().any(|arg| arg.reducible(context, aliases))
-    }
-
-    // 簡約基に至る経路を返す
-    fn reducible_path(&self, context: &Context, aliases: &Aliases) -> Option<Path> {
-        let mut path = PathBuilder::new();
-        let mut expr = self;
-
-        loop {
-            if expr.callable(context, aliases) {
-                path.set_arity(expr.arity(context, aliases).unwrap());
-                return Some(path.build());
-            } else {
-                match expr
-                    .args
-                    .iter()
-                    .rev()
-                    .enumerate()
-                    .find(|(_, arg)| arg.reducible(context, aliases))
-                {
-                    Some((index, arg)) => {
-                        path.add_route(index + 1);
-                        expr = arg;
+    // 評価戦略に従って簡約基に至る経路を返す
+    fn reducible_path(
+        &self,
+        context: &Context,
+        aliases: &Aliases,
+        strategy: &ReductionStrategy,
+        normalization: &Normalization,
+        bound: &[Identifier],
+    ) -> Option<Path> {
+        let args_path =
+            |count| self.args_path(context, aliases, strategy, normalization, bound, count);
+
+        if self.callable(context, aliases, bound) {
+            let arity = self.arity(context, aliases).unwrap();
+
+            if strategy.reduces_args_first() {
+                // 関数に渡される arity 個の引数の中に簡約基があれば、そちらを先に簡約する
+                if let Some(path) = args_path(arity) {
+                    return Some(path);
+                }
+            }
+
+            Some(Path::Callee(arity))
+        } else {
+            match normalization.target {
+                // 先頭が簡約できなければ弱頭部正規形に達している
+                ReductionTarget::WeakHeadNormalForm => None,
+                // 頭部正規形は先頭のラムダ抽象の内側の先頭も簡約できない形
+                ReductionTarget::HeadNormalForm => {
+                    self.head_path(context, aliases, strategy, normalization, bound)
+                }
+                // 正規形は頭部正規形より強いので、先頭は頭部正規形と同じく簡約する
+                // 強正規化のときは評価戦略によらず引数の中も簡約する
+                ReductionTarget::NormalForm => {
+                    let reduces_args = normalization.strong || strategy.reduces_stuck_args();
+                    if reduces_args && strategy.reduces_args_first() {
+                        if let Some(path) = args_path(self.args.len()) {
+                            return Some(path);
+                        }
                     }
-                    None => return None,
+
+                    self.head_path(context, aliases, strategy, normalization, bound)
+                        .or_else(|| reduces_args.then(|| args_path(self.args.len())).flatten())
                 }
             }
         }
     }
 
-    // 簡約基を可変借用する
-    fn reducible_expr(&mut self, reducible_path: &Path) -> &mut Expr {
-        match reducible_path {
-            Path::Arg(index, next) => {
-                let rev_index = self.args.len() - index;
-                self.args[rev_index].reducible_expr(next)
+    // 引数を与えても簡約できない先頭の項の中の簡約基に至る経路を返す
+    //
+    // 引数を伴わないラムダ抽象は本体の中を簡約し、強正規化の際は引数の足りない関数定義も展開する
+    fn head_path(
+        &self,
+        context: &Context,
+        aliases: &Aliases,
+        strategy: &ReductionStrategy,
+        normalization: &Normalization,
+        bound: &[Identifier],
+    ) -> Option<Path> {
+        match &self.callee {
+            expr::Expr::Variable(id)
+                if normalization.strong && !bound.contains(id) && context.get(id).is_some() =>
+            {
+                Some(Path::Callee(0))
+            }
+            expr::Expr::Lambda { param, body, .. } if self.args.is_empty() => {
+                if normalization.eta && is_eta_redex(param, body) {
+                    return Some(Path::Callee(0));
+                }
+
+                let mut bound = bound.to_vec();
+                bound.push(param.clone());
+                Expr::from(body.as_ref().clone())
+                    .reducible_path(context, aliases, strategy, normalization, &bound)
+                    .map(|path| Path::Body(Box::new(path)))
             }
-            Path::Callee(_) => self,
+            _ => None,
         }
     }
 
-    fn reduce(&mut self, context: &Context, aliases: &Aliases, reducible_path: &Path) -> Path {
-        let expr = self.reducible_expr(reducible_path);
-        let arity = expr.arity(context, aliases).unwrap();
-        let args: Vec<expr::Expr> = expr
+    // 先頭から数えて count 個の引数のうち、最も左にある簡約基に至る経路を返す
+    fn args_path(
+        &self,
+        context: &Context,
+        aliases: &Aliases,
+        strategy: &ReductionStrategy,
+        normalization: &Normalization,
+        bound: &[Identifier],
+        count: usize,
+    ) -> Option<Path> {
+        // self.args は後ろの引数ほど前に並んでいるので、逆順にたどる
+        self.args
+            .iter()
+            .rev()
+            .take(count)
+            .enumerate()
+            .find_map(|(index, arg)| {
+                arg.reducible_path(context, aliases, strategy, normalization, bound)
+                    .map(|path| Path::Arg(index + 1, Box::new(path)))
+            })
+    }
+
+    fn reduce(
+        &mut self,
+        context: &Context,
+        aliases: &Aliases,
+        reducible_path: &Path,
+        type_erasure: bool,
+    ) -> Path {
+        let arity = match reducible_path {
+            Path::Arg(index, next) => {
+                let rev_index = self.args.len() - index;
+                let path = self.args[rev_index].reduce(context, aliases, next, type_erasure);
+                return Path::Arg(*index, Box::new(path));
+            }
+            Path::Body(next) => {
+                let expr::Expr::Lambda { body, .. } = &mut self.callee else {
+                    unreachable!();
+                };
+                let mut expr = Expr::from(body.as_ref().clone());
+                let path = expr.reduce(context, aliases, next, type_erasure);
+                **body = expr.into();
+                return Path::Body(Box::new(path));
+            }
+            Path::Callee(arity) => *arity,
+        };
+
+        let args: Vec<expr::Expr> = self
             .args
-            .drain(expr.args.len() - arity..)
+            .drain(self.args.len() - arity..)
             .rev()
             .map(|expr| expr.into())
             .collect();
-        let mut callee = &mut expr.callee;
+        let mut callee = &mut self.callee;
 
-        // TODO: エラー握りつぶしてるけど大丈夫？
-        // TODO: apply() を reducer::Expr ベースに書き換えたい
-        let _ = apply(context, aliases, callee, args);
+        match callee {
+            // 引数を伴わないラムダ抽象への経路は η簡約を表す
+            expr::Expr::Lambda { body, .. } if arity == 0 => {
+                if let expr::Expr::Apply { lhs, .. } = body.as_mut() {
+                    *callee = lhs.as_ref().clone();
+                }
+            }
+
+            // 引数の足りない関数定義はラムダ抽象に展開する
+            expr::Expr::Variable(id)
+                if !aliases.has(id)
+                    && context.get(id).is_some_and(|func| func.arity() != arity) =>
+            {
+                *callee = context.get(id).unwrap().clone().into();
+            }
+
+            _ => {
+                // TODO: エラー握りつぶしてるけど大丈夫？
+                // TODO: apply() を reducer::Expr ベースに書き換えたい
+                let _ = apply(context, aliases, callee, args);
+            }
+        }
+        if type_erasure {
+            callee.erase_types();
+        }
 
         let mut num_args = 0;
         while let expr::Expr::Apply { lhs, rhs } = callee {
             num_args += 1;
             callee = lhs;
-            expr.args.push(Expr::from(*rhs.to_owned()));
+            self.args.push(Expr::from(*rhs.to_owned()));
         }
-        expr.callee = callee.to_owned();
+        self.callee = callee.to_owned();
 
-        let mut reduced_path = reducible_path.clone();
-        reduced_path.set_arity(num_args);
-        return reduced_path;
+        Path::Callee(num_args)
     }
 }
 
@@ -193,9 +662,9 @@ impl From<Expr> for expr::Expr {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::expr;
+    use crate::expr::{self, church_numeral};
     use crate::func;
-    use crate::Identifier;
+    use crate::types::Type;
     use std::collections::HashMap;
 
     fn setup() -> (Context, Aliases) {
@@ -223,11 +692,16 @@ mod tests {
         let (context, aliases) = setup();
 
         let expr = expr::a(":g", expr::a(":f", expr::a("i", ":y")));
-        let reducer = Reducer::new(context.clone(), aliases.clone(), expr);
+        let reducer = Reducer::new(
+            context.clone(),
+            aliases.clone(),
+            expr,
+            ReductionStrategy::NormalOrder,
+        );
 
         assert_eq!(
-            reducer.expr,
-            Expr {
+            reducer.state,
+            State::Tree(Expr {
                 callee: expr::s("g"),
                 args: vec![Expr {
                     callee: expr::s("f"),
@@ -239,7 +713,7 @@ mod tests {
                         }],
                     }],
                 }],
-            }
+            })
         );
     }
 
@@ -250,72 +724,126 @@ mod tests {
         let expr = expr::s("TRUE");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             None
         );
 
         let expr = expr::v("TRUE");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             None
         );
 
         let expr = expr::a(":i", ":x");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             None
         );
 
         let expr = expr::a("i", ":x");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![1])
         );
 
         let expr = expr::a(expr::a("i", ":x"), ":y");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![1])
         );
 
         let expr = expr::a(":f", expr::a("i", ":x"));
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![1, 1])
         );
 
         let expr = expr::a(expr::a("i", ":x"), expr::a("i", ":y"));
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![1])
         );
 
         let expr = expr::a(expr::a(":i", ":x"), expr::a("i", ":y"));
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![2, 1])
         );
 
         let expr = expr::a(":g", expr::a(":f", expr::a("i", ":y")));
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![1, 1, 1])
         );
     }
@@ -327,33 +855,57 @@ mod tests {
         let expr = expr::v("_");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![0])
         );
 
         let expr = expr::a("_", ":x");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![0])
         );
 
         let expr = expr::a(":x", "_");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            )
+            .map(Vec::<usize>::from),
             Some(vec![1, 0])
         );
 
+        // 正規形を目標とするので、強正規化でなくてもラムダ抽象の内側を簡約する
         let expr = expr::l("x", "_");
         let expr = Expr::from(expr);
         assert_eq!(
-            expr.reducible_path(&context, &aliases)
-                .map(Vec::<usize>::from),
-            None
+            expr.reducible_path(
+                &context,
+                &aliases,
+                &ReductionStrategy::NormalOrder,
+                &Normalization::default(),
+                &[]
+            ),
+            Some(Path::Body(Box::new(Path::Callee(0))))
         );
     }
 
@@ -390,12 +942,347 @@ mod tests {
         let i = expr::l("x", "x");
         let expr = expr::a(i, ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
+
+        assert_eq!(reducer.next().map(|result| result.expr), Some(expr::s("a")));
+        assert_eq!(reducer.next().map(|result| result.expr), None);
+    }
+
+    #[test]
+    fn test_reduce_type_erasure() {
+        let mut context = Context::new();
+        let id = expr::tl("α", expr::lt("x", Type::named("α"), "x"));
+        context
+            .def(func::new("ID", Vec::<&str>::new(), id))
+            .unwrap();
+        let aliases = Aliases::new();
+
+        let expr = expr::a(expr::ta("ID", Type::named("Nat")), ":a");
+
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
+        reducer.set_type_erasure(true);
 
+        // 型を取り除いた式を簡約する
+        assert_eq!(reducer.expr(), expr::a("ID", ":a"));
+        assert_eq!(
+            reducer.next().map(|result| result.expr),
+            Some(expr::a(expr::l("x", "x"), ":a"))
+        );
         assert_eq!(reducer.next().map(|result| result.expr), Some(expr::s("a")));
         assert_eq!(reducer.next().map(|result| result.expr), None);
     }
 
+    #[test]
+    fn test_reduce_let() {
+        for backend in [ReductionBackend::Tree, ReductionBackend::Graph] {
+            let new_reducer = |expr| {
+                let mut reducer = Reducer::new(
+                    Context::new(),
+                    Aliases::new(),
+                    expr,
+                    ReductionStrategy::NormalOrder,
+                );
+                reducer.set_backend(backend);
+                reducer
+            };
+
+            // 束縛を展開する1ステップと関数適用のステップは別に数える
+            let mut reducer = new_reducer(expr::let_(
+                "x",
+                ":a",
+                expr::a(expr::l("y", expr::a("y", "x")), ":f"),
+            ));
+            assert_eq!(
+                reducer.next().map(|result| result.expr),
+                Some(expr::a(expr::l("y", expr::a("y", ":a")), ":f"))
+            );
+            assert_eq!(
+                reducer.next().map(|result| result.expr),
+                Some(expr::a(":f", ":a"))
+            );
+            assert_eq!(reducer.next().map(|result| result.expr), None);
+
+            // letrec は本体の中の名前を letrec 式自身で置き換えて展開する
+            let g = || expr::a(":g", "f");
+            let mut reducer = new_reducer(expr::letrec("f", g(), "f"));
+            assert_eq!(
+                reducer.next().map(|result| result.expr),
+                Some(expr::letrec("f", g(), g()))
+            );
+            assert_eq!(
+                reducer.next().map(|result| result.expr),
+                Some(expr::a(":g", expr::letrec("f", g(), g())))
+            );
+        }
+    }
+
+    #[test]
+    fn test_reduce_strong_normalization() {
+        let (context, aliases) = setup();
+        for backend in [ReductionBackend::Tree, ReductionBackend::Graph] {
+            let new_reducer = |expr, strategy, eta| {
+                let mut reducer = Reducer::new(context.clone(), aliases.clone(), expr, strategy);
+                reducer.set_backend(backend);
+                reducer.set_strong_normalization(true);
+                reducer.set_eta_reduction(eta);
+                reducer
+            };
+
+            // 引数の足りない関数定義を展開し、ラムダ抽象の内側を簡約する
+            let reducer = new_reducer(
+                expr::a(expr::a("s", "k"), "k"),
+                ReductionStrategy::NormalOrder,
+                false,
+            );
+            let steps: Vec<_> = reducer.map(|result| result.expr).collect();
+            assert_eq!(
+                steps,
+                vec![
+                    expr::a(
+                        expr::a(
+                            expr::l(
+                                "x",
+                                expr::l(
+                                    "y",
+                                    expr::l("z", expr::a(expr::a("x", "z"), expr::a("y", "z")))
+                                )
+                            ),
+                            "k"
+                        ),
+                        "k"
+                    ),
+                    expr::a(
+                        expr::l(
+                            "y",
+                            expr::l("z", expr::a(expr::a("k", "z"), expr::a("y", "z")))
+                        ),
+                        "k"
+                    ),
+                    expr::l("z", expr::a(expr::a("k", "z"), expr::a("k", "z"))),
+                    expr::l("z", "z"),
+                ]
+            );
+
+            // ラムダ抽象の本体の中の簡約基は Path::Body で表す
+            let mut reducer = new_reducer(
+                expr::l("x", expr::a("i", "x")),
+                ReductionStrategy::NormalOrder,
+                false,
+            );
+            let result = reducer.next().unwrap();
+            assert_eq!(result.expr, expr::l("x", "x"));
+            assert_eq!(result.reduced_path, Path::Body(Box::new(Path::Callee(0))));
+            assert_eq!(reducer.next(), None);
+            assert_eq!(reducer.termination(), Some(&Termination::NormalForm));
+
+            // η簡約は本体の中より先に行う
+            let reducer = new_reducer(
+                expr::l("x", expr::a("i", "x")),
+                ReductionStrategy::NormalOrder,
+                true,
+            );
+            let steps: Vec<_> = reducer.map(|result| result.expr).collect();
+            assert_eq!(steps, vec![expr::v("i"), expr::l("x", "x")]);
+
+            // 束縛された変数は同名の関数定義を指さない
+            let mut reducer = new_reducer(
+                expr::l("k", expr::a(expr::a("k", ":a"), ":b")),
+                ReductionStrategy::NormalOrder,
+                false,
+            );
+            assert_eq!(reducer.next(), None);
+
+            // 名前呼びでも引数の中まで簡約する
+            let reducer = new_reducer(
+                expr::a(":f", expr::a("i", ":a")),
+                ReductionStrategy::CallByName,
+                false,
+            );
+            let steps: Vec<_> = reducer.map(|result| result.expr).collect();
+            assert_eq!(steps, vec![expr::a(":f", ":a")]);
+        }
+    }
+
+    #[test]
+    fn test_reduce_strong_normalization_capture() {
+        for backend in [ReductionBackend::Tree, ReductionBackend::Graph] {
+            let new_reducer = |context, expr| {
+                let mut reducer = Reducer::new(
+                    context,
+                    Aliases::new(),
+                    expr,
+                    ReductionStrategy::NormalOrder,
+                );
+                reducer.set_backend(backend);
+                reducer.set_strong_normalization(true);
+                reducer.set_max_steps(Some(200));
+                reducer
+            };
+
+            // 自由変数を含む引数を代入するとき、リネーム後の名前が引数の自由変数や置換する変数と衝突しない
+            // (x, X) => (X => (x => X)(:a))(x(X))
+            let reducer = new_reducer(
+                Context::new(),
+                expr::l(
+                    "x",
+                    expr::l(
+                        "X",
+                        expr::a(
+                            expr::l("X", expr::a(expr::l("x", "X"), ":a")),
+                            expr::a("x", "X"),
+                        ),
+                    ),
+                ),
+            );
+            assert_eq!(
+                reducer.last().map(|result| result.expr),
+                Some(expr::l("x", expr::l("X", expr::a("x", "X"))))
+            );
+
+            // 束縛変数の衝突が起きやすい Church 数の演算も正規形に達する
+            for (func, m, n, expected) in [("POW", 2, 3, 8), ("MUL", 2, 3, 6)] {
+                let mut reducer = new_reducer(
+                    Context::default(),
+                    expr::a(expr::a(func, church_numeral(m)), church_numeral(n)),
+                );
+                let last = reducer.by_ref().last().map(|result| result.expr);
+                assert_eq!(reducer.termination(), Some(&Termination::NormalForm));
+                assert_eq!(
+                    last.and_then(|expr| expr.as_church_numeral()),
+                    Some(expected)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduce_target() {
+        let (context, aliases) = setup();
+        for backend in [ReductionBackend::Tree, ReductionBackend::Graph] {
+            let new_reducer = |expr, target, strong| {
+                let mut reducer = Reducer::new(
+                    context.clone(),
+                    aliases.clone(),
+                    expr,
+                    ReductionStrategy::NormalOrder,
+                );
+                reducer.set_backend(backend);
+                reducer.set_target(target);
+                reducer.set_strong_normalization(strong);
+                reducer
+            };
+            let reached = |reducer: Reducer| -> Vec<_> {
+                reducer
+                    .map(|result| (result.expr, result.reached))
+                    .collect()
+            };
+            let expr = || expr::a(expr::a("k", expr::l("x", expr::a("i", "x"))), ":b");
+
+            // 先頭がシンボルの式は引数を簡約しなくても弱頭部正規形に達している
+            let reducer = new_reducer(
+                expr::a(":f", expr::a("i", ":a")),
+                ReductionTarget::WeakHeadNormalForm,
+                false,
+            );
+            assert_eq!(reducer.reached(), Some(ReductionTarget::HeadNormalForm));
+            assert_eq!(reached(reducer), vec![]);
+
+            // 頭部正規形と正規形を目標とすれば、強正規化でなくても先頭のラムダ抽象の内側を簡約する
+            for target in [ReductionTarget::HeadNormalForm, ReductionTarget::NormalForm] {
+                let mut reducer = new_reducer(expr(), target, false);
+                assert_eq!(
+                    reducer
+                        .by_ref()
+                        .map(|result| (result.expr, result.reached))
+                        .collect::<Vec<_>>(),
+                    vec![
+                        (
+                            expr::l("x", expr::a("i", "x")),
+                            Some(ReductionTarget::WeakHeadNormalForm)
+                        ),
+                        (expr::l("x", "x"), Some(ReductionTarget::NormalForm))
+                    ]
+                );
+                assert!(reducer.reached() >= Some(target));
+            }
+
+            let mut reducer = new_reducer(expr(), ReductionTarget::NormalForm, false);
+            reducer.run();
+            assert_eq!(reducer.termination(), Some(&Termination::NormalForm));
+
+            let mut reducer = new_reducer(expr(), ReductionTarget::WeakHeadNormalForm, false);
+            reducer.run();
+            assert_eq!(
+                reducer.termination(),
+                Some(&Termination::TargetReached(
+                    ReductionTarget::WeakHeadNormalForm
+                ))
+            );
+
+            let reducer = new_reducer(
+                expr::l("x", expr::a(expr::l("y", "y"), ":a")),
+                ReductionTarget::HeadNormalForm,
+                false,
+            );
+            assert_eq!(
+                reached(reducer),
+                vec![(expr::l("x", ":a"), Some(ReductionTarget::NormalForm))]
+            );
+
+            let reducer = new_reducer(
+                expr::l("y", expr::a(expr::l("z", "z"), "y")),
+                ReductionTarget::HeadNormalForm,
+                false,
+            );
+            assert_eq!(
+                reached(reducer),
+                vec![(expr::l("y", "y"), Some(ReductionTarget::NormalForm))]
+            );
+
+            // 強正規化でなければ引数の足りない関数定義は展開しない
+            let reducer = new_reducer(expr::a("s", "k"), ReductionTarget::HeadNormalForm, false);
+            assert_eq!(reached(reducer), vec![]);
+
+            // 目標を指定しなければ ReduceResult に達している形を含めない
+            let mut reducer = Reducer::new(
+                context.clone(),
+                aliases.clone(),
+                expr(),
+                ReductionStrategy::NormalOrder,
+            );
+            reducer.set_backend(backend);
+            assert_eq!(reducer.next().map(|result| result.reached), Some(None));
+
+            let reducer = new_reducer(expr(), ReductionTarget::WeakHeadNormalForm, true);
+            assert_eq!(
+                reached(reducer),
+                vec![(
+                    expr::l("x", expr::a("i", "x")),
+                    Some(ReductionTarget::WeakHeadNormalForm)
+                )]
+            );
+
+            let mut reducer = new_reducer(expr(), ReductionTarget::HeadNormalForm, true);
+            assert_eq!(reducer.reached(), None);
+            assert_eq!(
+                reducer
+                    .by_ref()
+                    .map(|result| result.reached)
+                    .collect::<Vec<_>>(),
+                vec![
+                    Some(ReductionTarget::WeakHeadNormalForm),
+                    Some(ReductionTarget::NormalForm)
+                ]
+            );
+            assert_eq!(reducer.expr(), expr::l("x", "x"));
+            // 正規形より弱い形を目標とした場合は、目標に達したことを終了理由とする
+            assert_eq!(
+                reducer.termination(),
+                Some(&Termination::TargetReached(ReductionTarget::HeadNormalForm))
+            );
+        }
+    }
+
     #[test]
     fn test_reduce_result_lambda_k_1() {
         let context = Context::new();
@@ -404,7 +1291,7 @@ mod tests {
         let k = expr::l("x", expr::l("y", "x"));
         let expr = expr::a(k, ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(
             reducer.next().map(|result| result.expr),
@@ -421,7 +1308,7 @@ mod tests {
         let k = expr::l("x", expr::l("y", "x"));
         let expr = expr::a(expr::a(k, ":a"), ":b");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(
             reducer.next().map(|result| result.expr),
@@ -437,7 +1324,7 @@ mod tests {
 
         let expr = expr::v("TRUE");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(reducer.next().map(|result| result.expr), None);
     }
@@ -448,7 +1335,7 @@ mod tests {
 
         let expr = expr::a(":a", "TRUE");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(reducer.next().map(|result| result.expr), None);
     }
@@ -459,7 +1346,7 @@ mod tests {
 
         let expr = expr::a(expr::a("TRUE", ":a"), ":b");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(
             reducer.next().map(|result| result.expr),
@@ -479,7 +1366,7 @@ mod tests {
 
         let expr = expr::a("i", ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(reducer.next().map(|result| result.expr), Some(":a".into()));
         assert_eq!(reducer.next().map(|result| result.expr), None);
@@ -491,7 +1378,7 @@ mod tests {
 
         let expr = expr::a("k", ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         // k の arity が2なのに対して引数を1つしか与えていないので簡約されない
         assert_eq!(reducer.next().map(|result| result.expr), None);
@@ -503,7 +1390,7 @@ mod tests {
 
         let expr = expr::a(expr::a("k", ":a"), ":b");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(reducer.next().map(|result| result.expr), Some(":a".into()));
         assert_eq!(reducer.next().map(|result| result.expr), None);
@@ -515,7 +1402,7 @@ mod tests {
 
         let expr = expr::a("s", ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         // s の arity が3なのに対して引数を1つしか与えていないので簡約されない
         assert_eq!(reducer.next().map(|result| result.expr), None);
@@ -527,7 +1414,7 @@ mod tests {
 
         let expr = expr::a(expr::a("s", ":a"), ":b");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         // s の arity が3なのに対して引数を2つしか与えていないので簡約されない
         assert_eq!(reducer.next().map(|result| result.expr), None);
@@ -539,7 +1426,7 @@ mod tests {
 
         let expr = expr::a(expr::a(expr::a("s", ":a"), ":b"), ":c");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(
             reducer.next().map(|result| result.expr),
@@ -555,7 +1442,7 @@ mod tests {
 
         let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(reducer.last().map(|result| result.expr), Some(":a".into()));
     }
@@ -567,7 +1454,7 @@ mod tests {
         // `:a``k:b:c
         let expr = expr::a(expr::s("a"), expr::a(expr::a("k", ":b"), ":c"));
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(
             reducer.next().map(|result| result.expr),
@@ -583,7 +1470,7 @@ mod tests {
         // ```:a`i:b`i:c
         let expr = expr::a(expr::a(":a", expr::a("i", ":b")), expr::a("i", ":c"));
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(
             reducer.next().map(|result| result.expr),
@@ -609,7 +1496,7 @@ mod tests {
             ":c",
         );
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         assert_eq!(
             reducer.next().map(|result| result.expr),
@@ -640,7 +1527,7 @@ mod tests {
         let (context, aliases) = setup();
         let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
         assert_eq!(
             reducer.reducible_path().as_ref().map(Vec::<usize>::from),
             Some(vec![3])
@@ -664,7 +1551,7 @@ mod tests {
         let (context, aliases) = setup();
         let expr = expr::a(expr::a(expr::a("s", "i"), expr::a("k", ":b")), ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
         assert_eq!(
             reducer.reducible_path().as_ref().map(Vec::<usize>::from),
             Some(vec![3])
@@ -694,7 +1581,7 @@ mod tests {
         let (context, aliases) = setup();
         let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         let result = reducer.next().unwrap();
         assert_eq!(Vec::<usize>::from(&result.reduced_path), vec![2]);
@@ -708,7 +1595,7 @@ mod tests {
         let (context, aliases) = setup();
         let expr = expr::a(expr::a(expr::a("s", "i"), expr::a("k", ":b")), ":a");
 
-        let mut reducer = Reducer::new(context, aliases, expr);
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
 
         let result = reducer.next().unwrap();
         assert_eq!(Vec::<usize>::from(&result.reduced_path), vec![2]);
@@ -719,4 +1606,377 @@ mod tests {
         let result = reducer.next().unwrap();
         assert_eq!(Vec::<usize>::from(&result.reduced_path), vec![1, 0]);
     }
+
+    fn reduce_all(strategy: ReductionStrategy, expr: expr::Expr) -> Vec<expr::Expr> {
+        let (context, aliases) = setup();
+        Reducer::new(context, aliases, expr, strategy)
+            .map(|result| result.expr)
+            .collect()
+    }
+
+    #[test]
+    fn test_strategy_normal_order() {
+        // ``k`i:a`i:b
+        let expr = expr::a(expr::a("k", expr::a("i", ":a")), expr::a("i", ":b"));
+        assert_eq!(
+            reduce_all(ReductionStrategy::NormalOrder, expr),
+            vec![expr::a("i", ":a"), expr::s("a")]
+        );
+
+        // `:f`i:a
+        let expr = expr::a(":f", expr::a("i", ":a"));
+        assert_eq!(
+            reduce_all(ReductionStrategy::NormalOrder, expr),
+            vec![expr::a(":f", ":a")]
+        );
+    }
+
+    #[test]
+    fn test_strategy_applicative_order() {
+        // ``k`i:a`i:b
+        let expr = expr::a(expr::a("k", expr::a("i", ":a")), expr::a("i", ":b"));
+        assert_eq!(
+            reduce_all(ReductionStrategy::ApplicativeOrder, expr),
+            vec![
+                expr::a(expr::a("k", ":a"), expr::a("i", ":b")),
+                expr::a(expr::a("k", ":a"), ":b"),
+                expr::s("a"),
+            ]
+        );
+
+        // `:f``k:a`i:b
+        let expr = expr::a(":f", expr::a(expr::a("k", ":a"), expr::a("i", ":b")));
+        assert_eq!(
+            reduce_all(ReductionStrategy::ApplicativeOrder, expr),
+            vec![
+                expr::a(":f", expr::a(expr::a("k", ":a"), ":b")),
+                expr::a(":f", ":a"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strategy_call_by_name() {
+        // ``k`i:a`i:b
+        let expr = expr::a(expr::a("k", expr::a("i", ":a")), expr::a("i", ":b"));
+        assert_eq!(
+            reduce_all(ReductionStrategy::CallByName, expr),
+            vec![expr::a("i", ":a"), expr::s("a")]
+        );
+
+        // 先頭が簡約できないので引数の中は簡約しない
+        let expr = expr::a(":f", expr::a("i", ":a"));
+        assert_eq!(
+            reduce_all(ReductionStrategy::CallByName, expr),
+            Vec::<expr::Expr>::new()
+        );
+    }
+
+    #[test]
+    fn test_strategy_call_by_value() {
+        // ``k`i:a`i:b
+        let expr = expr::a(expr::a("k", expr::a("i", ":a")), expr::a("i", ":b"));
+        assert_eq!(
+            reduce_all(ReductionStrategy::CallByValue, expr),
+            vec![
+                expr::a(expr::a("k", ":a"), expr::a("i", ":b")),
+                expr::a(expr::a("k", ":a"), ":b"),
+                expr::s("a"),
+            ]
+        );
+
+        // 先頭が簡約できないので引数の中は簡約しない
+        let expr = expr::a(":f", expr::a(expr::a("k", ":a"), expr::a("i", ":b")));
+        assert_eq!(
+            reduce_all(ReductionStrategy::CallByValue, expr),
+            Vec::<expr::Expr>::new()
+        );
+    }
+
+    #[test]
+    fn test_strategy_reducible_path() {
+        let (context, aliases) = setup();
+
+        // ```s`i:a`i:b`i:c
+        let expr = expr::a(
+            expr::a(expr::a("s", expr::a("i", ":a")), expr::a("i", ":b")),
+            expr::a("i", ":c"),
+        );
+
+        let reducer = Reducer::new(
+            context.clone(),
+            aliases.clone(),
+            expr.clone(),
+            ReductionStrategy::NormalOrder,
+        );
+        assert_eq!(
+            reducer.reducible_path().as_ref().map(Vec::<usize>::from),
+            Some(vec![3])
+        );
+
+        let reducer = Reducer::new(context, aliases, expr, ReductionStrategy::ApplicativeOrder);
+        assert_eq!(
+            reducer.reducible_path().as_ref().map(Vec::<usize>::from),
+            Some(vec![1, 1])
+        );
+    }
+
+    #[test]
+    fn test_termination_normal_form() {
+        let (context, aliases) = setup();
+        let expr = expr::a("i", ":a");
+
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
+        assert_eq!(reducer.termination(), None);
+
+        assert_eq!(reducer.next().map(|result| result.expr), Some(":a".into()));
+        assert_eq!(reducer.termination(), None);
+
+        assert_eq!(reducer.next().map(|result| result.expr), None);
+        assert_eq!(reducer.termination(), Some(&Termination::NormalForm));
+    }
+
+    #[test]
+    fn test_termination_step_limit() {
+        let (context, aliases) = setup();
+
+        // `^f.`^x.`f`xx^x.`f`xx:f
+        let y = expr::l(
+            "f",
+            expr::a(
+                expr::l("x", expr::a("f", expr::a("x", "x"))),
+                expr::l("x", expr::a("f", expr::a("x", "x"))),
+            ),
+        );
+        let expr = expr::a(y, ":f");
+
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
+        reducer.set_max_steps(Some(5));
+
+        assert_eq!(reducer.by_ref().count(), 5);
+        assert_eq!(
+            reducer.termination(),
+            Some(&Termination::StepLimit { max_steps: 5 })
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let (context, aliases) = setup();
+
+        // ```s`i:a`i:b:c
+        let expr = expr::a(
+            expr::a(expr::a("s", expr::a("i", ":a")), expr::a("i", ":b")),
+            ":c",
+        );
+
+        let reducer = |backend| {
+            let mut reducer = Reducer::new(
+                context.clone(),
+                aliases.clone(),
+                expr.clone(),
+                ReductionStrategy::NormalOrder,
+            );
+            reducer.set_backend(backend);
+            reducer
+        };
+
+        for backend in [ReductionBackend::Tree, ReductionBackend::Graph] {
+            let last = reducer(backend).last().unwrap();
+
+            let mut reducer = reducer(backend);
+            assert_eq!(reducer.run(), last.step);
+            assert_eq!(reducer.expr(), last.expr);
+            assert_eq!(reducer.termination(), Some(&Termination::NormalForm));
+        }
+
+        // 上限に達した場合も next() と同じところで止まる
+        let omega = expr::a(
+            expr::a(expr::a("s", "i"), "i"),
+            expr::a(expr::a("s", "i"), "i"),
+        );
+        let mut reducer = Reducer::new(context, aliases, omega, ReductionStrategy::NormalOrder);
+        reducer.set_max_steps(Some(5));
+        assert_eq!(reducer.run(), 5);
+        assert_eq!(
+            reducer.termination(),
+            Some(&Termination::StepLimit { max_steps: 5 })
+        );
+    }
+
+    #[test]
+    fn test_termination_size_limit() {
+        let (context, aliases) = setup();
+
+        // `^f.`^x.`f`xx^x.`f`xx:f
+        let y = expr::l(
+            "f",
+            expr::a(
+                expr::l("x", expr::a("f", expr::a("x", "x"))),
+                expr::l("x", expr::a("f", expr::a("x", "x"))),
+            ),
+        );
+        let expr = expr::a(y, ":f");
+
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
+        reducer.set_max_size(Some(20));
+
+        let last = reducer.by_ref().last().unwrap();
+        assert!(20 < last.expr.size());
+        assert_eq!(
+            reducer.termination(),
+            Some(&Termination::SizeLimit {
+                step: last.step,
+                size: last.expr.size()
+            })
+        );
+    }
+
+    #[test]
+    fn test_termination_cycle() {
+        let (context, aliases) = setup();
+
+        // `^x.`xx^y.`yy
+        let expr = expr::a(
+            expr::l("x", expr::a("x", "x")),
+            expr::l("y", expr::a("y", "y")),
+        );
+
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
+        reducer.set_cycle_detection(true);
+
+        assert_eq!(
+            reducer.next().map(|result| result.expr),
+            Some(expr::a(
+                expr::l("y", expr::a("y", "y")),
+                expr::l("y", expr::a("y", "y"))
+            ))
+        );
+        assert_eq!(reducer.next().map(|result| result.expr), None);
+        assert_eq!(
+            reducer.termination(),
+            Some(&Termination::Cycle { step: 1, since: 0 })
+        );
+    }
+
+    fn reduce_last(
+        context: Context,
+        backend: ReductionBackend,
+        strategy: ReductionStrategy,
+        expr: expr::Expr,
+    ) -> ReduceResult {
+        let mut reducer = Reducer::new(context, Aliases::new(), expr, strategy);
+        reducer.set_backend(backend);
+        reducer.last().unwrap()
+    }
+
+    #[test]
+    fn test_graph_backend() {
+        let exprs = [
+            // ``ADD 2 3 :f :x
+            expr::a(
+                expr::a(
+                    expr::a(expr::a("ADD", church_numeral(2)), church_numeral(3)),
+                    ":f",
+                ),
+                ":x",
+            ),
+            // ``MUL 3 4 :f :x
+            expr::a(
+                expr::a(
+                    expr::a(expr::a("MUL", church_numeral(3)), church_numeral(4)),
+                    ":f",
+                ),
+                ":x",
+            ),
+            // ``POW 2 3 :f :x
+            expr::a(
+                expr::a(
+                    expr::a(expr::a("POW", church_numeral(2)), church_numeral(3)),
+                    ":f",
+                ),
+                ":x",
+            ),
+            // `IF `IS_ZERO `PRED 1 :a :b
+            expr::a(
+                expr::a(
+                    expr::a("IF", expr::a("IS_ZERO", expr::a("PRED", church_numeral(1)))),
+                    ":a",
+                ),
+                ":b",
+            ),
+        ];
+
+        for strategy in [
+            ReductionStrategy::NormalOrder,
+            ReductionStrategy::ApplicativeOrder,
+        ] {
+            for expr in exprs.iter() {
+                let context = Context::default();
+                let tree = reduce_last(
+                    context.clone(),
+                    ReductionBackend::Tree,
+                    strategy,
+                    expr.clone(),
+                );
+                let graph = reduce_last(context, ReductionBackend::Graph, strategy, expr.clone());
+
+                assert_eq!(graph.expr, tree.expr);
+                assert!(graph.step <= tree.step);
+            }
+        }
+    }
+
+    #[test]
+    fn test_graph_backend_shares_work() {
+        // ``POW 3 3 :f :x は共有によって簡約ステップ数が大幅に減る
+        // 3 を関数として定義しておくと、その展開も共有される
+        let mut context = Context::default();
+        context
+            .def(func::new("THREE", Vec::<&str>::new(), church_numeral(3)))
+            .unwrap();
+        let expr = expr::a(
+            expr::a(expr::a(expr::a("POW", "THREE"), "THREE"), ":f"),
+            ":x",
+        );
+
+        let strategy = ReductionStrategy::NormalOrder;
+        let tree = reduce_last(
+            context.clone(),
+            ReductionBackend::Tree,
+            strategy,
+            expr.clone(),
+        );
+        let graph = reduce_last(context, ReductionBackend::Graph, strategy, expr);
+
+        assert_eq!(graph.expr.size(), 27 * 2 + 1);
+        assert_eq!(graph.expr, tree.expr);
+        assert!(graph.step * 2 < tree.step);
+    }
+
+    #[test]
+    fn test_set_backend() {
+        let (context, aliases) = setup();
+
+        // ```s`i:a`i:b:c
+        let expr = expr::a(
+            expr::a(expr::a("s", expr::a("i", ":a")), expr::a("i", ":b")),
+            ":c",
+        );
+
+        let mut reducer = Reducer::new(context, aliases, expr, ReductionStrategy::NormalOrder);
+        assert_eq!(reducer.backend(), ReductionBackend::Tree);
+
+        reducer.next();
+        reducer.set_backend(ReductionBackend::Graph);
+        assert_eq!(reducer.backend(), ReductionBackend::Graph);
+
+        let result = reducer.next().unwrap();
+        assert_eq!(result.step, 2);
+        assert_eq!(
+            result.expr,
+            expr::a(expr::a(":a", ":c"), expr::a(expr::a("i", ":b"), ":c"))
+        );
+        assert_eq!(Vec::<usize>::from(&result.reduced_path), vec![0]);
+    }
 }
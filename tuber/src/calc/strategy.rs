@@ -0,0 +1,44 @@
+/// 簡約基の選び方 (評価戦略) を表現する
+///
+/// いずれの戦略も、引数を与えられていないラムダ抽象の内側は簡約しない (強正規化を除く)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ReductionStrategy {
+    /// 正規順序: 最左最外の簡約基から簡約する
+    ///
+    /// 先頭が簡約できなくなった後も、引数の中の簡約基を左から順に簡約し続ける
+    #[default]
+    NormalOrder,
+
+    /// 適用順序: 最左最内の簡約基から簡約する
+    ///
+    /// 関数を適用する前に、その関数に渡される引数を先に簡約する
+    ApplicativeOrder,
+
+    /// 名前呼び: 先頭の簡約基のみを簡約する
+    ///
+    /// 先頭が簡約できなくなった時点で停止し、引数の中は簡約しない
+    CallByName,
+
+    /// 値呼び: 引数を先に簡約してから関数を適用する
+    ///
+    /// 名前呼びと同じく、先頭が簡約できなくなった時点で停止する
+    CallByValue,
+}
+
+impl ReductionStrategy {
+    /// 先頭が簡約できなくなった後も引数の中を簡約し続けるかどうか
+    pub fn reduces_stuck_args(&self) -> bool {
+        match self {
+            ReductionStrategy::NormalOrder | ReductionStrategy::ApplicativeOrder => true,
+            ReductionStrategy::CallByName | ReductionStrategy::CallByValue => false,
+        }
+    }
+
+    /// 関数を適用する前に、その関数に渡される引数を先に簡約するかどうか
+    pub fn reduces_args_first(&self) -> bool {
+        match self {
+            ReductionStrategy::ApplicativeOrder | ReductionStrategy::CallByValue => true,
+            ReductionStrategy::NormalOrder | ReductionStrategy::CallByName => false,
+        }
+    }
+}
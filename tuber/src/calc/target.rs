@@ -0,0 +1,25 @@
+/// 簡約の目標とする形
+///
+/// 弱い順に並んでおり、強い形に達した式はそれより弱い形にも達している
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReductionTarget {
+    /// 弱頭部正規形: 先頭が引数を与えても簡約できない項 (ラムダ抽象やシンボルなど)
+    WeakHeadNormalForm,
+
+    /// 頭部正規形: 先頭のラムダ抽象の内側の先頭も簡約できない形
+    ///
+    /// 強正規化のときは、引数の足りない関数定義も展開した形
+    HeadNormalForm,
+
+    /// 正規形: 頭部正規形であり、評価戦略と強正規化の設定のもとで簡約基を含まない
+    #[default]
+    NormalForm,
+}
+
+impl ReductionTarget {
+    pub const ALL: [ReductionTarget; 3] = [
+        ReductionTarget::WeakHeadNormalForm,
+        ReductionTarget::HeadNormalForm,
+        ReductionTarget::NormalForm,
+    ];
+}
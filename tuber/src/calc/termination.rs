@@ -0,0 +1,20 @@
+use super::target::ReductionTarget;
+
+/// 簡約列が終了した理由
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Termination {
+    /// 簡約基がなくなった (正規形に達した)
+    NormalForm,
+
+    /// 正規形より弱い目標とする形 (弱頭部正規形か頭部正規形) に達した
+    TargetReached(ReductionTarget),
+
+    /// 簡約ステップ数が上限に達した
+    StepLimit { max_steps: usize },
+
+    /// 式の大きさが上限を超えた
+    SizeLimit { step: usize, size: usize },
+
+    /// step 番目の式が since 番目の式と (α同値の意味で) 一致した
+    Cycle { step: usize, since: usize },
+}
@@ -0,0 +1,373 @@
+use crate::context::Context;
+use crate::expr::{self, Expr, Identifier};
+
+/// Kiselyov の方法でラムダ抽象を除去する
+///
+/// 必要な n 引数版の B, C, S (B2, C3 など) がすべて Context で定義されていれば、
+/// 結果の大きさは de Bruijn インデックスで表した式の大きさに比例する
+/// 定義されていないコンビネータは s, k, i と定義されているものの組み合わせで置き換える
+pub fn unlambda(context: &Context, expr: Expr) -> Expr {
+    let kiselyov = Kiselyov::new(context);
+    unlambda_(context, &kiselyov, expr)
+}
+
+fn unlambda_(context: &Context, kiselyov: &Kiselyov, expr: Expr) -> Expr {
+    match expr {
+        Expr::Variable(ref id) => {
+            if is_combinator(id) {
+                expr
+            } else {
+                match context.get(id) {
+                    Some(func) => unlambda_(context, kiselyov, func.to_owned().into()),
+                    None => expr,
+                }
+            }
+        }
+        Expr::Symbol(_) => expr,
+        Expr::Apply { lhs, rhs } => expr::a(
+            unlambda_(context, kiselyov, *lhs),
+            unlambda_(context, kiselyov, *rhs),
+        ),
+        Expr::Lambda { .. } => {
+            let (_, compiled) = kiselyov.compile(&Term::from(&expr));
+            unlambda_(context, kiselyov, compiled)
+        }
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda_(context, kiselyov, *body)
+        }
+        // let 式はラムダ抽象の適用に書き換える
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda_(context, kiselyov, expr)
+        }
+    }
+}
+
+// 結果に現れるコンビネータ (展開しない)
+fn is_combinator(id: &Identifier) -> bool {
+    let name = id.as_str();
+    if ["s", "k", "i", "B", "C"].contains(&name) {
+        return true;
+    }
+
+    let mut chars = name.chars();
+    matches!(chars.next(), Some('B' | 'C' | 'S'))
+        && !chars.as_str().is_empty()
+        && chars.all(|c| c.is_ascii_digit())
+}
+
+// ========================================================================== //
+
+// de Bruijn インデックスで表した式
+enum Term {
+    Zero,
+    Succ(Box<Term>),
+    Lambda(Box<Term>),
+    Apply(Box<Term>, Box<Term>),
+
+    // 束縛されていない変数やシンボル
+    Constant(Expr),
+}
+
+impl From<&Expr> for Term {
+    fn from(expr: &Expr) -> Self {
+        to_term(expr, &mut Vec::new())
+    }
+}
+
+fn to_term(expr: &Expr, params: &mut Vec<Identifier>) -> Term {
+    match expr {
+        Expr::Variable(id) => match params.iter().rev().position(|param| param == id) {
+            Some(index) => (0..index).fold(Term::Zero, |term, _| Term::Succ(Box::new(term))),
+            None => Term::Constant(expr.clone()),
+        },
+        Expr::Symbol(_) => Term::Constant(expr.clone()),
+        Expr::Apply { lhs, rhs } => Term::Apply(
+            Box::new(to_term(lhs, params)),
+            Box::new(to_term(rhs, params)),
+        ),
+        Expr::Lambda { param, body, .. } => {
+            params.push(param.clone());
+            let body = to_term(body, params);
+            params.pop();
+            Term::Lambda(Box::new(body))
+        }
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => to_term(body, params),
+        Expr::Let { .. } => {
+            let mut expr = expr.clone();
+            expr.desugar_let();
+            to_term(&expr, params)
+        }
+    }
+}
+
+// ========================================================================== //
+
+// Context で定義されているコンビネータ
+struct Kiselyov<'a> {
+    context: &'a Context,
+}
+
+#[derive(Clone, Copy)]
+enum Bulk {
+    B,
+    C,
+    S,
+}
+
+impl<'a> Kiselyov<'a> {
+    fn new(context: &'a Context) -> Self {
+        Self { context }
+    }
+
+    // (n, d): 内側から n 個の変数を引数として d に与えると元の式になる
+    fn compile(&self, term: &Term) -> (usize, Expr) {
+        match term {
+            Term::Zero => (1, expr::v("i")),
+            Term::Succ(term) => {
+                let (n, d) = self.compile(term);
+                (n + 1, self.combine((0, expr::v("k")), (n, d)))
+            }
+            Term::Lambda(body) => match self.compile(body) {
+                (0, d) => (0, expr::a("k", d)),
+                (n, d) => (n - 1, d),
+            },
+            Term::Apply(lhs, rhs) => {
+                let (n1, d1) = self.compile(lhs);
+                let (n2, d2) = self.compile(rhs);
+                (n1.max(n2), self.combine((n1, d1), (n2, d2)))
+            }
+            Term::Constant(expr) => (0, expr.clone()),
+        }
+    }
+
+    fn combine(&self, (n1, d1): (usize, Expr), (n2, d2): (usize, Expr)) -> Expr {
+        match (n1, n2) {
+            (0, 0) => expr::a(d1, d2),
+            // B d1 I => d1
+            (0, 1) if d2 == expr::v("i") => d1,
+            (0, n) => self.apply(Bulk::B, n, d1, d2),
+            (n, 0) => self.apply(Bulk::C, n, d1, d2),
+            (n, m) if n == m => self.apply(Bulk::S, n, d1, d2),
+            // B_{m-n} (S_n d1) d2
+            (n, m) if n < m => {
+                let s = self.partial(Bulk::S, n, d1);
+                self.apply(Bulk::B, m - n, s, d2)
+            }
+            // C_{n-m} (B_{n-m} S_m d1) d2
+            (n, m) => match self.combinator(Bulk::S, m) {
+                Some(s) => {
+                    let b = self.apply(Bulk::B, n - m, s, d1);
+                    self.apply(Bulk::C, n - m, b, d2)
+                }
+                // S_n d1 (K^{n-m} d2)
+                None => {
+                    let d2 = (m..n).fold(d2, |d, _| expr::a("k", d));
+                    self.apply(Bulk::S, n, d1, d2)
+                }
+            },
+        }
+    }
+
+    // X_n f g
+    fn apply(&self, bulk: Bulk, n: usize, f: Expr, g: Expr) -> Expr {
+        if let Some(x) = self.combinator(bulk, n) {
+            return expr::a(expr::a(x, f), g);
+        }
+
+        match (bulk, n) {
+            // B f g = S (K f) g
+            (Bulk::B, 1) => expr::a(expr::a("s", expr::a("k", f)), g),
+            // C f g = S f (K g)
+            (Bulk::C, 1) => expr::a(expr::a("s", f), expr::a("k", g)),
+            // B_n f g = B_{n-1} (B f) g
+            (Bulk::B, n) => {
+                let f = self.partial(Bulk::B, 1, f);
+                self.apply(Bulk::B, n - 1, f, g)
+            }
+            // C_n f g = C_{n-1} (B_{n-1} C f) g
+            (Bulk::C, n) => match self.combinator(Bulk::C, 1) {
+                Some(c) => {
+                    let f = self.apply(Bulk::B, n - 1, c, f);
+                    self.apply(Bulk::C, n - 1, f, g)
+                }
+                // C_n f g = S_n f (K^n g)
+                None => {
+                    let g = (0..n).fold(g, |g, _| expr::a("k", g));
+                    self.apply(Bulk::S, n, f, g)
+                }
+            },
+            // S_n f g = S_{n-1} (B_{n-1} S f) g
+            (Bulk::S, n) => {
+                let f = self.apply(Bulk::B, n - 1, expr::v("s"), f);
+                self.apply(Bulk::S, n - 1, f, g)
+            }
+        }
+    }
+
+    // X_n f
+    fn partial(&self, bulk: Bulk, n: usize, f: Expr) -> Expr {
+        if let Some(x) = self.combinator(bulk, n) {
+            return expr::a(x, f);
+        }
+
+        match (bulk, n) {
+            // B f = S (K f)
+            (Bulk::B, 1) => expr::a("s", expr::a("k", f)),
+            // S_n f = S_{n-1} (B_{n-1} S f)
+            (Bulk::S, n) => {
+                let f = self.apply(Bulk::B, n - 1, expr::v("s"), f);
+                self.partial(Bulk::S, n - 1, f)
+            }
+            _ => unreachable!("partial application of B_n or C_n is not used"),
+        }
+    }
+
+    // Context で定義されている X_n
+    fn combinator(&self, bulk: Bulk, n: usize) -> Option<Expr> {
+        let name = match (bulk, n) {
+            (Bulk::S, 1) => return Some(expr::v("s")),
+            (Bulk::B, 1) => "B".to_string(),
+            (Bulk::C, 1) => "C".to_string(),
+            (Bulk::B, n) => format!("B{}", n),
+            (Bulk::C, n) => format!("C{}", n),
+            (Bulk::S, n) => format!("S{}", n),
+        };
+        let id = Identifier::from(name);
+        self.context.get(&id).map(|_| expr::v(id))
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{unlambda_recursive, Aliases, Reducer, ReductionStrategy};
+    use crate::parser::parse_expr;
+
+    fn context(modules: &[&str]) -> Context {
+        let mut context = Context::default();
+        for module in modules {
+            context.import(module).unwrap();
+        }
+        context
+    }
+
+    // 引数を与えて正規形まで簡約する
+    fn reduce(context: &Context, expr: Expr, args: &[&str]) -> Expr {
+        let expr = args
+            .iter()
+            .fold(expr, |e, arg| expr::a(e, parse_expr(arg).unwrap()));
+        let mut reducer = Reducer::new(
+            context.clone(),
+            Aliases::new(),
+            expr,
+            ReductionStrategy::NormalOrder,
+        );
+        reducer.set_max_steps(Some(1000));
+        reducer.by_ref().for_each(drop);
+        reducer.expr()
+    }
+
+    #[test]
+    fn test_unlambda() {
+        let context = context(&["turner", "bulk"]);
+        let cases = [
+            ("x => x", "i"),
+            ("x => :a", "k(:a)"),
+            ("x => :f(x)", ":f"),
+            ("x => y => x", "k"),
+            ("x => y => :f(y)", "k(:f)"),
+            ("x => y => y(x)", "B(s(i), k)"),
+        ];
+
+        for (source, expected) in cases {
+            assert_eq!(
+                unlambda(&context, parse_expr(source).unwrap()),
+                parse_expr(expected).unwrap(),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_combinator() {
+        for name in ["s", "k", "i", "B", "C", "B2", "C10", "S3"] {
+            assert!(is_combinator(&name.into()), "{}", name);
+        }
+        for name in ["S", "B_", "CONS", "ADD", "x"] {
+            assert!(!is_combinator(&name.into()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let cases: [(&str, &[&str]); 7] = [
+            ("NOT", &["TRUE", ":a", ":b"]),
+            ("AND", &["TRUE", "FALSE", ":a", ":b"]),
+            ("CONS", &[":a", ":b", "k"]),
+            ("ADD", &["2", "3", ":f", ":x"]),
+            ("x => y => z => x(z, y(z))", &[":a", ":b", ":c"]),
+            ("f => g => x => f(g(x), x, g(f))", &[":a", ":b", ":c"]),
+            (
+                "a => b => c => d => e => e(a, :z, c(b, d))",
+                &[":a", ":b", ":c", ":d", ":e"],
+            ),
+        ];
+
+        // 使えるコンビネータが少なくても同じ関数になる
+        for modules in [&[][..], &["turner"], &["bulk"], &["turner", "bulk"]] {
+            let context = context(modules);
+            for (source, args) in cases {
+                let expr = parse_expr(source).unwrap();
+                let result = unlambda(&context, expr.clone());
+                assert_eq!(
+                    reduce(&context, result, args),
+                    reduce(&context, expr, args),
+                    "{} {:?}",
+                    source,
+                    modules
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_defined_only() {
+        let context = Context::default();
+        let expr = parse_expr("a => b => c => d => e => e(a, :z, c(b, d))").unwrap();
+        let result = unlambda(&context, expr);
+        assert!(result.to_string().chars().all(|c| "`ski:z".contains(c)));
+    }
+
+    #[test]
+    fn test_linear() {
+        // 変数を逆順に適用する式: a => b => … => h(g, …, a)
+        let reverse = |n: usize| {
+            let params: Vec<String> = (b'a'..b'a' + n as u8)
+                .map(|c| (c as char).to_string())
+                .collect();
+            let body = params
+                .iter()
+                .rev()
+                .skip(1)
+                .fold(expr::v(params[n - 1].as_str()), |e, p| {
+                    expr::a(e, p.as_str())
+                });
+            params
+                .iter()
+                .rev()
+                .fold(body, |e, p| expr::l(p.as_str(), e))
+        };
+
+        let context = context(&["turner", "bulk"]);
+        for n in [8, 12] {
+            let kiselyov = unlambda(&context, reverse(n));
+            let ski = unlambda_recursive(&context, reverse(n));
+            assert!(kiselyov.size() < ski.size(), "{}", n);
+        }
+    }
+}
@@ -1,16 +1,22 @@
+mod kiselyov;
 mod recursive_iota;
 mod recursive_sk;
 mod recursive_ski;
 mod shallow_sk;
 mod shallow_ski;
+mod trace;
+mod turner;
 
 use crate::context::Context;
 use crate::expr::{Expr, Identifier};
+pub use kiselyov::unlambda as unlambda_kiselyov;
 pub use recursive_iota::unlambda as unlambda_recursive_iota;
 pub use recursive_sk::unlambda as unlambda_recursive_sk;
 pub use recursive_ski::unlambda as unlambda_recursive_ski;
 pub use shallow_sk::unlambda as unlambda_shallow_sk;
 pub use shallow_ski::unlambda as unlambda_shallow_ski;
+pub use trace::{AbstractionRule, UnlambdaStep, UnlambdaTrace, UnlambdaTraceError};
+pub use turner::unlambda as unlambda_turner;
 
 pub fn unlambda_shallow(expr: Expr) -> Expr {
     let ski = [&("s".into()), &("k".into()), &("i".into())];
@@ -56,6 +62,8 @@ pub fn unlambda_recursive_(strategy: &RecursiveStrategy, context: &Context, expr
         RecursiveStrategy::SKWith { s, k } => unlambda_recursive_sk(context, expr, &[s, k]),
         RecursiveStrategy::Iota => unlambda_recursive_iota(context, expr, &("ι".into())),
         RecursiveStrategy::IotaWith { iota } => unlambda_recursive_iota(context, expr, iota),
+        RecursiveStrategy::Turner => unlambda_turner(context, expr),
+        RecursiveStrategy::Kiselyov => unlambda_kiselyov(context, expr),
     }
 }
 
@@ -78,7 +86,7 @@ pub enum ShallowStrategy {
 }
 
 #[allow(dead_code)]
-#[derive(PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum RecursiveStrategy {
     SKI,
     SKIWith {
@@ -95,4 +103,8 @@ pub enum RecursiveStrategy {
     IotaWith {
         iota: Identifier,
     },
+    /// B, C, S_PRIME, B_STAR, C_PRIME のうち Context で定義されているものを使う
+    Turner,
+    /// B, C と n 引数版の B2, C2, S2 などのうち Context で定義されているものを使う
+    Kiselyov,
 }
@@ -18,7 +18,16 @@ pub fn unlambda(context: &Context, expr: Expr, iota: &Identifier) -> Expr {
         Expr::Apply { lhs, rhs } => {
             expr::a(unlambda(context, *lhs, iota), unlambda(context, *rhs, iota))
         }
-        Expr::Lambda { param, body } => unlambda(context, unlambda_(*body, &param, iota), iota),
+        Expr::Lambda { param, body, .. } => unlambda(context, unlambda_(*body, &param, iota), iota),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda(context, *body, iota)
+        }
+        // let 式はラムダ抽象の適用に書き換える
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda(context, expr, iota)
+        }
     }
 }
 
@@ -63,8 +72,16 @@ fn unlambda_(expr: Expr, param: &Identifier, iota: &Identifier) -> Expr {
                 )
             }
         },
-        Expr::Lambda { param: inner, body } => {
-            unlambda_(unlambda_(*body, &inner, iota), param, iota)
+        Expr::Lambda {
+            param: inner, body, ..
+        } => unlambda_(unlambda_(*body, &inner, iota), param, iota),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda_(*body, param, iota)
+        }
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda_(expr, param, iota)
         }
     }
 }
@@ -18,7 +18,16 @@ pub fn unlambda(context: &Context, expr: Expr, sk: &[&Identifier; 2]) -> Expr {
         Expr::Apply { lhs, rhs } => {
             expr::a(unlambda(context, *lhs, sk), unlambda(context, *rhs, sk))
         }
-        Expr::Lambda { param, body } => unlambda(context, unlambda_(*body, &param, sk), sk),
+        Expr::Lambda { param, body, .. } => unlambda(context, unlambda_(*body, &param, sk), sk),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda(context, *body, sk)
+        }
+        // let 式はラムダ抽象の適用に書き換える
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda(context, expr, sk)
+        }
     }
 }
 
@@ -45,7 +54,17 @@ fn unlambda_(expr: Expr, param: &Identifier, sk: &[&Identifier; 2]) -> Expr {
                 unlambda_(*rhs, param, sk),
             ),
         },
-        Expr::Lambda { param: inner, body } => unlambda_(unlambda_(*body, &inner, sk), param, sk),
+        Expr::Lambda {
+            param: inner, body, ..
+        } => unlambda_(unlambda_(*body, &inner, sk), param, sk),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda_(*body, param, sk)
+        }
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda_(expr, param, sk)
+        }
     }
 }
 
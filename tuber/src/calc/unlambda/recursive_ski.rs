@@ -18,7 +18,16 @@ pub fn unlambda(context: &Context, expr: Expr, ski: &[&Identifier; 3]) -> Expr {
         Expr::Apply { lhs, rhs } => {
             expr::a(unlambda(context, *lhs, ski), unlambda(context, *rhs, ski))
         }
-        Expr::Lambda { param, body } => unlambda(context, unlambda_(*body, &param, ski), ski),
+        Expr::Lambda { param, body, .. } => unlambda(context, unlambda_(*body, &param, ski), ski),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda(context, *body, ski)
+        }
+        // let 式はラムダ抽象の適用に書き換える
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda(context, expr, ski)
+        }
     }
 }
 
@@ -43,7 +52,17 @@ fn unlambda_(expr: Expr, param: &Identifier, ski: &[&Identifier; 3]) -> Expr {
                 unlambda_(*rhs, param, ski),
             ),
         },
-        Expr::Lambda { param: inner, body } => unlambda_(unlambda_(*body, &inner, ski), param, ski),
+        Expr::Lambda {
+            param: inner, body, ..
+        } => unlambda_(unlambda_(*body, &inner, ski), param, ski),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda_(*body, param, ski)
+        }
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda_(expr, param, ski)
+        }
     }
 }
 
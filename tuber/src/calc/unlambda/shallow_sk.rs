@@ -6,7 +6,14 @@ pub fn unlambda(expr: Expr, sk: &[&Identifier; 2]) -> Expr {
         Expr::Variable(_) => expr,
         Expr::Symbol(_) => expr,
         Expr::Apply { lhs, rhs } => expr::a(unlambda(*lhs, sk), unlambda(*rhs, sk)),
-        Expr::Lambda { param, body } => unlambda_(*body, &param, sk),
+        Expr::Lambda { param, body, .. } => unlambda_(*body, &param, sk),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => unlambda(*body, sk),
+        // let 式はラムダ抽象の適用に書き換える
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda(expr, sk)
+        }
     }
 }
 
@@ -33,7 +40,17 @@ fn unlambda_(expr: Expr, param: &Identifier, sk: &[&Identifier; 2]) -> Expr {
                 unlambda_(*rhs, param, sk),
             ),
         },
-        Expr::Lambda { param: inner, body } => unlambda_(unlambda_(*body, &inner, sk), param, sk),
+        Expr::Lambda {
+            param: inner, body, ..
+        } => unlambda_(unlambda_(*body, &inner, sk), param, sk),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda_(*body, param, sk)
+        }
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda_(expr, param, sk)
+        }
     }
 }
 
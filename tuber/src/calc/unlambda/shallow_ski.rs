@@ -6,7 +6,14 @@ pub fn unlambda(expr: Expr, ski: &[&Identifier; 3]) -> Expr {
         Expr::Variable(_) => expr,
         Expr::Symbol(_) => expr,
         Expr::Apply { lhs, rhs } => expr::a(unlambda(*lhs, ski), unlambda(*rhs, ski)),
-        Expr::Lambda { param, body } => unlambda_(*body, &param, ski),
+        Expr::Lambda { param, body, .. } => unlambda_(*body, &param, ski),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => unlambda(*body, ski),
+        // let 式はラムダ抽象の適用に書き換える
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda(expr, ski)
+        }
     }
 }
 
@@ -31,7 +38,17 @@ fn unlambda_(expr: Expr, param: &Identifier, ski: &[&Identifier; 3]) -> Expr {
                 unlambda_(*rhs, param, ski),
             ),
         },
-        Expr::Lambda { param: inner, body } => unlambda_(unlambda_(*body, &inner, ski), param, ski),
+        Expr::Lambda {
+            param: inner, body, ..
+        } => unlambda_(unlambda_(*body, &inner, ski), param, ski),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda_(*body, param, ski)
+        }
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda_(expr, param, ski)
+        }
     }
 }
 
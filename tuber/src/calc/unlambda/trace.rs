@@ -0,0 +1,466 @@
+use super::RecursiveStrategy;
+use crate::context::Context;
+use crate::expr::{self, Direction, Expr, FreeVars, Identifier, Path};
+use std::fmt::Display;
+
+/// ラムダ抽象の除去 (unlambda_recursive_) を1ステップずつ行うイテレータ
+///
+/// 各ステップでは、ラムダ抽象を含まない本体をもつラムダ抽象のうち最も左にあるものに規則を1つ適用する
+/// ラムダ抽象がなくなったら、Context で定義された関数を1つずつ展開する
+/// 再帰的に定義された関数を含む式では終わらないことがある
+/// RecursiveStrategy::Turner と RecursiveStrategy::Kiselyov の規則には対応しておらず、作成時にエラーを返す
+pub struct UnlambdaTrace {
+    context: Context,
+    strategy: RecursiveStrategy,
+    expr: Expr,
+    step: usize,
+}
+
+/// ラムダ抽象の除去の1ステップ
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnlambdaStep {
+    pub step: usize,
+    pub rule: AbstractionRule,
+    pub expr: Expr,
+
+    /// 書き換えた部分式に至る経路
+    ///
+    /// 書き換えた部分式がラムダ抽象の本体の中にある場合は、そのラムダ抽象に至る経路になる
+    pub path: Path,
+}
+
+/// ラムダ抽象の除去に用いる規則
+#[derive(Clone, Debug, PartialEq)]
+pub enum AbstractionRule {
+    /// T[λx.x] => I
+    Identity,
+
+    /// T[λx.E] => K E (E に x が自由に現れない)
+    Constant,
+
+    /// T[λx.E x] => E (E に x が自由に現れない)
+    Eta,
+
+    /// T[λx.E1 E2] => S T[λx.E1] T[λx.E2]
+    Composition,
+
+    /// 関数を定義に置き換える
+    Expand(Identifier),
+}
+
+impl Display for AbstractionRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AbstractionRule::Identity => write!(f, "T[λx.x] => I"),
+            AbstractionRule::Constant => write!(f, "T[λx.E] => K E"),
+            AbstractionRule::Eta => write!(f, "T[λx.E x] => E"),
+            AbstractionRule::Composition => write!(f, "T[λx.E1 E2] => S T[λx.E1] T[λx.E2]"),
+            AbstractionRule::Expand(id) => write!(f, "expand {}", id),
+        }
+    }
+}
+
+/// UnlambdaTrace を作成できなかった理由
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnlambdaTraceError {
+    /// 1ステップずつの除去に対応していない戦略 (Turner, Kiselyov) を指定した
+    UnsupportedStrategy(RecursiveStrategy),
+}
+
+impl Display for UnlambdaTraceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnlambdaTraceError::UnsupportedStrategy(strategy) => write!(
+                f,
+                "the {:?} strategy cannot be traced step by step; use SKI, SK or Iota",
+                strategy
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UnlambdaTraceError {}
+
+impl UnlambdaTrace {
+    pub fn new(
+        context: Context,
+        strategy: RecursiveStrategy,
+        mut expr: Expr,
+    ) -> Result<Self, UnlambdaTraceError> {
+        if let RecursiveStrategy::Turner | RecursiveStrategy::Kiselyov = strategy {
+            return Err(UnlambdaTraceError::UnsupportedStrategy(strategy));
+        }
+
+        expr.erase_types();
+        expr.desugar_let();
+        Ok(Self {
+            context,
+            strategy,
+            expr,
+            step: 0,
+        })
+    }
+
+    pub fn expr(&self) -> Expr {
+        self.expr.clone()
+    }
+
+    // 書き換える部分式の位置と、適用する規則で書き換えた式
+    fn rewrite(&self) -> Option<(Vec<Direction>, AbstractionRule, Expr)> {
+        let mut position = Vec::new();
+        if find_abstraction(&self.expr, &mut position) {
+            let Expr::Lambda { param, body, .. } = at(&self.expr, &position) else {
+                unreachable!("find_abstraction returns the position of a lambda");
+            };
+            let (rule, expr) = self.abstract_(param, body);
+            return Some((position, rule, expr));
+        }
+
+        let mut position = Vec::new();
+        if self.find_expansion(&self.expr, &mut position) {
+            let Expr::Variable(id) = at(&self.expr, &position) else {
+                unreachable!("find_expansion returns the position of a variable");
+            };
+            let mut expansion: Expr = self.context.get(id).unwrap().clone().into();
+            expansion.erase_types();
+            expansion.desugar_let();
+            return Some((position, AbstractionRule::Expand(id.clone()), expansion));
+        }
+
+        None
+    }
+
+    // 本体にラムダ抽象を含まないラムダ抽象 λparam.body に規則を1つ適用する
+    fn abstract_(&self, param: &Identifier, body: &Expr) -> (AbstractionRule, Expr) {
+        match body {
+            Expr::Variable(id) if id == param => (AbstractionRule::Identity, self.i()),
+            _ if !FreeVars::from(body).contains(param) => {
+                (AbstractionRule::Constant, expr::a(self.k(), body.clone()))
+            }
+            Expr::Apply { lhs, rhs } => match rhs.as_ref() {
+                Expr::Variable(id)
+                    if id == param && !FreeVars::from(lhs.as_ref()).contains(param) =>
+                {
+                    (AbstractionRule::Eta, *lhs.clone())
+                }
+                _ => (
+                    AbstractionRule::Composition,
+                    expr::a(
+                        expr::a(self.s(), expr::l(param.clone(), *lhs.clone())),
+                        expr::l(param.clone(), *rhs.clone()),
+                    ),
+                ),
+            },
+            _ => unreachable!("the body of the abstraction has no lambda"),
+        }
+    }
+
+    fn find_expansion(&self, expr: &Expr, position: &mut Vec<Direction>) -> bool {
+        match expr {
+            Expr::Variable(id) => !self.is_combinator(id) && self.context.get(id).is_some(),
+            Expr::Symbol(_) => false,
+            Expr::Apply { lhs, rhs } => {
+                position.push(Direction::Lhs);
+                if self.find_expansion(lhs, position) {
+                    return true;
+                }
+                position.pop();
+                position.push(Direction::Rhs);
+                if self.find_expansion(rhs, position) {
+                    return true;
+                }
+                position.pop();
+                false
+            }
+            Expr::Lambda { .. } => unreachable!("expansion starts after all abstractions"),
+            Expr::TypeLambda { .. } | Expr::TypeApply { .. } => unreachable!("types are erased"),
+            Expr::Let { .. } => unreachable!("let expressions are desugared"),
+        }
+    }
+
+    fn is_combinator(&self, id: &Identifier) -> bool {
+        match &self.strategy {
+            RecursiveStrategy::SKI | RecursiveStrategy::Turner | RecursiveStrategy::Kiselyov => {
+                ["s", "k", "i"].contains(&id.as_str())
+            }
+            RecursiveStrategy::SKIWith { s, k, i } => [s, k, i].contains(&id),
+            RecursiveStrategy::SK => ["s", "k"].contains(&id.as_str()),
+            RecursiveStrategy::SKWith { s, k } => [s, k].contains(&id),
+            RecursiveStrategy::Iota => id.as_str() == "ι",
+            RecursiveStrategy::IotaWith { iota } => iota == id,
+        }
+    }
+
+    fn s(&self) -> Expr {
+        match &self.strategy {
+            RecursiveStrategy::SKI
+            | RecursiveStrategy::SK
+            | RecursiveStrategy::Turner
+            | RecursiveStrategy::Kiselyov => expr::v("s"),
+            RecursiveStrategy::SKIWith { s, .. } | RecursiveStrategy::SKWith { s, .. } => {
+                expr::v(s.clone())
+            }
+            RecursiveStrategy::Iota | RecursiveStrategy::IotaWith { .. } => {
+                let iota = self.iota();
+                expr::a(
+                    iota.clone(),
+                    expr::a(
+                        iota.clone(),
+                        expr::a(iota.clone(), expr::a(iota.clone(), iota)),
+                    ),
+                )
+            }
+        }
+    }
+
+    fn k(&self) -> Expr {
+        match &self.strategy {
+            RecursiveStrategy::SKI
+            | RecursiveStrategy::SK
+            | RecursiveStrategy::Turner
+            | RecursiveStrategy::Kiselyov => expr::v("k"),
+            RecursiveStrategy::SKIWith { k, .. } | RecursiveStrategy::SKWith { k, .. } => {
+                expr::v(k.clone())
+            }
+            RecursiveStrategy::Iota | RecursiveStrategy::IotaWith { .. } => {
+                let iota = self.iota();
+                expr::a(
+                    iota.clone(),
+                    expr::a(iota.clone(), expr::a(iota.clone(), iota)),
+                )
+            }
+        }
+    }
+
+    fn i(&self) -> Expr {
+        match &self.strategy {
+            RecursiveStrategy::SKI | RecursiveStrategy::Turner | RecursiveStrategy::Kiselyov => {
+                expr::v("i")
+            }
+            RecursiveStrategy::SKIWith { i, .. } => expr::v(i.clone()),
+            RecursiveStrategy::SK | RecursiveStrategy::SKWith { .. } => {
+                expr::a(expr::a(self.s(), self.k()), self.k())
+            }
+            RecursiveStrategy::Iota | RecursiveStrategy::IotaWith { .. } => {
+                expr::a(self.iota(), self.iota())
+            }
+        }
+    }
+
+    fn iota(&self) -> Expr {
+        match &self.strategy {
+            RecursiveStrategy::IotaWith { iota } => expr::v(iota.clone()),
+            _ => expr::v("ι"),
+        }
+    }
+}
+
+impl Iterator for UnlambdaTrace {
+    type Item = UnlambdaStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, rule, rewritten) = self.rewrite()?;
+
+        *at_mut(&mut self.expr, &position) = rewritten;
+        self.step += 1;
+
+        Some(UnlambdaStep {
+            step: self.step,
+            rule,
+            expr: self.expr.clone(),
+            path: to_path(&self.expr, &position),
+        })
+    }
+}
+
+// ========================================================================== //
+
+// 本体にラムダ抽象を含まないラムダ抽象のうち、最も左にあるものを探す
+fn find_abstraction(expr: &Expr, position: &mut Vec<Direction>) -> bool {
+    match expr {
+        Expr::Variable(_) | Expr::Symbol(_) => false,
+        Expr::Apply { lhs, rhs } => {
+            position.push(Direction::Lhs);
+            if find_abstraction(lhs, position) {
+                return true;
+            }
+            position.pop();
+            position.push(Direction::Rhs);
+            if find_abstraction(rhs, position) {
+                return true;
+            }
+            position.pop();
+            false
+        }
+        Expr::Lambda { body, .. } => {
+            position.push(Direction::Body);
+            if find_abstraction(body, position) {
+                return true;
+            }
+            position.pop();
+            true
+        }
+        Expr::TypeLambda { .. } | Expr::TypeApply { .. } => unreachable!("types are erased"),
+        Expr::Let { .. } => unreachable!("let expressions are desugared"),
+    }
+}
+
+fn at<'a>(expr: &'a Expr, position: &[Direction]) -> &'a Expr {
+    match (expr, position.split_first()) {
+        (_, None) => expr,
+        (Expr::Apply { lhs, .. }, Some((Direction::Lhs, rest))) => at(lhs, rest),
+        (Expr::Apply { rhs, .. }, Some((Direction::Rhs, rest))) => at(rhs, rest),
+        (Expr::Lambda { body, .. }, Some((Direction::Body, rest))) => at(body, rest),
+        _ => unreachable!("invalid position"),
+    }
+}
+
+fn at_mut<'a>(expr: &'a mut Expr, position: &[Direction]) -> &'a mut Expr {
+    match (expr, position.split_first()) {
+        (expr, None) => expr,
+        (Expr::Apply { lhs, .. }, Some((Direction::Lhs, rest))) => at_mut(lhs, rest),
+        (Expr::Apply { rhs, .. }, Some((Direction::Rhs, rest))) => at_mut(rhs, rest),
+        (Expr::Lambda { body, .. }, Some((Direction::Body, rest))) => at_mut(body, rest),
+        _ => unreachable!("invalid position"),
+    }
+}
+
+// 部分式の位置を、関数と引数の並びでたどる Path に変換する
+// Path はラムダ抽象の本体の中をたどれないので、そこで打ち切る
+fn to_path(expr: &Expr, position: &[Direction]) -> Path {
+    let mut callee = expr;
+    let mut arity = 0;
+    while let Expr::Apply { lhs, .. } = callee {
+        arity += 1;
+        callee = lhs;
+    }
+
+    let lhs_count = position
+        .iter()
+        .take_while(|direction| **direction == Direction::Lhs)
+        .count();
+    let arity = arity - lhs_count;
+
+    match position.get(lhs_count) {
+        Some(Direction::Rhs) => {
+            let Expr::Apply { rhs, .. } = at(expr, &position[..lhs_count]) else {
+                unreachable!("invalid position");
+            };
+            Path::Arg(arity, Box::new(to_path(rhs, &position[lhs_count + 1..])))
+        }
+        _ => Path::Callee(arity),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::unlambda_recursive_;
+    use crate::parser::parse_expr;
+
+    fn trace(strategy: RecursiveStrategy, s: &str) -> Vec<UnlambdaStep> {
+        let expr = parse_expr(s).unwrap();
+        UnlambdaTrace::new(Context::default(), strategy, expr)
+            .unwrap()
+            .collect()
+    }
+
+    #[test]
+    fn test_rules() {
+        let steps = trace(RecursiveStrategy::SKI, "x => y => y(x)");
+        let rules: Vec<_> = steps.iter().map(|step| step.rule.clone()).collect();
+        assert_eq!(
+            rules,
+            vec![
+                AbstractionRule::Composition,
+                AbstractionRule::Identity,
+                AbstractionRule::Constant,
+                AbstractionRule::Composition,
+                AbstractionRule::Constant,
+                AbstractionRule::Eta,
+            ]
+        );
+        assert_eq!(
+            steps.last().unwrap().expr,
+            parse_expr("s(k(s(i)), k)").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unsupported_strategy() {
+        for strategy in [RecursiveStrategy::Turner, RecursiveStrategy::Kiselyov] {
+            let expr = parse_expr("x => y => y(x)").unwrap();
+            assert_eq!(
+                UnlambdaTrace::new(Context::default(), strategy.clone(), expr).err(),
+                Some(UnlambdaTraceError::UnsupportedStrategy(strategy))
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand() {
+        let steps = trace(RecursiveStrategy::SKI, "x => TRUE");
+        assert_eq!(steps[0].rule, AbstractionRule::Constant);
+        assert_eq!(steps[0].expr, parse_expr("k(TRUE)").unwrap());
+        assert_eq!(steps[1].rule, AbstractionRule::Expand("TRUE".into()));
+        assert_eq!(steps.last().unwrap().expr, parse_expr("k(k)").unwrap());
+    }
+
+    #[test]
+    fn test_path() {
+        let steps = trace(RecursiveStrategy::SKI, ":a(x => x(:b), y => y)");
+
+        // :a(s(x => x, x => :b), y => y)
+        assert_eq!(steps[0].path, Path::Arg(1, Box::new(Path::Callee(2))));
+        // :a(s(i, x => :b), y => y)
+        assert_eq!(
+            steps[1].path,
+            Path::Arg(1, Box::new(Path::Arg(1, Box::new(Path::Callee(0)))))
+        );
+        // :a(s(i, k(:b)), y => y)
+        assert_eq!(
+            steps[2].path,
+            Path::Arg(1, Box::new(Path::Arg(2, Box::new(Path::Callee(1)))))
+        );
+        // :a(s(i, k(:b)), i)
+        assert_eq!(steps[3].path, Path::Arg(2, Box::new(Path::Callee(0))));
+        assert_eq!(steps.len(), 4);
+
+        // ラムダ抽象の本体の中は、そのラムダ抽象で打ち切る
+        let steps = trace(RecursiveStrategy::SKI, "x => y => x");
+        assert_eq!(steps[0].expr, parse_expr("x => k(x)").unwrap());
+        assert_eq!(steps[0].path, Path::Callee(0));
+    }
+
+    #[test]
+    fn test_same_as_unlambda_recursive() {
+        let context = Context::default();
+        let strategies = [
+            RecursiveStrategy::SKI,
+            RecursiveStrategy::SK,
+            RecursiveStrategy::Iota,
+        ];
+        let sources = [
+            "x => x",
+            "x => y => z => x(z, y(z))",
+            "f => x => f(f(x))",
+            "NOT",
+            "IF(IS_ZERO(:n), CONS(:a, NIL), 2)",
+            "Y",
+        ];
+
+        for strategy in strategies {
+            for source in sources {
+                let expr = parse_expr(source).unwrap();
+                let expected = unlambda_recursive_(&strategy, &context, expr.clone());
+
+                let mut trace =
+                    UnlambdaTrace::new(context.clone(), strategy.clone(), expr).unwrap();
+                trace.by_ref().for_each(drop);
+                assert_eq!(trace.expr(), expected, "{}", source);
+            }
+        }
+    }
+}
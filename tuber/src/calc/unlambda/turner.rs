@@ -0,0 +1,263 @@
+use crate::context::Context;
+use crate::expr::FreeVars;
+use crate::expr::{self, Expr, Identifier};
+
+/// 結果に現れるコンビネータ (展開しない)
+const COMBINATORS: [&str; 8] = ["s", "k", "i", "B", "C", "S_PRIME", "B_STAR", "C_PRIME"];
+
+/// Turner の方法でラムダ抽象を除去する
+///
+/// B, C, S_PRIME, B_STAR, C_PRIME のうち Context で定義されているものだけを使い、
+/// 定義されていないものは s, k, i で置き換える
+pub fn unlambda(context: &Context, expr: Expr) -> Expr {
+    let turner = Turner::new(context);
+    unlambda_(context, &turner, expr)
+}
+
+fn unlambda_(context: &Context, turner: &Turner, expr: Expr) -> Expr {
+    match expr {
+        Expr::Variable(ref id) => {
+            if COMBINATORS.contains(&id.as_str()) {
+                expr
+            } else {
+                match context.get(id) {
+                    Some(func) => unlambda_(context, turner, func.to_owned().into()),
+                    None => expr,
+                }
+            }
+        }
+        Expr::Symbol(_) => expr,
+        Expr::Apply { lhs, rhs } => expr::a(
+            unlambda_(context, turner, *lhs),
+            unlambda_(context, turner, *rhs),
+        ),
+        Expr::Lambda { param, body, .. } => {
+            unlambda_(context, turner, turner.abstract_(*body, &param))
+        }
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+            unlambda_(context, turner, *body)
+        }
+        // let 式はラムダ抽象の適用に書き換える
+        mut expr @ Expr::Let { .. } => {
+            expr.desugar_let();
+            unlambda_(context, turner, expr)
+        }
+    }
+}
+
+// Context で定義されているコンビネータ
+struct Turner {
+    b: bool,
+    c: bool,
+    s_prime: bool,
+    b_star: bool,
+    c_prime: bool,
+}
+
+impl Turner {
+    fn new(context: &Context) -> Self {
+        let defined = |name: &str| context.get(&Identifier::from(name)).is_some();
+        Self {
+            b: defined("B"),
+            c: defined("C"),
+            s_prime: defined("S_PRIME"),
+            b_star: defined("B_STAR"),
+            c_prime: defined("C_PRIME"),
+        }
+    }
+
+    fn abstract_(&self, expr: Expr, param: &Identifier) -> Expr {
+        match expr {
+            Expr::Lambda {
+                param: inner, body, ..
+            } => self.abstract_(self.abstract_(*body, &inner), param),
+            Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => {
+                self.abstract_(*body, param)
+            }
+            mut expr @ Expr::Let { .. } => {
+                expr.desugar_let();
+                self.abstract_(expr, param)
+            }
+            Expr::Variable(id) if &id == param => expr::v("i"),
+            _ if !FreeVars::from(&expr).contains(param) => expr::a("k", expr),
+            Expr::Apply { lhs, rhs } => {
+                self.combine(self.abstract_(*lhs, param), self.abstract_(*rhs, param))
+            }
+            _ => unreachable!("a variable other than param is not free"),
+        }
+    }
+
+    // S p q を最適化する
+    fn combine(&self, p: Expr, q: Expr) -> Expr {
+        match (k_arg(&p), k_arg(&q)) {
+            // S (K p) (K q) => K (p q)
+            (Some(p), Some(q)) => return expr::a("k", expr::a(p.clone(), q.clone())),
+            // S (K p) I => p
+            (Some(p), None) if q == expr::v("i") => return p.clone(),
+            _ => {}
+        }
+
+        if let Some(p) = k_arg(&p) {
+            // S (K p) (B q r) => B* p q r
+            if let (true, Some((q, r))) = (self.b_star, b_args(&q)) {
+                return apply3("B_STAR", p, q, r);
+            }
+            // S (K p) q => B p q
+            if self.b {
+                return expr::a(expr::a("B", p.clone()), q);
+            }
+        }
+
+        if let Some(r) = k_arg(&q) {
+            // S (B p q) (K r) => C' p q r
+            if let (true, Some((p, q))) = (self.c_prime, b_args(&p)) {
+                return apply3("C_PRIME", p, q, r);
+            }
+            // S p (K q) => C p q
+            if self.c {
+                return expr::a(expr::a("C", p), r.clone());
+            }
+        }
+
+        // S (B p q) r => S' p q r
+        if let (true, Some((p, q_))) = (self.s_prime, b_args(&p)) {
+            return apply3("S_PRIME", p, q_, &q);
+        }
+
+        expr::a(expr::a("s", p), q)
+    }
+}
+
+// K p であれば p を返す
+fn k_arg(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Apply { lhs, rhs } if lhs.as_ref() == &expr::v("k") => Some(rhs),
+        _ => None,
+    }
+}
+
+// B p q であれば (p, q) を返す
+fn b_args(expr: &Expr) -> Option<(&Expr, &Expr)> {
+    match expr {
+        Expr::Apply { lhs, rhs: q } => match lhs.as_ref() {
+            Expr::Apply { lhs, rhs: p } if lhs.as_ref() == &expr::v("B") => Some((p, q)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn apply3(combinator: &str, x: &Expr, y: &Expr, z: &Expr) -> Expr {
+    expr::a(
+        expr::a(expr::a(combinator, x.clone()), y.clone()),
+        z.clone(),
+    )
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{unlambda_recursive, Aliases, Reducer, ReductionStrategy};
+    use crate::parser::parse_expr;
+
+    fn context() -> Context {
+        let mut context = Context::default();
+        context.import("turner").unwrap();
+        context
+    }
+
+    // 引数を与えて正規形まで簡約する
+    fn reduce(context: &Context, expr: Expr, args: &[&str]) -> Expr {
+        let expr = args
+            .iter()
+            .fold(expr, |e, arg| expr::a(e, parse_expr(arg).unwrap()));
+        let mut reducer = Reducer::new(
+            context.clone(),
+            Aliases::new(),
+            expr,
+            ReductionStrategy::NormalOrder,
+        );
+        reducer.set_max_steps(Some(1000));
+        reducer.by_ref().for_each(drop);
+        reducer.expr()
+    }
+
+    #[test]
+    fn test_unlambda() {
+        let context = context();
+        let cases = [
+            ("x => x", "i"),
+            ("x => :a", "k(:a)"),
+            ("x => :f(x)", ":f"),
+            ("x => x(:a)", "C(i, :a)"),
+            ("x => :f(:g(x))", "B(:f, :g)"),
+            ("x => y => y(x)", "C(i)"),
+            ("x => :f(:g(:h(x)))", "B_STAR(:f, :g, :h)"),
+            ("x => :f(:g(x), :a)", "C_PRIME(:f, :g, :a)"),
+            ("x => :f(:g(x), x)", "S_PRIME(:f, :g, i)"),
+            ("x => x(x)", "s(i, i)"),
+        ];
+
+        for (source, expected) in cases {
+            assert_eq!(
+                unlambda(&context, parse_expr(source).unwrap()),
+                parse_expr(expected).unwrap(),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_defined_only() {
+        // Turner のコンビネータが定義されていなければ s, k, i だけを使う
+        let context = Context::default();
+        for source in ["x => :f(:g(x))", "x => y => y(x)", "NOT", "ADD"] {
+            let expr = parse_expr(source).unwrap();
+            assert_eq!(
+                unlambda(&context, expr.clone()),
+                unlambda_recursive(&context, expr),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let context = context();
+        let cases: [(&str, &[&str]); 6] = [
+            ("NOT", &["TRUE", ":a", ":b"]),
+            ("AND", &["TRUE", "FALSE", ":a", ":b"]),
+            ("CONS", &[":a", ":b", "k"]),
+            ("ADD", &["2", "3", ":f", ":x"]),
+            ("x => y => z => x(z, y(z))", &[":a", ":b", ":c"]),
+            ("f => g => x => f(g(x), x, g(f))", &[":a", ":b", ":c"]),
+        ];
+
+        for (source, args) in cases {
+            let expr = parse_expr(source).unwrap();
+            let result = unlambda(&context, expr.clone());
+            assert_eq!(
+                reduce(&context, result, args),
+                reduce(&context, expr, args),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_smaller() {
+        let context = context();
+        for source in ["NOT", "ADD", "MUL", "f => x => y => f(y, x)"] {
+            let expr = parse_expr(source).unwrap();
+            let turner = unlambda(&context, expr.clone());
+            let ski = unlambda_recursive(&context, expr);
+            assert!(turner.size() < ski.size(), "{}", source);
+        }
+    }
+}
@@ -1,49 +1,222 @@
+use super::error::ContextError;
+use super::module::Module;
+use crate::data::DataType;
 use crate::expr::Identifier;
 use crate::func::Func;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// 定義済みの名前空間を表現する
 ///
 /// 識別子と関数の組を保持する
-#[derive(Clone, Debug, PartialEq)]
-pub struct Context(HashMap<Identifier, Func>);
+/// import したモジュールの関数は修飾なしの名前でも登録され、定義で上書きすることはできない
+/// `bool::TRUE` のように修飾した識別子は、読み込んだモジュールから探す
+///
+/// serde では、関数定義を Context::to_vec と同じ順序で並べた配列として表現する
+/// (どのモジュールから import したかは保持しないので、必要なら Context::restore_imports で戻す)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(into = "Vec<Func>", from = "Vec<Func>")]
+pub struct Context {
+    funcs: HashMap<Identifier, Func>,
+    modules: HashMap<String, Module>,
+    imported: HashMap<Identifier, String>,
+}
 
 impl Context {
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self::from(HashMap::new())
     }
 
     pub fn get(&self, id: &Identifier) -> Option<&Func> {
-        self.0.get(id)
+        match id.module() {
+            Some(module) => self.modules.get(module)?.get(id.local_name()),
+            None => self.funcs.get(id),
+        }
+    }
+
+    /// 関数を定義する
+    ///
+    /// import した関数を上書きする場合は、先に Context::del で削除しておく必要がある
+    pub fn def(&mut self, func: Func) -> Result<(), ContextError> {
+        let id: Identifier = func.name().into();
+
+        if id.module().is_some() {
+            return Err(ContextError::Qualified(id));
+        }
+        if id.is_numeral() {
+            return Err(ContextError::Numeral(id));
+        }
+        if let Some(module) = self.imported.get(&id) {
+            return Err(ContextError::Imported {
+                name: id,
+                module: module.clone(),
+            });
+        }
+
+        self.funcs.insert(id, func);
+        Ok(())
+    }
+
+    /// data 宣言から生成した関数を定義する
+    ///
+    /// 同じ名前のデータ型から生成した関数は、先にまとめて削除する
+    pub fn def_data(&mut self, data: &DataType) -> Result<Vec<Func>, ContextError> {
+        let funcs = data.funcs()?;
+
+        // 一部の関数だけが定義されることのないよう、先にすべて検査する
+        for func in &funcs {
+            let id: Identifier = func.name().into();
+            if let Some(module) = self.imported.get(&id) {
+                return Err(ContextError::Imported {
+                    name: id,
+                    module: module.clone(),
+                });
+            }
+        }
+
+        self.del_data(data.name());
+        for func in &funcs {
+            self.insert(func.clone());
+        }
+        Ok(funcs)
+    }
+
+    /// 名前が name のデータ型から生成した関数を削除し、削除した関数を返す
+    pub fn del_data(&mut self, name: &Identifier) -> Vec<Func> {
+        let funcs = self.data(name);
+        for func in &funcs {
+            self.funcs.remove(&func.name().into());
+        }
+        funcs
+    }
+
+    /// 名前が name のデータ型から生成した関数
+    pub fn data(&self, name: &Identifier) -> Vec<Func> {
+        let funcs = self
+            .funcs
+            .values()
+            .filter(|func| func.origin() == Some(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        Context::from(funcs).to_vec()
     }
 
-    pub fn def(&mut self, func: Func) {
-        self.0.insert(func.name().into(), func);
+    // 検査せずに関数を登録する
+    pub(super) fn insert(&mut self, func: Func) {
+        self.funcs.insert(func.name().into(), func);
     }
 
     pub fn del(&mut self, id: &Identifier) -> &mut Self {
-        self.0.remove(id);
+        self.funcs.remove(id);
+        self.imported.remove(id);
         self
     }
 
+    /// モジュールを import し、その関数を修飾なしの名前で登録する
+    ///
+    /// requires に挙げられたモジュールも合わせて import する
+    /// import した関数以外の定義と名前が衝突する場合はエラーを返す
+    pub fn import(&mut self, name: &str) -> Result<(), ContextError> {
+        let module = match self.modules.get(name) {
+            Some(module) => module.clone(),
+            None => Module::builtin(name)
+                .ok_or_else(|| ContextError::UnknownModule(name.to_string()))?,
+        };
+
+        for required in module.requires() {
+            self.import(required)?;
+        }
+
+        // 既存の定義を上書きしないよう、先に衝突がないことを確かめる
+        for func in module.funcs() {
+            let id: Identifier = func.name().into();
+            if self.funcs.contains_key(&id)
+                && self.imported.get(&id).map(String::as_str) != Some(name)
+            {
+                return Err(ContextError::Conflict {
+                    name: id,
+                    module: name.to_string(),
+                });
+            }
+        }
+
+        for func in module.funcs() {
+            let id: Identifier = func.name().into();
+            self.imported.insert(id.clone(), name.to_string());
+            self.funcs.insert(id, func.clone());
+        }
+        self.modules.insert(name.to_string(), module);
+
+        Ok(())
+    }
+
+    /// 読み込み済みのモジュールを名前から探す
+    pub fn module(&self, name: &str) -> Option<&Module> {
+        self.modules.get(name)
+    }
+
+    /// 読み込み済みのモジュールの名前
+    pub fn module_names(&self) -> impl Iterator<Item = &str> {
+        self.modules.keys().map(String::as_str)
+    }
+
+    /// 修飾なしの名前 id で登録された関数を import したモジュールの名前
+    pub fn imported_from(&self, id: &Identifier) -> Option<&str> {
+        self.imported.get(id).map(String::as_str)
+    }
+
+    /// 組み込みのモジュールを読み込み直し、imported に挙げた関数を import したものとして扱う
+    ///
+    /// 関数定義だけを受け渡した Context に、Context::module_names と
+    /// Context::imported_from で取り出した情報を戻すのに使う
+    /// 不明なモジュール名があればエラーを返し、Context は変更しない
+    pub fn restore_imports(
+        &mut self,
+        modules: impl IntoIterator<Item = String>,
+        imported: impl IntoIterator<Item = (Identifier, String)>,
+    ) -> Result<(), ContextError> {
+        let modules = modules
+            .into_iter()
+            .map(|name| match Module::builtin(&name) {
+                Some(module) => Ok((name, module)),
+                None => Err(ContextError::UnknownModule(name)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let imported = imported.into_iter().collect::<Vec<_>>();
+        for (_, name) in &imported {
+            if !self.modules.contains_key(name) && !modules.iter().any(|(n, _)| n == name) {
+                return Err(ContextError::UnknownModule(name.clone()));
+            }
+        }
+
+        self.modules.extend(modules);
+        for (id, name) in imported {
+            if self.funcs.contains_key(&id) {
+                self.imported.insert(id, name);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&Identifier, &Func)> {
-        self.0.iter()
+        self.funcs.iter()
     }
 
     #[cfg(test)]
     pub fn arity(&self, id: &str) -> Option<usize> {
-        self.0.get(&id.into()).map(|f| f.arity())
+        self.funcs.get(&id.into()).map(|f| f.arity())
     }
 
     #[cfg(test)]
     pub fn count(&self) -> usize {
-        self.0.len()
+        self.funcs.len()
     }
 
     pub fn to_vec(self) -> Vec<Func> {
         let mut vec = self
-            .0
+            .funcs
             .into_values()
             .map(feature)
             .collect::<Vec<(Feature, Func)>>();
@@ -64,11 +237,17 @@ impl Context {
 
 impl From<Vec<Func>> for Context {
     fn from(v: Vec<Func>) -> Self {
-        let mut context = HashMap::new();
+        let mut funcs = HashMap::new();
         for func in v {
-            context.insert(func.name().into(), func);
+            funcs.insert(func.name().into(), func);
         }
-        Self(context)
+        Self::from(funcs)
+    }
+}
+
+impl From<Context> for Vec<Func> {
+    fn from(context: Context) -> Vec<Func> {
+        context.to_vec()
     }
 }
 
@@ -77,19 +256,23 @@ impl IntoIterator for Context {
     type IntoIter = std::collections::hash_map::IntoIter<Identifier, Func>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.funcs.into_iter()
     }
 }
 
 impl From<Context> for HashMap<Identifier, Func> {
     fn from(tuber_context: Context) -> HashMap<Identifier, Func> {
-        tuber_context.0
+        tuber_context.funcs
     }
 }
 
 impl From<HashMap<Identifier, Func>> for Context {
-    fn from(context: HashMap<Identifier, Func>) -> Context {
-        Context(context)
+    fn from(funcs: HashMap<Identifier, Func>) -> Context {
+        Context {
+            funcs,
+            modules: HashMap::new(),
+            imported: HashMap::new(),
+        }
     }
 }
 
@@ -127,7 +310,8 @@ fn feature(func: Func) -> (Feature, Func) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::expr;
+    use crate::context::BUILTIN_MODULES;
+    use crate::expr::{self, FreeVars};
     use crate::func;
 
     fn setup() -> Context {
@@ -166,4 +350,136 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_import() {
+        let mut context = Context::new();
+        assert_eq!(context.import("list"), Ok(()));
+
+        // requires に挙げられた bool も import される
+        assert!(context.get(&"CONS".into()).is_some());
+        assert!(context.get(&"TRUE".into()).is_some());
+        assert!(context.get(&"list::CAR".into()).is_some());
+        assert!(context.get(&"bool::FALSE".into()).is_some());
+        assert!(context.get(&"list::TRUE".into()).is_none());
+        assert!(context.get(&"s".into()).is_none());
+        assert!(context.get(&"comb::s".into()).is_none());
+
+        // 同じモジュールを何度 import してもよい
+        assert_eq!(context.import("bool"), Ok(()));
+
+        assert_eq!(
+            context.import("foo"),
+            Err(ContextError::UnknownModule("foo".to_string()))
+        );
+        let mut context = Context::from(vec![func::new("TRUE", vec!["x"], "x")]);
+        assert_eq!(
+            context.restore_imports(
+                vec!["bool".to_string()],
+                vec![("TRUE".into(), "foo".to_string())]
+            ),
+            Err(ContextError::UnknownModule("foo".to_string()))
+        );
+        assert!(context.module("bool").is_none());
+    }
+
+    #[test]
+    fn test_import_conflict() {
+        let mut context = setup();
+
+        assert_eq!(
+            context.import("comb"),
+            Err(ContextError::Conflict {
+                name: "i".into(),
+                module: "comb".to_string()
+            })
+        );
+        assert!(context.get(&"Y".into()).is_none());
+        assert_eq!(context.count(), 3);
+    }
+
+    #[test]
+    fn test_def_imported() {
+        let mut context = Context::default();
+        let t = func::new("TRUE", Vec::<&str>::new(), ":t");
+
+        assert_eq!(
+            context.def(t.clone()),
+            Err(ContextError::Imported {
+                name: "TRUE".into(),
+                module: "bool".to_string()
+            })
+        );
+
+        // 削除してからであれば上書きできる
+        context.del(&"TRUE".into());
+        assert_eq!(context.def(t.clone()), Ok(()));
+        assert_eq!(context.get(&"TRUE".into()), Some(&t));
+        assert_ne!(context.get(&"bool::TRUE".into()), Some(&t));
+
+        // 利用者の定義は上書きできる
+        assert_eq!(context.def(func::new("TRUE", vec!["x"], "x")), Ok(()));
+        assert_eq!(context.arity("TRUE"), Some(1));
+
+        assert_eq!(
+            context.def(func::new("bool::TRUE", Vec::<&str>::new(), ":t")),
+            Err(ContextError::Qualified("bool::TRUE".into()))
+        );
+        assert_eq!(
+            context.def(func::new("21", Vec::<&str>::new(), ":t")),
+            Err(ContextError::Numeral("21".into()))
+        );
+    }
+
+    #[test]
+    fn test_restore_imports() {
+        let mut context = Context::default();
+        context.del(&"TRUE".into());
+        let modules: Vec<String> = context.module_names().map(String::from).collect();
+        let imported: Vec<(Identifier, String)> = context
+            .iter()
+            .filter_map(|(id, _)| Some((id.clone(), context.imported_from(id)?.to_string())))
+            .collect();
+        assert_eq!(context.imported_from(&"FALSE".into()), Some("bool"));
+        assert_eq!(context.imported_from(&"TRUE".into()), None);
+
+        // 関数定義だけを受け渡しても、import の情報を戻せば元の Context と同じになる
+        let mut restored = Context::from(context.clone().to_vec());
+        assert!(restored.get(&"bool::TRUE".into()).is_none());
+        assert_eq!(restored.restore_imports(modules, imported), Ok(()));
+        assert_eq!(restored, context);
+        assert!(restored.get(&"bool::TRUE".into()).is_some());
+        assert!(matches!(
+            restored.def(func::new("FALSE", Vec::<&str>::new(), ":f")),
+            Err(ContextError::Imported { .. })
+        ));
+
+        assert_eq!(
+            restored.restore_imports(vec!["foo".to_string()], vec![]),
+            Err(ContextError::UnknownModule("foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_builtin_modules() {
+        // 組み込みのモジュールの関数の本体は、requires を含めて import した名前だけで閉じている
+        for name in BUILTIN_MODULES {
+            let mut context = Context::new();
+            context.import(name).unwrap();
+
+            let module = context.module(name).unwrap();
+            for f in module.funcs() {
+                for var in FreeVars::from(f.body()).0 {
+                    assert!(
+                        f.params().iter().any(|p| p.as_str() == var)
+                            || context.get(&var.into()).is_some(),
+                        "{} in {}::{}",
+                        var,
+                        name,
+                        f.name()
+                    );
+                }
+            }
+        }
+    }
 }
@@ -1,927 +1,311 @@
+use super::module::Module;
 use crate::context::Context;
-use crate::expr;
-use crate::func;
+use crate::expr::{self, church_numeral};
+use crate::func::{self, Func};
+
+/// 組み込みのモジュール名 (Context::default で import する順)
+pub const BUILTIN_MODULES: [&str; 4] = ["comb", "bool", "list", "nat"];
 
 impl Default for Context {
     fn default() -> Self {
-        Context::from(vec![
-            func::new("i", vec!["x"], "x"),
-            func::new("k", vec!["x", "y"], "x"),
-            func::new(
-                "s",
-                vec!["x", "y", "z"],
-                expr::a(expr::a("x", "z"), expr::a("y", "z")),
-            ),
-            func::new(
-                "ι",
-                vec!["f"],
+        let mut context = Context::new();
+        for name in BUILTIN_MODULES {
+            context
+                .import(name)
+                .expect("builtin modules must not conflict");
+        }
+        context
+    }
+}
+
+/// 組み込みのモジュールを名前から作る
+pub fn builtin(name: &str) -> Option<Module> {
+    match name {
+        "comb" => Some(Module::new("comb", vec![], combinators())),
+        "bool" => Some(Module::new("bool", vec![], booleans())),
+        "list" => Some(Module::new("list", vec!["bool"], lists())),
+        "nat" => Some(Module::new("nat", vec!["comb", "bool", "list"], naturals())),
+        "turner" => Some(Module::new("turner", vec![], turner())),
+        "bulk" => Some(Module::new("bulk", vec![], bulk())),
+        _ => None,
+    }
+}
+
+// S, K, I などのコンビネータと不動点コンビネータ
+fn combinators() -> Vec<Func> {
+    vec![
+        func::new("i", vec!["x"], "x"),
+        func::new("k", vec!["x", "y"], "x"),
+        func::new(
+            "s",
+            vec!["x", "y", "z"],
+            expr::a(expr::a("x", "z"), expr::a("y", "z")),
+        ),
+        func::new(
+            "ι",
+            vec!["f"],
+            expr::a(
                 expr::a(
-                    expr::a(
-                        "f",
+                    "f",
+                    expr::l(
+                        "x",
                         expr::l(
-                            "x",
-                            expr::l(
-                                "y",
-                                expr::l("z", expr::a(expr::a("x", "z"), expr::a("y", "z"))),
-                            ),
+                            "y",
+                            expr::l("z", expr::a(expr::a("x", "z"), expr::a("y", "z"))),
                         ),
                     ),
-                    expr::l("x", expr::l("y", "x")),
                 ),
-            ),
-            func::new("TRUE", Vec::<&str>::new(), expr::l("x", expr::l("y", "x"))),
-            func::new("FALSE", Vec::<&str>::new(), expr::l("x", expr::l("y", "y"))),
-            func::new(
-                "IF",
-                vec!["PRED", "THEN", "ELSE"],
-                expr::a(expr::a("PRED", "THEN"), "ELSE"),
-            ),
-            func::new("NOT", vec!["x"], expr::a(expr::a("x", "FALSE"), "TRUE")),
-            func::new("AND", vec!["x", "y"], expr::a(expr::a("x", "y"), "FALSE")),
-            func::new("OR", vec!["x", "y"], expr::a(expr::a("x", "TRUE"), "y")),
-            func::new(
-                "XOR",
-                vec!["x", "y"],
-                expr::a(expr::a("x", expr::a("NOT", "y")), "y"),
-            ),
-            func::new(
-                "CONS",
-                vec!["x", "y"],
-                expr::l("f", expr::a(expr::a("f", "x"), "y")),
-            ),
-            func::new("CAR", vec!["x"], expr::a("x", "TRUE")),
-            func::new("CDR", vec!["x"], expr::a("x", "FALSE")),
-            func::new("NIL", Vec::<&str>::new(), "FALSE"),
-            func::new(
-                "IS_NIL",
-                vec!["x"],
-                expr::a(expr::a("x", expr::l("_", expr::l("_", expr::l("_", "FALSE")))), "TRUE"),
-            ),
-            func::new(
-                "Y",
-                vec!["f"],
-                expr::a(
-                    expr::l("x", expr::a("f", expr::a("x", "x"))),
-                    expr::l("x", expr::a("f", expr::a("x", "x"))),
-                ),
-            ),
-            func::new(
-                "Z",
-                vec!["f"],
-                expr::a(
-                    expr::l(
-                        "x",
-                        expr::a("f", expr::l("y", expr::a(expr::a("x", "x"), "y"))),
-                    ),
-                    expr::l(
-                        "x",
-                        expr::a("f", expr::l("y", expr::a(expr::a("x", "x"), "y"))),
-                    ),
+                expr::l("x", expr::l("y", "x")),
+            ),
+        ),
+        func::new(
+            "Y",
+            vec!["f"],
+            expr::a(
+                expr::l("x", expr::a("f", expr::a("x", "x"))),
+                expr::l("x", expr::a("f", expr::a("x", "x"))),
+            ),
+        ),
+        func::new(
+            "Z",
+            vec!["f"],
+            expr::a(
+                expr::l(
+                    "x",
+                    expr::a("f", expr::l("y", expr::a(expr::a("x", "x"), "y"))),
                 ),
-            ),
-            func::new(
-                "IS_ZERO",
-                vec!["n"],
-                expr::a(expr::a("n", expr::l("_", "FALSE")), "TRUE"),
-            ),
-            func::new(
-                "SUCC",
-                vec!["n"],
                 expr::l(
-                    "f",
-                    expr::l("x", expr::a("f", expr::a(expr::a("n", "f"), "x"))),
+                    "x",
+                    expr::a("f", expr::l("y", expr::a(expr::a("x", "x"), "y"))),
                 ),
             ),
-            func::new(
-                "ADD",
-                vec!["m", "n"],
+        ),
+    ]
+}
+
+// Turner の最適化されたラムダ抽象の除去に用いるコンビネータ
+fn turner() -> Vec<Func> {
+    vec![
+        func::new("B", vec!["f", "g", "x"], expr::a("f", expr::a("g", "x"))),
+        func::new("C", vec!["f", "g", "x"], expr::a(expr::a("f", "x"), "g")),
+        func::new(
+            "S_PRIME",
+            vec!["c", "f", "g", "x"],
+            expr::a(expr::a("c", expr::a("f", "x")), expr::a("g", "x")),
+        ),
+        func::new(
+            "B_STAR",
+            vec!["c", "f", "g", "x"],
+            expr::a("c", expr::a("f", expr::a("g", "x"))),
+        ),
+        func::new(
+            "C_PRIME",
+            vec!["c", "f", "g", "x"],
+            expr::a(expr::a("c", expr::a("f", "x")), "g"),
+        ),
+    ]
+}
+
+// Kiselyov のラムダ抽象の除去に用いる n 引数版の B, C, S (n = 2, 3, 4)
+fn bulk() -> Vec<Func> {
+    let mut funcs = Vec::new();
+    for n in 2..=4 {
+        let xs: Vec<&str> = ["x", "y", "z", "w"][..n].to_vec();
+        let apply_xs = |f: &str| xs.iter().fold(expr::v(f), |e, x| expr::a(e, *x));
+        let params: Vec<&str> = ["f", "g"].iter().chain(xs.iter()).copied().collect();
+
+        funcs.push(func::new(
+            format!("B{}", n),
+            params.clone(),
+            expr::a("f", apply_xs("g")),
+        ));
+        funcs.push(func::new(
+            format!("C{}", n),
+            params.clone(),
+            expr::a(apply_xs("f"), "g"),
+        ));
+        funcs.push(func::new(
+            format!("S{}", n),
+            params,
+            expr::a(apply_xs("f"), apply_xs("g")),
+        ));
+    }
+    funcs
+}
+
+// Church 真偽値と論理演算
+fn booleans() -> Vec<Func> {
+    vec![
+        func::new("TRUE", Vec::<&str>::new(), expr::l("x", expr::l("y", "x"))),
+        func::new("FALSE", Vec::<&str>::new(), expr::l("x", expr::l("y", "y"))),
+        func::new(
+            "IF",
+            vec!["PRED", "THEN", "ELSE"],
+            expr::a(expr::a("PRED", "THEN"), "ELSE"),
+        ),
+        func::new("NOT", vec!["x"], expr::a(expr::a("x", "FALSE"), "TRUE")),
+        func::new("AND", vec!["x", "y"], expr::a(expr::a("x", "y"), "FALSE")),
+        func::new("OR", vec!["x", "y"], expr::a(expr::a("x", "TRUE"), "y")),
+        func::new(
+            "XOR",
+            vec!["x", "y"],
+            expr::a(expr::a("x", expr::a("NOT", "y")), "y"),
+        ),
+    ]
+}
+
+// 対とリスト
+fn lists() -> Vec<Func> {
+    vec![
+        func::new(
+            "CONS",
+            vec!["x", "y"],
+            expr::l("f", expr::a(expr::a("f", "x"), "y")),
+        ),
+        func::new("CAR", vec!["x"], expr::a("x", "TRUE")),
+        func::new("CDR", vec!["x"], expr::a("x", "FALSE")),
+        func::new("NIL", Vec::<&str>::new(), "FALSE"),
+        func::new(
+            "IS_NIL",
+            vec!["x"],
+            expr::a(
+                expr::a("x", expr::l("_", expr::l("_", expr::l("_", "FALSE")))),
+                "TRUE",
+            ),
+        ),
+    ]
+}
+
+// Church 数の算術演算 (数値そのものはリテラルとして書く)
+fn naturals() -> Vec<Func> {
+    vec![
+        func::new(
+            "IS_ZERO",
+            vec!["n"],
+            expr::a(expr::a("n", expr::l("_", "FALSE")), "TRUE"),
+        ),
+        func::new(
+            "SUCC",
+            vec!["n"],
+            expr::l(
+                "f",
+                expr::l("x", expr::a("f", expr::a(expr::a("n", "f"), "x"))),
+            ),
+        ),
+        func::new(
+            "ADD",
+            vec!["m", "n"],
+            expr::l(
+                "f",
                 expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(expr::a("m", "f"), expr::a(expr::a("n", "f"), "x")),
-                    ),
+                    "x",
+                    expr::a(expr::a("m", "f"), expr::a(expr::a("n", "f"), "x")),
                 ),
             ),
-            func::new(
-                "MUL",
-                vec!["m", "n"],
-                expr::l("f", expr::a("m", expr::a("n", "f"))),
-            ),
-            func::new(
-                "DIV",
-                vec!["m", "n"],
+        ),
+        func::new(
+            "MUL",
+            vec!["m", "n"],
+            expr::l("f", expr::a("m", expr::a("n", "f"))),
+        ),
+        func::new(
+            "DIV",
+            vec!["m", "n"],
+            expr::a(
                 expr::a(
                     expr::a(
-                        expr::a(
-                            "Y",
+                        "Y",
+                        expr::l(
+                            "g",
                             expr::l(
-                                "g",
+                                "m",
                                 expr::l(
-                                    "m",
-                                    expr::l(
-                                        "n",
-                                        expr::a(
-                                            expr::a(
-                                                expr::a(expr::a("GTE", "m"), "n"),
-                                                expr::a(
-                                                    expr::a(
-                                                        "CONS",
-                                                        expr::a(
-                                                            "SUCC",
-                                                            expr::a(
-                                                                "CAR",
-                                                                expr::a(
-                                                                    expr::a(
-                                                                        "g",
-                                                                        expr::a(
-                                                                            expr::a("SUB", "m"),
-                                                                            "n"
-                                                                        )
-                                                                    ),
-                                                                    "n"
-                                                                )
-                                                            )
-                                                        )
-                                                    ),
-                                                    expr::a(
-                                                        "CDR",
-                                                        expr::a(
-                                                            expr::a(
-                                                                "g",
-                                                                expr::a(expr::a("SUB", "m"), "n")
-                                                            ),
-                                                            "n"
-                                                        )
-                                                    )
-                                                )
-                                            ),
-                                            expr::a(expr::a("CONS", "0"), "m")
-                                        )
-                                    )
-                                )
-                            )
-                        ),
-                        "m"
-                    ),
-                    "n"
-                )
-            ),
-            func::new("POW", vec!["m", "n"], expr::a("n", "m")),
-            func::new(
-                "PRED",
-                vec!["n"],
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            expr::a(
-                                expr::a(
                                     "n",
-                                    expr::l("g", expr::l("h", expr::a("h", expr::a("g", "f")))),
-                                ),
-                                expr::l("u", "x"),
-                            ),
-                            expr::l("u", "u"),
-                        ),
-                    ),
-                ),
-            ),
-            func::new("SUB", vec!["m", "n"], expr::a(expr::a("n", "PRED"), "m")),
-            func::new(
-                "GTE",
-                vec!["m", "n"],
-                expr::a("IS_ZERO", expr::a(expr::a("SUB", "n"), "m")),
-            ),
-            func::new(
-                "LTE",
-                vec!["m", "n"],
-                expr::a("IS_ZERO", expr::a(expr::a("SUB", "m"), "n")),
-            ),
-            func::new(
-                "EQ",
-                vec!["m", "n"],
-                expr::a(
-                    expr::a("AND", expr::a(expr::a("GTE", "m"), "n")),
-                    expr::a(expr::a("LTE", "m"), "n"),
-                ),
-            ),
-            func::new("0", Vec::<&str>::new(), expr::l("f", expr::l("x", "x"))),
-            func::new(
-                "1",
-                Vec::<&str>::new(),
-                expr::l("f", expr::l("x", expr::a("f", "x"))),
-            ),
-            func::new(
-                "2",
-                Vec::<&str>::new(),
-                expr::l("f", expr::l("x", expr::a("f", expr::a("f", "x")))),
-            ),
-            func::new(
-                "3",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l("x", expr::a("f", expr::a("f", expr::a("f", "x")))),
-                ),
-            ),
-            func::new(
-                "4",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a("f", expr::a("f", expr::a("f", expr::a("f", "x")))),
-                    ),
-                ),
-            ),
-            func::new(
-                "5",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a("f", expr::a("f", expr::a("f", expr::a("f", "x")))),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "6",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a("f", expr::a("f", expr::a("f", expr::a("f", "x")))),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "7",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a("f", expr::a("f", expr::a("f", expr::a("f", "x")))),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "8",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
                                     expr::a(
-                                        "f",
-                                        expr::a("f", expr::a("f", expr::a("f", expr::a("f", "x")))),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "9",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
                                         expr::a(
-                                            "f",
+                                            expr::a(expr::a("GTE", "m"), "n"),
                                             expr::a(
-                                                "f",
-                                                expr::a("f", expr::a("f", expr::a("f", "x"))),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "10",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a("f", expr::a("f", expr::a("f", "x"))),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "11",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a("f", expr::a("f", "x")),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "12",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
                                                 expr::a(
-                                                    "f",
+                                                    "CONS",
                                                     expr::a(
-                                                        "f",
+                                                        "SUCC",
                                                         expr::a(
-                                                            "f",
+                                                            "CAR",
                                                             expr::a(
-                                                                "f",
-                                                                expr::a("f", expr::a("f", "x")),
-                                                            ),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "13",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
-                                                                expr::a(
-                                                                    "f",
-                                                                    expr::a("f", expr::a("f", "x")),
-                                                                ),
-                                                            ),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "14",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
-                                                                expr::a(
-                                                                    "f",
-                                                                    expr::a(
-                                                                        "f",
-                                                                        expr::a(
-                                                                            "f",
-                                                                            expr::a("f", "x"),
-                                                                        ),
-                                                                    ),
-                                                                ),
-                                                            ),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "15",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
-                                                                expr::a(
-                                                                    "f",
-                                                                    expr::a(
-                                                                        "f",
-                                                                        expr::a(
-                                                                            "f",
-                                                                            expr::a(
-                                                                                "f",
-                                                                                expr::a("f", "x"),
-                                                                            ),
-                                                                        ),
-                                                                    ),
-                                                                ),
-                                                            ),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "16",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
                                                                 expr::a(
-                                                                    "f",
+                                                                    "g",
                                                                     expr::a(
-                                                                        "f",
-                                                                        expr::a(
-                                                                            "f",
-                                                                            expr::a(
-                                                                                "f",
-                                                                                expr::a(
-                                                                                    "f",
-                                                                                    expr::a(
-                                                                                        "f", "x",
-                                                                                    ),
-                                                                                ),
-                                                                            ),
-                                                                        ),
+                                                                        expr::a("SUB", "m"),
+                                                                        "n",
                                                                     ),
                                                                 ),
+                                                                "n",
                                                             ),
                                                         ),
                                                     ),
                                                 ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "17",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
                                                 expr::a(
-                                                    "f",
+                                                    "CDR",
                                                     expr::a(
-                                                        "f",
                                                         expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
-                                                                expr::a(
-                                                                    "f",
-                                                                    expr::a(
-                                                                        "f",
-                                                                        expr::a(
-                                                                            "f",
-                                                                            expr::a(
-                                                                                "f",
-                                                                                expr::a(
-                                                                                    "f",
-                                                                                    expr::a(
-                                                                                        "f",
-                                                                                        expr::a(
-                                                                                            "f",
-                                                                                            "x",
-                                                                                        ),
-                                                                                    ),
-                                                                                ),
-                                                                            ),
-                                                                        ),
-                                                                    ),
-                                                                ),
-                                                            ),
+                                                            "g",
+                                                            expr::a(expr::a("SUB", "m"), "n"),
                                                         ),
+                                                        "n",
                                                     ),
                                                 ),
                                             ),
                                         ),
+                                        expr::a(expr::a("CONS", church_numeral(0)), "m"),
                                     ),
                                 ),
                             ),
                         ),
                     ),
+                    "m",
                 ),
-            ),
-            func::new(
-                "18",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
-                                                                expr::a(
-                                                                    "f",
-                                                                    expr::a(
-                                                                        "f",
-                                                                        expr::a(
-                                                                            "f",
-                                                                            expr::a(
-                                                                                "f",
-                                                                                expr::a(
-                                                                                    "f",
-                                                                                    expr::a(
-                                                                                        "f",
-                                                                                        expr::a(
-                                                                                            "f",
-                                                                                            expr::a(
-                                                                                                "f",
-                                                                                                "x",
-                                                                                            ),
-                                                                                        ),
-                                                                                    ),
-                                                                                ),
-                                                                            ),
-                                                                        ),
-                                                                    ),
-                                                                ),
-                                                            ),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "19",
-                Vec::<&str>::new(),
+                "n",
+            ),
+        ),
+        func::new("POW", vec!["m", "n"], expr::a("n", "m")),
+        func::new(
+            "PRED",
+            vec!["n"],
+            expr::l(
+                "f",
                 expr::l(
-                    "f",
-                    expr::l(
-                        "x",
-                        expr::a(
-                            "f",
-                            expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
-                                                                expr::a(
-                                                                    "f",
-                                                                    expr::a(
-                                                                        "f",
-                                                                        expr::a(
-                                                                            "f",
-                                                                            expr::a(
-                                                                                "f",
-                                                                                expr::a(
-                                                                                    "f",
-                                                                                    expr::a(
-                                                                                        "f",
-                                                                                        expr::a(
-                                                                                            "f",
-                                                                                            expr::a(
-                                                                                                "f",
-                                                                                                expr::a(
-                                                                                                    "f",
-                                                                                                    "x",
-                                                                                                ),
-                                                                                            ),
-                                                                                        ),
-                                                                                    ),
-                                                                                ),
-                                                                            ),
-                                                                        ),
-                                                                    ),
-                                                                ),
-                                                            ),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
-                            ),
-                        ),
-                    ),
-                ),
-            ),
-            func::new(
-                "20",
-                Vec::<&str>::new(),
-                expr::l(
-                    "f",
-                    expr::l(
-                        "x",
+                    "x",
+                    expr::a(
                         expr::a(
-                            "f",
                             expr::a(
-                                "f",
-                                expr::a(
-                                    "f",
-                                    expr::a(
-                                        "f",
-                                        expr::a(
-                                            "f",
-                                            expr::a(
-                                                "f",
-                                                expr::a(
-                                                    "f",
-                                                    expr::a(
-                                                        "f",
-                                                        expr::a(
-                                                            "f",
-                                                            expr::a(
-                                                                "f",
-                                                                expr::a(
-                                                                    "f",
-                                                                    expr::a(
-                                                                        "f",
-                                                                        expr::a(
-                                                                            "f",
-                                                                            expr::a(
-                                                                                "f",
-                                                                                expr::a(
-                                                                                    "f",
-                                                                                    expr::a(
-                                                                                        "f",
-                                                                                        expr::a(
-                                                                                            "f",
-                                                                                            expr::a(
-                                                                                                "f",
-                                                                                                expr::a(
-                                                                                                    "f",
-                                                                                                    expr::a(
-                                                                                                        "f",
-                                                                                                        "x",
-                                                                                                    ),
-                                                                                                ),
-                                                                                            ),
-                                                                                        ),
-                                                                                    ),
-                                                                                ),
-                                                                            ),
-                                                                        ),
-                                                                    ),
-                                                                ),
-                                                            ),
-                                                        ),
-                                                    ),
-                                                ),
-                                            ),
-                                        ),
-                                    ),
-                                ),
+                                "n",
+                                expr::l("g", expr::l("h", expr::a("h", expr::a("g", "f")))),
                             ),
+                            expr::l("u", "x"),
                         ),
+                        expr::l("u", "u"),
                     ),
                 ),
             ),
-        ])
-    }
+        ),
+        func::new("SUB", vec!["m", "n"], expr::a(expr::a("n", "PRED"), "m")),
+        func::new(
+            "GTE",
+            vec!["m", "n"],
+            expr::a("IS_ZERO", expr::a(expr::a("SUB", "n"), "m")),
+        ),
+        func::new(
+            "LTE",
+            vec!["m", "n"],
+            expr::a("IS_ZERO", expr::a(expr::a("SUB", "m"), "n")),
+        ),
+        func::new(
+            "EQ",
+            vec!["m", "n"],
+            expr::a(
+                expr::a("AND", expr::a(expr::a("GTE", "m"), "n")),
+                expr::a(expr::a("LTE", "m"), "n"),
+            ),
+        ),
+    ]
 }
@@ -0,0 +1,76 @@
+use crate::data::DataError;
+use crate::expr::Identifier;
+use std::fmt::Display;
+
+/// Context の更新に失敗した理由を表現する
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContextError {
+    /// import した関数を定義で上書きしようとした
+    Imported { name: Identifier, module: String },
+    /// import するモジュールの関数が既存の定義と衝突した
+    Conflict { name: Identifier, module: String },
+    /// モジュール名で修飾された関数を定義しようとした
+    Qualified(Identifier),
+    /// 数値リテラルとして読まれる名前の関数を定義しようとした
+    Numeral(Identifier),
+    /// 存在しないモジュールを import しようとした
+    UnknownModule(String),
+    /// data 宣言から関数を生成できなかった
+    Data(DataError),
+}
+
+impl Display for ContextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContextError::Imported { name, module } => write!(
+                f,
+                "`{}` is imported from module `{}`; delete it first with `{} = {}` to redefine it",
+                name, module, name, name
+            ),
+            ContextError::Conflict { name, module } => write!(
+                f,
+                "cannot import module `{}`: `{}` is already defined",
+                module, name
+            ),
+            ContextError::Qualified(name) => {
+                write!(f, "cannot define qualified name `{}`", name)
+            }
+            ContextError::Numeral(name) => {
+                write!(f, "cannot define `{}`: it is read as a numeral", name)
+            }
+            ContextError::UnknownModule(module) => write!(f, "unknown module `{}`", module),
+            ContextError::Data(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ContextError {}
+
+impl From<DataError> for ContextError {
+    fn from(error: DataError) -> Self {
+        ContextError::Data(error)
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ContextError::Imported {
+                name: "TRUE".into(),
+                module: "bool".to_string()
+            }
+            .to_string(),
+            "`TRUE` is imported from module `bool`; delete it first with `TRUE = TRUE` to redefine it"
+        );
+        assert_eq!(
+            ContextError::UnknownModule("foo".to_string()).to_string(),
+            "unknown module `foo`"
+        );
+    }
+}
@@ -1,4 +1,10 @@
 mod context;
 mod default;
+mod error;
+mod module;
+mod source;
 
 pub use context::Context;
+pub use default::BUILTIN_MODULES;
+pub use error::ContextError;
+pub use module::Module;
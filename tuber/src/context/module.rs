@@ -0,0 +1,73 @@
+use super::default;
+use crate::func::Func;
+
+/// 名前の付いた関数定義の集まりを表現する
+///
+/// import すると、モジュールの関数を修飾なしの名前で参照できるようになる
+/// requires に挙げたモジュールは import する前に import される
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module {
+    name: String,
+    requires: Vec<String>,
+    funcs: Vec<Func>,
+}
+
+impl Module {
+    pub fn new(name: &str, requires: Vec<&str>, funcs: Vec<Func>) -> Self {
+        Self {
+            name: name.to_string(),
+            requires: requires.into_iter().map(String::from).collect(),
+            funcs,
+        }
+    }
+
+    /// 組み込みのモジュール (comb, bool, list, nat) を名前から作る
+    pub fn builtin(name: &str) -> Option<Self> {
+        default::builtin(name)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn requires(&self) -> &[String] {
+        &self.requires
+    }
+
+    pub fn funcs(&self) -> &[Func] {
+        &self.funcs
+    }
+
+    /// モジュール名を除いた名前で関数を探す
+    pub fn get(&self, name: &str) -> Option<&Func> {
+        self.funcs.iter().find(|f| f.name() == name)
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::BUILTIN_MODULES;
+
+    #[test]
+    fn test_builtin() {
+        for name in BUILTIN_MODULES {
+            let module = Module::builtin(name).unwrap();
+            assert_eq!(module.name(), name);
+            assert!(!module.funcs().is_empty());
+        }
+
+        assert_eq!(Module::builtin("UNKNOWN"), None);
+    }
+
+    #[test]
+    fn test_get() {
+        let module = Module::builtin("bool").unwrap();
+
+        assert_eq!(module.get("TRUE").map(|f| f.arity()), Some(0));
+        assert_eq!(module.get("NOT").map(|f| f.arity()), Some(1));
+        assert_eq!(module.get("s"), None);
+    }
+}
@@ -0,0 +1,226 @@
+use super::Context;
+use crate::engine::Command;
+use crate::parser::{parse_definitions, ParseError};
+use crate::to_string::{DisplayStyle, Format};
+
+impl Context {
+    /// 関数定義を 1 行に 1 つずつ並べたスクリプトとして出力する
+    ///
+    /// 出力は Context::from_source で読み込める
+    pub fn to_source(&self, style: &DisplayStyle) -> String {
+        let mut source = self.format(style);
+        if !source.is_empty() {
+            source.push('\n');
+        }
+        source
+    }
+
+    /// 関数定義を並べたスクリプトから Context を作る
+    ///
+    /// ECMAScript スタイルと Lazy K スタイルのどちらの定義も読み込める
+    pub fn from_source(source: &str) -> Result<Context, ParseError> {
+        let mut context = Context::new();
+
+        for statement in parse_definitions(source)? {
+            match statement.command {
+                Command::Update(func) => context.insert(func),
+                Command::Del(id) => {
+                    context.del(&id);
+                }
+                _ => unreachable!("parse_definitions returns only Update or Del"),
+            }
+        }
+
+        Ok(context)
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{self, Expr, Identifier};
+    use crate::func::{self, Func};
+    use crate::types::Type;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+
+    const IDENTIFIERS: [&str; 10] = ["x", "y", "z", "f", "g", "ι", "FOO", "BAR_1", "_", "N42"];
+    const TYPE_NAMES: [&str; 2] = ["Nat", "Bool"];
+    const TYPE_PARAMS: [&str; 2] = ["α", "β"];
+
+    // 失敗を再現できるよう、乱数の種を固定する
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(20)
+    }
+
+    fn random_identifier(rng: &mut impl Rng) -> Identifier {
+        (*IDENTIFIERS.choose(rng).unwrap()).into()
+    }
+
+    fn random_type_param(rng: &mut impl Rng) -> Identifier {
+        (*TYPE_PARAMS.choose(rng).unwrap()).into()
+    }
+
+    fn random_type(rng: &mut impl Rng, depth: usize) -> Type {
+        match rng.gen_range(0..if depth == 0 { 2 } else { 4 }) {
+            0 => Type::named(*TYPE_NAMES.choose(rng).unwrap()),
+            1 => Type::named(random_type_param(rng)),
+            2 => Type::arrow(random_type(rng, depth - 1), random_type(rng, depth - 1)),
+            _ => Type::forall(random_type_param(rng), random_type(rng, depth - 1)),
+        }
+    }
+
+    fn random_expr(rng: &mut impl Rng, depth: usize) -> Expr {
+        match rng.gen_range(0..if depth == 0 { 2 } else { 9 }) {
+            0 => expr::v(random_identifier(rng)),
+            1 => expr::s(random_identifier(rng)),
+            2 | 3 => expr::a(random_expr(rng, depth - 1), random_expr(rng, depth - 1)),
+            4 => expr::l(random_identifier(rng), random_expr(rng, depth - 1)),
+            5 => expr::lt(
+                random_identifier(rng),
+                random_type(rng, 2),
+                random_expr(rng, depth - 1),
+            ),
+            6 => expr::tl(random_type_param(rng), random_expr(rng, depth - 1)),
+            7 => expr::ta(random_expr(rng, depth - 1), random_type(rng, 2)),
+            _ => {
+                let name = random_identifier(rng);
+                let value = random_expr(rng, depth - 1);
+                let body = random_expr(rng, depth - 1);
+                if rng.gen() {
+                    expr::letrec(name, value, body)
+                } else {
+                    expr::let_(name, value, body)
+                }
+            }
+        }
+    }
+
+    fn random_func(rng: &mut impl Rng) -> Func {
+        let name = random_identifier(rng);
+        let arity = rng.gen_range(0..4);
+        let params = (0..arity).map(|_| random_identifier(rng)).collect();
+        // 引数がなく自身を参照するだけの関数 (`f = (f)`) も一定の割合で含める
+        let body = if arity == 0 && rng.gen_ratio(1, 10) {
+            expr::v(name.clone())
+        } else {
+            random_expr(rng, 4)
+        };
+        Func::new(name, params, body)
+    }
+
+    fn random_context(rng: &mut impl Rng) -> Context {
+        let size = rng.gen_range(0..8);
+        Context::from((0..size).map(|_| random_func(rng)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_to_source() {
+        let context = Context::from(vec![
+            func::new("i", vec!["x"], "x"),
+            func::new("k", vec!["x", "y"], "x"),
+        ]);
+
+        assert_eq!(
+            context.to_source(&DisplayStyle::EcmaScript),
+            "i(x) = x\nk(x, y) = x\n"
+        );
+        assert_eq!(
+            context.to_source(&DisplayStyle::LazyK),
+            "`ix = x\n``kxy = x\n"
+        );
+        assert_eq!(Context::new().to_source(&DisplayStyle::EcmaScript), "");
+
+        // 自身を参照するだけの関数は削除 (`f = f`) と区別できるよう括弧で囲む
+        let context = Context::from(vec![func::new("f", Vec::<&str>::new(), "f")]);
+        for style in [DisplayStyle::EcmaScript, DisplayStyle::LazyK] {
+            let source = context.to_source(&style);
+            assert_eq!(source, "f = (f)\n");
+            assert_eq!(Context::from_source(&source), Ok(context.clone()));
+        }
+    }
+
+    #[test]
+    fn test_from_source() {
+        let source = "
+# 組み込みの関数
+i(x) = x
+``kxy = x
+s(x, y, z) = x(z)(y(z))
+i = i
+";
+
+        assert_eq!(
+            Context::from_source(source),
+            Ok(Context::from(vec![
+                func::new("k", vec!["x", "y"], "x"),
+                func::new(
+                    "s",
+                    vec!["x", "y", "z"],
+                    expr::a(expr::a("x", "z"), expr::a("y", "z"))
+                ),
+            ]))
+        );
+
+        assert!(Context::from_source("i(x) = x\n!i(:a)").is_err());
+
+        // 数字だけからなる名前は数値リテラルとして読まれるので定義できない
+        assert!(Context::from_source("21 = :a").is_err());
+        assert!(Context::from_source("`21x = x").is_err());
+    }
+
+    #[test]
+    fn test_round_trip_default() {
+        for style in [DisplayStyle::EcmaScript, DisplayStyle::LazyK] {
+            let context = Context::default();
+            let source = context.to_source(&style);
+            // どのモジュールから import したかは復元されない
+            assert_eq!(
+                Context::from_source(&source).map(Context::to_vec),
+                Ok(context.to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip_source() {
+        let mut rng = rng();
+
+        for _ in 0..200 {
+            let context = random_context(&mut rng);
+            for style in [DisplayStyle::EcmaScript, DisplayStyle::LazyK] {
+                let source = context.to_source(&style);
+                assert_eq!(
+                    Context::from_source(&source).as_ref(),
+                    Ok(&context),
+                    "{}",
+                    source
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_json() {
+        let mut rng = rng();
+
+        for _ in 0..200 {
+            let context = random_context(&mut rng);
+            let json = serde_json::to_string(&context).unwrap();
+            assert_eq!(serde_json::from_str::<Context>(&json).unwrap(), context);
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        let context = Context::from(vec![func::new("k", vec!["x", "y"], expr::l("z", "x"))]);
+
+        assert_eq!(
+            serde_json::to_string(&context).unwrap(),
+            r#"[{"name":"k","params":["x","y"],"body":{"Lambda":{"param":"z","body":{"Variable":"x"}}}}]"#
+        );
+    }
+}
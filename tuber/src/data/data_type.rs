@@ -0,0 +1,260 @@
+use super::error::DataError;
+use crate::expr::Identifier;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// data 宣言で定義する代数的データ型
+///
+/// `data TREE = LEAF | NODE l:TREE v r:TREE` のように、構築子とそのフィールドを並べて宣言する
+/// 宣言した型名を注釈したフィールドは再帰的なフィールドとして扱う
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataType {
+    name: Identifier,
+    encoding: Encoding,
+    constructors: Vec<Constructor>,
+}
+
+/// 構築子とそのフィールド
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constructor {
+    name: Identifier,
+    fields: Vec<Field>,
+}
+
+/// 構築子のフィールド (`v` または型名を注釈した `l:TREE`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    name: Identifier,
+    ty: Option<Identifier>,
+}
+
+/// データ型をラムダ式で表現する方法
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// 値を畳み込みとして表現する (再帰的なフィールドは畳み込んだ結果を渡す)
+    Church,
+    /// 値を場合分けとして表現する (フィールドをそのまま渡す)
+    #[default]
+    Scott,
+    /// フィールドと、再帰的なフィールドを畳み込んだ結果の両方を渡す
+    Parigot,
+}
+
+impl DataType {
+    pub fn new(name: Identifier, encoding: Encoding, constructors: Vec<Constructor>) -> Self {
+        Self {
+            name,
+            encoding,
+            constructors,
+        }
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn constructors(&self) -> &[Constructor] {
+        &self.constructors
+    }
+
+    /// 場合分けをする関数の名前 (`CASE_TREE`)
+    pub fn case_name(&self) -> Identifier {
+        format!("CASE_{}", self.name.as_str()).into()
+    }
+
+    /// 畳み込みをする関数の名前 (`FOLD_TREE`)
+    pub fn fold_name(&self) -> Identifier {
+        format!("FOLD_{}", self.name.as_str()).into()
+    }
+
+    pub fn is_recursive(&self, field: &Field) -> bool {
+        field.ty.as_ref() == Some(&self.name)
+    }
+
+    /// 生成する関数の名前とフィールドの名前が衝突しないことを確かめる
+    pub fn validate(&self) -> Result<(), DataError> {
+        let mut names = HashSet::from([self.case_name(), self.fold_name()]);
+        for constructor in &self.constructors {
+            if !names.insert(constructor.name.clone()) {
+                return Err(DataError::DuplicateName(constructor.name.clone()));
+            }
+        }
+
+        for constructor in &self.constructors {
+            let mut fields = HashSet::new();
+            for field in &constructor.fields {
+                if names.contains(&field.name) || !fields.insert(&field.name) {
+                    return Err(DataError::InvalidField {
+                        constructor: constructor.name.clone(),
+                        field: field.name.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Constructor {
+    pub fn new(name: Identifier, fields: Vec<Field>) -> Self {
+        Self { name, fields }
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+}
+
+impl Field {
+    pub fn new(name: Identifier, ty: Option<Identifier>) -> Self {
+        Self { name, ty }
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn ty(&self) -> Option<&Identifier> {
+        self.ty.as_ref()
+    }
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "data ")?;
+        if self.encoding != Encoding::default() {
+            write!(f, "{} ", self.encoding)?;
+        }
+        write!(f, "{} =", self.name)?;
+        for (i, constructor) in self.constructors.iter().enumerate() {
+            if 0 < i {
+                write!(f, " |")?;
+            }
+            write!(f, " {}", constructor.name)?;
+            for field in &constructor.fields {
+                match &field.ty {
+                    Some(ty) => write!(f, " {}:{}", field.name, ty)?,
+                    None => write!(f, " {}", field.name)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encoding::Church => write!(f, "church"),
+            Encoding::Scott => write!(f, "scott"),
+            Encoding::Parigot => write!(f, "parigot"),
+        }
+    }
+}
+
+/// data 宣言を作る (`constructors` は構築子の名前とフィールドの組)
+#[cfg(test)]
+pub fn new<Name, Ctor, F>(
+    name: Name,
+    encoding: Encoding,
+    constructors: Vec<(Ctor, Vec<F>)>,
+) -> DataType
+where
+    Name: Into<Identifier>,
+    Ctor: Into<Identifier>,
+    F: Into<Field>,
+{
+    DataType::new(
+        name.into(),
+        encoding,
+        constructors
+            .into_iter()
+            .map(|(name, fields)| {
+                Constructor::new(name.into(), fields.into_iter().map(Into::into).collect())
+            })
+            .collect(),
+    )
+}
+
+impl From<&str> for Field {
+    fn from(name: &str) -> Field {
+        Field::new(name.into(), None)
+    }
+}
+
+impl From<(&str, &str)> for Field {
+    fn from((name, ty): (&str, &str)) -> Field {
+        Field::new(name.into(), Some(ty.into()))
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let tree = new(
+            "TREE",
+            Encoding::Scott,
+            vec![
+                ("LEAF", vec![]),
+                ("NODE", vec![("l", "TREE"), ("v", "NAT"), ("r", "TREE")]),
+            ],
+        );
+        assert_eq!(tree.validate(), Ok(()));
+
+        let node = &tree.constructors()[1];
+        assert!(tree.is_recursive(&node.fields()[0]));
+        assert!(!tree.is_recursive(&node.fields()[1]));
+
+        let data = new::<_, _, &str>("T", Encoding::Scott, vec![("A", vec![]), ("A", vec![])]);
+        assert_eq!(data.validate(), Err(DataError::DuplicateName("A".into())));
+
+        let data = new("T", Encoding::Scott, vec![("A", vec!["x", "x"])]);
+        assert_eq!(
+            data.validate(),
+            Err(DataError::InvalidField {
+                constructor: "A".into(),
+                field: "x".into()
+            })
+        );
+
+        let data = new("T", Encoding::Scott, vec![("A", vec!["B"]), ("B", vec![])]);
+        assert!(data.validate().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let tree = new(
+            "TREE",
+            Encoding::Scott,
+            vec![
+                ("LEAF", vec![]),
+                (
+                    "NODE",
+                    vec![("l", "TREE").into(), Field::from("v"), ("r", "TREE").into()],
+                ),
+            ],
+        );
+        assert_eq!(tree.to_string(), "data TREE = LEAF | NODE l:TREE v r:TREE");
+
+        let nat = new(
+            "NAT",
+            Encoding::Church,
+            vec![("Z", vec![]), ("S", vec![("n", "NAT")])],
+        );
+        assert_eq!(nat.to_string(), "data church NAT = Z | S n:NAT");
+    }
+}
@@ -0,0 +1,392 @@
+use super::data_type::{Constructor, DataType, Encoding};
+use super::error::DataError;
+use crate::expr::{self, Expr, Identifier};
+use crate::func::Func;
+use std::collections::HashSet;
+
+impl DataType {
+    /// 構築子と場合分け・畳み込みの関数を生成する
+    ///
+    /// 生成した関数はデータ型の名前を origin にもつ
+    /// 構築子の値は、構築子ごとの処理 (ハンドラ) を宣言の順に受け取る関数になる
+    ///
+    /// - Scott: `NODE(l, v, r) = (a, n) => n(l, v, r)`
+    /// - Church: `NODE(l, v, r) = (a, n) => n(l(a, n), v, r(a, n))`
+    /// - Parigot: `NODE(l, v, r) = (a, n) => n(l, l(a, n), v, r, r(a, n))`
+    pub fn funcs(&self) -> Result<Vec<Func>, DataError> {
+        self.validate()?;
+
+        let mut names = Names::new(self);
+        let handlers: Vec<Identifier> = self
+            .constructors()
+            .iter()
+            .map(|constructor| names.fresh(&constructor.name().as_str().to_lowercase()))
+            .collect();
+        let x = names.fresh("x");
+
+        let mut funcs: Vec<Func> = self
+            .constructors()
+            .iter()
+            .zip(&handlers)
+            .map(|(constructor, handler)| self.constructor(constructor, &handlers, handler))
+            .collect();
+        funcs.push(self.case(&mut names.clone(), &x, &handlers));
+        funcs.push(self.fold(&mut names.clone(), &x, &handlers));
+
+        Ok(funcs
+            .into_iter()
+            .map(|func| func.with_origin(self.name().clone()))
+            .collect())
+    }
+
+    fn constructor(
+        &self,
+        constructor: &Constructor,
+        handlers: &[Identifier],
+        handler: &Identifier,
+    ) -> Func {
+        let mut args = Vec::new();
+        for field in constructor.fields() {
+            let folded = apply(expr::v(field.name().clone()), vars(handlers));
+            match self.encoding() {
+                Encoding::Scott => args.push(expr::v(field.name().clone())),
+                Encoding::Church if self.is_recursive(field) => args.push(folded),
+                Encoding::Church => args.push(expr::v(field.name().clone())),
+                Encoding::Parigot => {
+                    args.push(expr::v(field.name().clone()));
+                    if self.is_recursive(field) {
+                        args.push(folded);
+                    }
+                }
+            }
+        }
+
+        Func::new(
+            constructor.name().clone(),
+            constructor
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect(),
+            lambda(handlers, apply(expr::v(handler.clone()), args)),
+        )
+    }
+
+    // CASE_T(x, a, n) は x の最も外側の構築子で場合分けする
+    fn case(&self, names: &mut Names, x: &Identifier, handlers: &[Identifier]) -> Func {
+        let body = match self.encoding() {
+            Encoding::Scott => apply(expr::v(x.clone()), vars(handlers)),
+
+            // 畳み込んだ結果を捨て、フィールドだけをハンドラに渡す
+            Encoding::Parigot => {
+                let cases = self.parigot(names, handlers, |field, _| field);
+                apply(expr::v(x.clone()), cases)
+            }
+
+            // 元の値と場合分けの結果の組を畳み込みで作り、後者を取り出す
+            Encoding::Church => {
+                let p = names.fresh("p");
+                let q = names.fresh("q");
+                let s = names.fresh("s");
+                let first = expr::l(p.clone(), expr::l(q.clone(), expr::v(p.clone())));
+                let second = expr::l(p, expr::l(q.clone(), expr::v(q)));
+
+                let cases =
+                    self.constructors()
+                        .iter()
+                        .zip(handlers)
+                        .map(|(constructor, handler)| {
+                            let fields: Vec<Expr> = constructor
+                                .fields()
+                                .iter()
+                                .map(|field| match self.is_recursive(field) {
+                                    true => expr::a(expr::v(field.name().clone()), first.clone()),
+                                    false => expr::v(field.name().clone()),
+                                })
+                                .collect();
+                            let pair = expr::l(
+                                s.clone(),
+                                apply(
+                                    expr::v(s.clone()),
+                                    [
+                                        apply(expr::v(constructor.name().clone()), fields.clone()),
+                                        apply(expr::v(handler.clone()), fields),
+                                    ],
+                                ),
+                            );
+                            lambda(&field_names(constructor), pair)
+                        });
+                expr::a(apply(expr::v(x.clone()), cases), second)
+            }
+        };
+
+        Func::new(self.case_name(), params(x, handlers), body)
+    }
+
+    // FOLD_T(x, a, n) は再帰的なフィールドを畳み込んだ結果をハンドラに渡す
+    fn fold(&self, names: &mut Names, x: &Identifier, handlers: &[Identifier]) -> Func {
+        let body = match self.encoding() {
+            Encoding::Church => apply(expr::v(x.clone()), vars(handlers)),
+            Encoding::Parigot => {
+                let cases = self.parigot(names, handlers, |_, folded| folded);
+                apply(expr::v(x.clone()), cases)
+            }
+
+            // Scott エンコーディングでは FOLD_T 自身を再帰的に呼び出す
+            Encoding::Scott => {
+                let cases =
+                    self.constructors()
+                        .iter()
+                        .zip(handlers)
+                        .map(|(constructor, handler)| {
+                            if !self.has_recursive_field(constructor) {
+                                return expr::v(handler.clone());
+                            }
+                            let args = constructor.fields().iter().map(|field| {
+                                let field_var = expr::v(field.name().clone());
+                                match self.is_recursive(field) {
+                                    true => apply(
+                                        expr::a(expr::v(self.fold_name()), field_var),
+                                        vars(handlers),
+                                    ),
+                                    false => field_var,
+                                }
+                            });
+                            lambda(
+                                &field_names(constructor),
+                                apply(expr::v(handler.clone()), args),
+                            )
+                        });
+                apply(expr::v(x.clone()), cases)
+            }
+        };
+
+        Func::new(self.fold_name(), params(x, handlers), body)
+    }
+
+    // Parigot エンコーディングの値に渡すハンドラ
+    //
+    // 再帰的なフィールドはフィールドと畳み込んだ結果の 2 つを受け取り、select でどちらかを選ぶ
+    fn parigot(
+        &self,
+        names: &mut Names,
+        handlers: &[Identifier],
+        select: impl Fn(Expr, Expr) -> Expr,
+    ) -> Vec<Expr> {
+        self.constructors()
+            .iter()
+            .zip(handlers)
+            .map(|(constructor, handler)| {
+                if !self.has_recursive_field(constructor) {
+                    return expr::v(handler.clone());
+                }
+
+                let mut params = Vec::new();
+                let mut args = Vec::new();
+                for field in constructor.fields() {
+                    params.push(field.name().clone());
+                    if self.is_recursive(field) {
+                        let folded = names.fresh(&field.name().as_str().to_uppercase());
+                        params.push(folded.clone());
+                        args.push(select(expr::v(field.name().clone()), expr::v(folded)));
+                    } else {
+                        args.push(expr::v(field.name().clone()));
+                    }
+                }
+                lambda(&params, apply(expr::v(handler.clone()), args))
+            })
+            .collect()
+    }
+
+    fn has_recursive_field(&self, constructor: &Constructor) -> bool {
+        constructor
+            .fields()
+            .iter()
+            .any(|field| self.is_recursive(field))
+    }
+}
+
+// ========================================================================== //
+
+// 生成する関数の中で使う名前
+//
+// フィールドや構築子、生成する関数の名前と衝突しない名前を選ぶ
+#[derive(Clone)]
+struct Names(HashSet<Identifier>);
+
+impl Names {
+    fn new(data: &DataType) -> Self {
+        let mut used = HashSet::from([data.name().clone(), data.case_name(), data.fold_name()]);
+        for constructor in data.constructors() {
+            used.insert(constructor.name().clone());
+            used.extend(field_names(constructor));
+        }
+        Self(used)
+    }
+
+    // base の先頭の文字 (英小文字なら a から z の順に、それ以外は末尾に数字を付けて) から選ぶ
+    fn fresh(&mut self, base: &str) -> Identifier {
+        let candidates: Vec<Identifier> = match base.chars().next() {
+            Some(c) if c.is_ascii_lowercase() => std::iter::once(c)
+                .chain('a'..='z')
+                .map(|c| c.to_string().into())
+                .collect(),
+            _ => vec![base.into()],
+        };
+        let name = candidates
+            .into_iter()
+            .find(|name| !self.0.contains(name))
+            .unwrap_or_else(|| {
+                let base = base.to_uppercase();
+                (0..)
+                    .map(|i| Identifier::from(format!("{}{}", base, i).as_str()))
+                    .find(|name| !self.0.contains(name))
+                    .unwrap()
+            });
+        self.0.insert(name.clone());
+        name
+    }
+}
+
+fn field_names(constructor: &Constructor) -> Vec<Identifier> {
+    constructor
+        .fields()
+        .iter()
+        .map(|field| field.name().clone())
+        .collect()
+}
+
+fn params(x: &Identifier, handlers: &[Identifier]) -> Vec<Identifier> {
+    std::iter::once(x.clone())
+        .chain(handlers.iter().cloned())
+        .collect()
+}
+
+fn vars(ids: &[Identifier]) -> Vec<Expr> {
+    ids.iter().map(|id| expr::v(id.clone())).collect()
+}
+
+fn apply(f: Expr, args: impl IntoIterator<Item = Expr>) -> Expr {
+    args.into_iter().fold(f, expr::a)
+}
+
+fn lambda(params: &[Identifier], body: Expr) -> Expr {
+    params
+        .iter()
+        .rev()
+        .fold(body, |body, param| expr::l(param.clone(), body))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::super::data_type::{new, Field};
+    use super::*;
+    use crate::calc::{Aliases, Reducer, ReductionStrategy};
+    use crate::context::Context;
+    use crate::parser::parse_expr;
+    use crate::to_string::{DisplayStyle, Format};
+
+    fn tree(encoding: Encoding) -> DataType {
+        new(
+            "TREE",
+            encoding,
+            vec![
+                ("LEAF", vec![]),
+                (
+                    "NODE",
+                    vec![("l", "TREE").into(), Field::from("v"), ("r", "TREE").into()],
+                ),
+            ],
+        )
+    }
+
+    fn formatted(data: &DataType) -> Vec<String> {
+        data.funcs()
+            .unwrap()
+            .iter()
+            .map(|func| func.format(&DisplayStyle::EcmaScript))
+            .collect()
+    }
+
+    fn eval(data: &DataType, source: &str) -> Expr {
+        let mut context = Context::default();
+        context.def_data(data).unwrap();
+        let reducer = Reducer::new(
+            context,
+            Aliases::new(),
+            parse_expr(source).unwrap(),
+            ReductionStrategy::NormalOrder,
+        );
+        reducer.last().unwrap().expr
+    }
+
+    #[test]
+    fn test_scott() {
+        assert_eq!(
+            formatted(&tree(Encoding::Scott)),
+            vec![
+                "LEAF = (a, n) => a",
+                "NODE(l, v, r) = (a, n) => n(l, v, r)",
+                "CASE_TREE(x, a, n) = x(a, n)",
+                "FOLD_TREE(x, a, n) = x(a, (l, v, r) => n(FOLD_TREE(l, a, n), v, FOLD_TREE(r, a, n)))",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_church() {
+        assert_eq!(
+            formatted(&tree(Encoding::Church)),
+            vec![
+                "LEAF = (a, n) => a",
+                "NODE(l, v, r) = (a, n) => n(l(a, n), v, r(a, n))",
+                "CASE_TREE(x, a, n) = x(s => s(LEAF, a), (l, v, r, s) => s(NODE(l((p, q) => p), v, r((p, q) => p)), n(l((p, q) => p), v, r((p, q) => p))), (p, q) => q)",
+                "FOLD_TREE(x, a, n) = x(a, n)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parigot() {
+        assert_eq!(
+            formatted(&tree(Encoding::Parigot)),
+            vec![
+                "LEAF = (a, n) => a",
+                "NODE(l, v, r) = (a, n) => n(l, l(a, n), v, r, r(a, n))",
+                "CASE_TREE(x, a, n) = x(a, (l, L, v, r, R) => n(l, v, r))",
+                "FOLD_TREE(x, a, n) = x(a, (l, L, v, r, R) => n(L, v, R))",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_origin() {
+        for func in tree(Encoding::Scott).funcs().unwrap() {
+            assert_eq!(func.origin(), Some(&"TREE".into()));
+        }
+    }
+
+    #[test]
+    fn test_eval() {
+        // どのエンコーディングでも、場合分けと畳み込みは同じ結果になる
+        for encoding in [Encoding::Church, Encoding::Scott, Encoding::Parigot] {
+            let data = tree(encoding);
+
+            let case = "CASE_TREE(NODE(LEAF, :v, LEAF), :LEAF, (l, v, r) => v)";
+            assert_eq!(eval(&data, case), expr::s("v"), "{:?}", encoding);
+
+            let case = "CASE_TREE(LEAF, :LEAF, :NODE)";
+            assert_eq!(eval(&data, case), expr::s("LEAF"), "{:?}", encoding);
+
+            let fold = "FOLD_TREE(NODE(NODE(LEAF, :a, LEAF), :b, LEAF), :z, (l, v, r) => :n(l, r))";
+            assert_eq!(
+                eval(&data, fold),
+                expr::a(expr::a(":n", expr::a(expr::a(":n", ":z"), ":z")), ":z"),
+                "{:?}",
+                encoding
+            );
+        }
+    }
+}
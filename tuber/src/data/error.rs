@@ -0,0 +1,29 @@
+use crate::expr::Identifier;
+use std::fmt::Display;
+
+/// data 宣言から関数を生成できなかった理由を表現する
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataError {
+    /// 構築子の名前が他の構築子や生成する関数と重複した
+    DuplicateName(Identifier),
+    /// フィールドの名前が重複したか、構築子や生成する関数の名前と衝突した
+    InvalidField {
+        constructor: Identifier,
+        field: Identifier,
+    },
+}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::DuplicateName(name) => write!(f, "`{}` is declared more than once", name),
+            DataError::InvalidField { constructor, field } => write!(
+                f,
+                "field `{}` of `{}` must have a name unique among the fields and the generated functions",
+                field, constructor
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
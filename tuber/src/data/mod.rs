@@ -0,0 +1,8 @@
+mod data_type;
+mod encode;
+mod error;
+
+#[cfg(test)]
+pub use data_type::new;
+pub use data_type::{Constructor, DataType, Encoding, Field};
+pub use error::DataError;
@@ -1,3 +1,4 @@
+use crate::data::DataType;
 use crate::expr::{Expr, Identifier};
 use crate::func::Func;
 
@@ -13,7 +14,11 @@ pub enum Command {
     EvalTail(usize, Expr), // β変換列の末尾のみ表示
     Query(Identifier),     // Context から定義済み関数を検索
     Context,               // Context 全体を表示
+    Import(String),        // モジュールを import
     Unlambda(u8, Expr),    // Expr からラムダ抽象を除去する
+    Metrics(Expr),         // ラムダ抽象の除去の方法ごとに結果の式を比較する
+    TypeOf(Expr),          // 式の型を推論する
+    Data(DataType),        // データ型の構築子などの関数を定義する
 }
 
 #[cfg(test)]
@@ -56,7 +61,27 @@ pub fn context() -> Command {
     Command::Context
 }
 
+#[cfg(test)]
+pub fn import(module: &str) -> Command {
+    Command::Import(module.to_string())
+}
+
 #[cfg(test)]
 pub fn unlambda<E: Into<Expr>>(level: u8, expr: E) -> Command {
     Command::Unlambda(level, expr.into())
 }
+
+#[cfg(test)]
+pub fn metrics<E: Into<Expr>>(expr: E) -> Command {
+    Command::Metrics(expr.into())
+}
+
+#[cfg(test)]
+pub fn type_of<E: Into<Expr>>(expr: E) -> Command {
+    Command::TypeOf(expr.into())
+}
+
+#[cfg(test)]
+pub fn data(data: DataType) -> Command {
+    Command::Data(data)
+}
@@ -0,0 +1,586 @@
+use super::command::Command;
+use super::output::{Comparison, Output, Reduction};
+use crate::calc::{
+    compare_unlambda, expand, sample_args, unlambda_iota, unlambda_kiselyov, unlambda_recursive,
+    unlambda_recursive_, unlambda_turner, Aliases, RecursiveStrategy, Reducer, ReductionStrategy,
+    ReductionTarget, Termination,
+};
+use crate::context::Context;
+use crate::expr::Expr;
+use crate::types::{infer_type, Type};
+use anyhow::{anyhow, Result};
+use std::collections::VecDeque;
+
+/// 簡約ステップ数の上限の既定値
+pub const DEFAULT_MAX_STEPS: usize = 1000;
+
+/// Context と Aliases を保持し、コマンドを実行する
+pub struct Engine {
+    context: Context,
+    aliases: Aliases,
+    strategy: ReductionStrategy,
+    max_steps: usize,
+    strong_normalization: bool,
+    eta_reduction: bool,
+    target: ReductionTarget,
+}
+
+impl Engine {
+    pub fn new(context: Context, aliases: Aliases) -> Self {
+        Self {
+            context,
+            aliases,
+            strategy: ReductionStrategy::default(),
+            max_steps: DEFAULT_MAX_STEPS,
+            strong_normalization: false,
+            eta_reduction: false,
+            target: ReductionTarget::default(),
+        }
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    pub fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    /// 簡約に用いる評価戦略を設定する
+    pub fn set_strategy(&mut self, strategy: ReductionStrategy) {
+        self.strategy = strategy;
+    }
+
+    /// 1つのコマンドで行う簡約ステップ数の上限を設定する
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        self.max_steps = max_steps;
+    }
+
+    /// 関数定義を展開し、ラムダ抽象の内側まで簡約するかどうかを設定する
+    pub fn set_strong_normalization(&mut self, enabled: bool) {
+        self.strong_normalization = enabled;
+    }
+
+    /// 強正規化の際に η簡約も行うかどうかを設定する
+    pub fn set_eta_reduction(&mut self, enabled: bool) {
+        self.eta_reduction = enabled;
+    }
+
+    /// どの形に達したら簡約をやめるかを設定する
+    pub fn set_target(&mut self, target: ReductionTarget) {
+        self.target = target;
+    }
+
+    /// コマンドを実行する
+    ///
+    /// 簡約やラムダ抽象の除去の結果は `_` として Aliases に登録する
+    pub fn run(&mut self, command: Command) -> Result<Output> {
+        match command {
+            // データ型の名前を指定すると、そこから生成した関数をまとめて削除する
+            Command::Del(id) => {
+                self.context.del_data(&id);
+                self.context.del(&id);
+                Ok(Output::Del(id))
+            }
+            Command::Update(func) => {
+                self.context.def(func.clone())?;
+                Ok(Output::Update(func))
+            }
+            Command::Eval(expr) => {
+                let reduction = self.reduce(expr, self.max_steps, None)?;
+                Ok(Output::Eval(reduction))
+            }
+            Command::EvalLast(expr) => {
+                let reduction = self.reduce(expr, self.max_steps, Some(1))?;
+                Ok(Output::EvalLast(reduction))
+            }
+            Command::EvalHead(count, expr) => {
+                let reduction = self.reduce(expr, count.min(self.max_steps), None)?;
+                Ok(Output::EvalHead(reduction))
+            }
+            Command::EvalTail(count, expr) => {
+                let reduction = self.reduce(expr, self.max_steps, Some(count))?;
+                Ok(Output::EvalTail(reduction))
+            }
+            Command::Query(id) => match self.context.get(&id) {
+                Some(func) => Ok(Output::QueryDefined(func.clone())),
+                None => {
+                    let funcs = self.context.data(&id);
+                    if funcs.is_empty() {
+                        Ok(Output::QueryUndefined(id))
+                    } else {
+                        Ok(Output::Data { name: id, funcs })
+                    }
+                }
+            },
+            Command::Context => Ok(Output::Context(self.context.clone().to_vec())),
+            Command::Import(module) => {
+                self.context.import(&module)?;
+                let funcs = self.context.module(&module).unwrap().funcs().to_vec();
+                Ok(Output::Import { module, funcs })
+            }
+            Command::Unlambda(level, expr) => {
+                let result = match level {
+                    1 => expand(&self.context, expr.clone()),
+                    2 => unlambda_recursive(&self.context, expr.clone()),
+                    3 => unlambda_recursive_(&RecursiveStrategy::SK, &self.context, expr.clone()),
+                    4 => unlambda_iota(&self.context, expr.clone()),
+                    5 => unlambda_turner(&self.context, expr.clone()),
+                    6 => unlambda_kiselyov(&self.context, expr.clone()),
+                    _ => return Err(anyhow!("invalid unlambda level: {}", level)),
+                };
+                self.aliases.push(result.clone());
+                Ok(Output::Unlambda { expr, result })
+            }
+            Command::Metrics(expr) => {
+                let samples = vec![sample_args(&self.context, &expr)];
+                let reports = compare_unlambda(&self.context, &expr, &samples);
+                Ok(Output::Metrics(Comparison {
+                    expr,
+                    samples,
+                    reports,
+                }))
+            }
+            Command::TypeOf(expr) => {
+                let ty = self.infer_type(&expr)?;
+                Ok(Output::TypeOf { expr, ty })
+            }
+            Command::Data(data) => {
+                let funcs = self.context.def_data(&data)?;
+                Ok(Output::Data {
+                    name: data.name().clone(),
+                    funcs,
+                })
+            }
+        }
+    }
+
+    // `_` などの別名は登録された式に置き換えてから推論する
+    fn infer_type(&self, expr: &Expr) -> Result<Type> {
+        let mut resolved = expr.clone();
+        for (id, alias) in self.aliases.clone() {
+            resolved.substitute(&id, &alias);
+        }
+        Ok(infer_type(&self.context, &resolved)?)
+    }
+
+    // max_steps ステップまで簡約し、tail が与えられた場合は末尾の tail ステップのみを残す
+    //
+    // 型注釈の付いた式は型を検査してから、型を取り除いて簡約する
+    fn reduce(&mut self, expr: Expr, max_steps: usize, tail: Option<usize>) -> Result<Reduction> {
+        if expr.is_typed() {
+            self.infer_type(&expr)?;
+        }
+
+        let mut reducer = Reducer::new(
+            self.context.clone(),
+            self.aliases.clone(),
+            expr.clone(),
+            self.strategy,
+        );
+        reducer.set_max_steps(Some(max_steps));
+        reducer.set_type_erasure(true);
+        reducer.set_strong_normalization(self.strong_normalization);
+        reducer.set_eta_reduction(self.eta_reduction);
+        // 既定の目標のままなら、各ステップで達している形を求める手間を省く
+        if self.target != ReductionTarget::default() {
+            reducer.set_target(self.target);
+        }
+
+        let mut steps = VecDeque::new();
+        for result in reducer.by_ref() {
+            steps.push_back(result);
+            if let Some(tail) = tail {
+                if steps.len() > tail {
+                    steps.pop_front();
+                }
+            }
+        }
+        let steps = Vec::from(steps);
+
+        if let Some(last) = steps.last() {
+            self.aliases.push(last.expr.clone());
+        }
+
+        Ok(Reduction {
+            expr,
+            steps,
+            termination: reducer
+                .termination()
+                .cloned()
+                .unwrap_or(Termination::NormalForm),
+        })
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::COMPARED_STRATEGIES;
+    use crate::engine::command;
+    use crate::expr;
+    use crate::func;
+    use crate::types::TypeError;
+
+    fn setup() -> Engine {
+        Engine::new(Context::default(), Aliases::new())
+    }
+
+    fn exprs(reduction: &Reduction) -> Vec<Expr> {
+        reduction.steps.iter().map(|r| r.expr.clone()).collect()
+    }
+
+    #[test]
+    fn test_update_and_del() {
+        let mut engine = Engine::new(Context::new(), Aliases::new());
+
+        let f = func::new("f", vec!["x"], "x");
+        assert_eq!(
+            engine.run(command::update(f.clone())).unwrap(),
+            Output::Update(f.clone())
+        );
+        assert_eq!(
+            engine.run(command::query("f")).unwrap(),
+            Output::QueryDefined(f.clone())
+        );
+        assert_eq!(
+            engine.run(command::context()).unwrap(),
+            Output::Context(vec![f])
+        );
+
+        assert_eq!(
+            engine.run(command::del("f")).unwrap(),
+            Output::Del("f".into())
+        );
+        assert_eq!(
+            engine.run(command::query("f")).unwrap(),
+            Output::QueryUndefined("f".into())
+        );
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut engine = setup();
+
+        let output = engine
+            .run(command::eval(expr::a(
+                expr::a(expr::a("s", "k"), "k"),
+                ":x",
+            )))
+            .unwrap();
+        let Output::Eval(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(
+            exprs(&reduction),
+            vec![
+                expr::a(expr::a("k", ":x"), expr::a("k", ":x")),
+                expr::s("x"),
+            ]
+        );
+        assert_eq!(reduction.termination, Termination::NormalForm);
+
+        // 結果は `_` として参照できる
+        assert_eq!(engine.aliases().get(&"_".into()), Some(&expr::s("x")));
+    }
+
+    #[test]
+    fn test_eval_last() {
+        let mut engine = setup();
+
+        let output = engine
+            .run(command::eval_last(expr::a(
+                expr::a(expr::a("s", "k"), "k"),
+                ":x",
+            )))
+            .unwrap();
+        let Output::EvalLast(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(exprs(&reduction), vec![expr::s("x")]);
+        assert_eq!(reduction.steps[0].step, 2);
+    }
+
+    #[test]
+    fn test_eval_strong_normalization() {
+        let mut engine = setup();
+        engine.set_strong_normalization(true);
+
+        let output = engine
+            .run(command::eval_last(expr::a(expr::a("s", "k"), "k")))
+            .unwrap();
+        let Output::EvalLast(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(exprs(&reduction), vec![expr::l("z", "z")]);
+        assert_eq!(reduction.termination, Termination::NormalForm);
+    }
+
+    #[test]
+    fn test_eval_head_and_tail() {
+        let mut engine = setup();
+        let w = expr::l("x", expr::a("x", "x"));
+        let omega = expr::a(w.clone(), w);
+
+        let output = engine.run(command::eval_head(3, omega.clone())).unwrap();
+        let Output::EvalHead(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(reduction.steps.len(), 3);
+        assert_eq!(
+            reduction.termination,
+            Termination::StepLimit { max_steps: 3 }
+        );
+
+        engine.set_max_steps(10);
+        let output = engine.run(command::eval_tail(3, omega)).unwrap();
+        let Output::EvalTail(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        let steps: Vec<_> = reduction.steps.iter().map(|r| r.step).collect();
+        assert_eq!(steps, vec![8, 9, 10]);
+        assert_eq!(
+            reduction.termination,
+            Termination::StepLimit { max_steps: 10 }
+        );
+    }
+
+    #[test]
+    fn test_unlambda() {
+        let mut engine = setup();
+
+        let output = engine.run(command::unlambda(2, expr::l("x", "x"))).unwrap();
+        assert_eq!(
+            output,
+            Output::Unlambda {
+                expr: expr::l("x", "x"),
+                result: expr::v("i"),
+            }
+        );
+        assert_eq!(engine.aliases().get(&"_".into()), Some(&expr::v("i")));
+
+        let output = engine
+            .run(command::unlambda(6, expr::l("x", expr::l("y", "x"))))
+            .unwrap();
+        assert_eq!(
+            output,
+            Output::Unlambda {
+                expr: expr::l("x", expr::l("y", "x")),
+                result: expr::v("k"),
+            }
+        );
+
+        assert!(engine.run(command::unlambda(7, expr::v("x"))).is_err());
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut engine = setup();
+
+        let output = engine.run(command::metrics(expr::v("TRUE"))).unwrap();
+        let Output::Metrics(comparison) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(comparison.expr, expr::v("TRUE"));
+        assert_eq!(comparison.samples, vec![vec![expr::s("a"), expr::s("b")]]);
+        assert_eq!(comparison.reports.len(), COMPARED_STRATEGIES.len());
+        assert_eq!(comparison.reports[0].result, expr::v("k"));
+        assert_eq!(comparison.reports[0].steps, vec![Some(1)]);
+
+        // 比較は Aliases に登録しない
+        assert_eq!(engine.aliases().get(&"_".into()), None);
+    }
+
+    #[test]
+    fn test_type_of() {
+        let mut engine = setup();
+
+        let output = engine.run(command::type_of(expr::v("k"))).unwrap();
+        assert_eq!(
+            output,
+            Output::TypeOf {
+                expr: expr::v("k"),
+                ty: Type::arrow(Type::Var(0), Type::arrow(Type::Var(1), Type::Var(0))),
+            }
+        );
+
+        engine
+            .run(command::eval_last(expr::a("i", expr::a("k", "i"))))
+            .unwrap();
+        let Output::TypeOf { ty, .. } = engine.run(command::type_of(expr::v("_"))).unwrap() else {
+            panic!("unexpected output");
+        };
+        assert_eq!(ty.to_string(), "a -> b -> b");
+
+        let error = engine
+            .run(command::type_of(expr::l("x", expr::a("x", "x"))))
+            .unwrap_err();
+        assert!(error.downcast_ref::<TypeError>().is_some());
+    }
+
+    #[test]
+    fn test_eval_typed() {
+        let mut engine = setup();
+        let nat = || Type::named("Nat");
+
+        // 型を検査してから、型を取り除いて簡約する
+        let id = expr::tl("α", expr::lt("x", Type::named("α"), "x"));
+        let output = engine
+            .run(command::eval(expr::a(expr::ta(id.clone(), nat()), ":a")))
+            .unwrap();
+        let Output::Eval(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(exprs(&reduction), vec![expr::s("a")]);
+        assert!(reduction.steps.iter().all(|step| !step.expr.is_typed()));
+
+        // 型の合わない式は簡約しない
+        let error = engine
+            .run(command::eval(expr::a(expr::lt("x", nat(), "x"), id)))
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<TypeError>(),
+            Some(TypeError::Mismatch { .. })
+        ));
+        assert_eq!(engine.aliases().get(&"_".into()), Some(&expr::s("a")));
+    }
+
+    #[test]
+    fn test_aliases() {
+        let mut engine = setup();
+
+        engine.run(command::eval_last(expr::a("i", ":a"))).unwrap();
+        engine.run(command::eval_last(expr::a("i", ":b"))).unwrap();
+
+        let output = engine
+            .run(command::eval_last(expr::a(expr::a("k", "_"), "_0")))
+            .unwrap();
+        let Output::EvalLast(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(exprs(&reduction), vec![expr::s("b")]);
+    }
+
+    #[test]
+    fn test_import() {
+        let mut engine = Engine::new(Context::new(), Aliases::new());
+
+        let output = engine.run(command::import("bool")).unwrap();
+        let Output::Import { module, funcs } = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(module, "bool");
+        assert!(funcs.iter().any(|f| f.name() == "TRUE"));
+
+        let output = engine
+            .run(command::eval_last(expr::a(
+                expr::a("bool::NOT", "TRUE"),
+                ":a",
+            )))
+            .unwrap();
+        let Output::EvalLast(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(exprs(&reduction), vec![expr::l("y", "y")]);
+
+        assert!(engine.run(command::import("foo")).is_err());
+    }
+
+    #[test]
+    fn test_update_imported() {
+        let mut engine = setup();
+
+        let f = func::new("i", vec!["x"], expr::a("x", "x"));
+        assert!(engine.run(command::update(f.clone())).is_err());
+        assert_eq!(
+            engine.context().get(&"i".into()).map(|f| f.arity()),
+            Some(1)
+        );
+
+        engine.run(command::del("i")).unwrap();
+        assert_eq!(
+            engine.run(command::update(f.clone())).unwrap(),
+            Output::Update(f)
+        );
+    }
+
+    #[test]
+    fn test_data() {
+        let mut engine = setup();
+
+        let data = crate::data::new(
+            "LIST",
+            crate::data::Encoding::Scott,
+            vec![
+                ("EMPTY", vec![]),
+                (
+                    "PUSH",
+                    vec![crate::data::Field::from("h"), ("t", "LIST").into()],
+                ),
+            ],
+        );
+        let output = engine.run(command::data(data.clone())).unwrap();
+        let Output::Data { name, funcs } = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(name, "LIST".into());
+        assert_eq!(funcs, data.funcs().unwrap());
+
+        let command = crate::parser::parse_command("! CASE_LIST(PUSH(:a, EMPTY), :e, k)").unwrap();
+        let Output::EvalLast(reduction) = engine.run(command).unwrap() else {
+            panic!("unexpected output");
+        };
+        assert_eq!(exprs(&reduction), vec![expr::s("a")]);
+
+        // データ型の名前で生成した関数をまとめて検索・削除する
+        let Output::Data { funcs, .. } = engine.run(command::query("LIST")).unwrap() else {
+            panic!("unexpected output");
+        };
+        assert_eq!(funcs.len(), 4);
+
+        engine.run(command::del("LIST")).unwrap();
+        assert_eq!(
+            engine.run(command::query("PUSH")).unwrap(),
+            Output::QueryUndefined("PUSH".into())
+        );
+        assert_eq!(
+            engine.run(command::query("LIST")).unwrap(),
+            Output::QueryUndefined("LIST".into())
+        );
+
+        // import した関数は上書きしない
+        let data = crate::data::new::<_, _, &str>(
+            "BOOL",
+            crate::data::Encoding::Scott,
+            vec![("TRUE", vec![]), ("FALSE", vec![])],
+        );
+        assert!(engine.run(command::data(data)).is_err());
+        assert!(engine.context().get(&"CASE_BOOL".into()).is_none());
+    }
+
+    #[test]
+    fn test_numeral_literal() {
+        let mut engine = setup();
+
+        // 組み込みの定義にない 21 もリテラルとして書ける
+        let command = crate::parser::parse_command("! ADD(20, 1, :f, :x)").unwrap();
+        let output = engine.run(command).unwrap();
+        let Output::EvalLast(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        let mut expected = expr::s("x");
+        for _ in 0..21 {
+            expected = expr::a(":f", expected);
+        }
+        assert_eq!(exprs(&reduction), vec![expected]);
+
+        let command = crate::parser::parse_command("! k(21, :a)").unwrap();
+        let output = engine.run(command).unwrap();
+        let Output::EvalLast(reduction) = output else {
+            panic!("unexpected output: {:?}", output);
+        };
+        assert_eq!(reduction.steps[0].expr.resugar_numerals(), expr::v("21"));
+    }
+}
@@ -1,3 +1,9 @@
 pub mod command;
+mod executor;
+mod output;
+mod script;
 
 pub use command::Command;
+pub use executor::{Engine, DEFAULT_MAX_STEPS};
+pub use output::{Comparison, Output, Reduction};
+pub use script::run_script;
@@ -0,0 +1,49 @@
+use crate::calc::{ReduceResult, StrategyMetrics, Termination};
+use crate::expr::{Expr, Identifier};
+use crate::func::Func;
+use crate::types::Type;
+
+/// Engine がコマンドを実行した結果
+#[derive(Clone, Debug, PartialEq)]
+pub enum Output {
+    Del(Identifier),                             // 削除された関数の識別子
+    Update(Func),                                // 定義された関数
+    Eval(Reduction),                             // β変換列
+    EvalLast(Reduction),                         // β変換の結果のみ
+    EvalHead(Reduction),                         // β変換列の先頭
+    EvalTail(Reduction),                         // β変換列の末尾
+    QueryDefined(Func),                          // 検索された関数
+    QueryUndefined(Identifier),                  // 定義されていなかった識別子
+    Context(Vec<Func>),                          // 定義済み関数の一覧
+    Import { module: String, funcs: Vec<Func> }, // import したモジュールとその関数
+    Unlambda { expr: Expr, result: Expr },       // ラムダ抽象を除去する前後の式
+    Metrics(Comparison),                         // ラムダ抽象の除去の方法ごとの比較
+    TypeOf { expr: Expr, ty: Type },             // 式とその主要型
+    Data { name: Identifier, funcs: Vec<Func> }, // データ型とそこから生成した関数
+}
+
+/// 簡約の経過
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reduction {
+    /// 簡約前の式
+    pub expr: Expr,
+
+    /// 出力する簡約ステップ
+    pub steps: Vec<ReduceResult>,
+
+    /// 簡約列が終了した理由
+    pub termination: Termination,
+}
+
+/// ラムダ抽象の除去の方法ごとの比較
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    /// ラムダ抽象を除去する前の式
+    pub expr: Expr,
+
+    /// 結果の式に与えたサンプルの入力
+    pub samples: Vec<Vec<Expr>>,
+
+    /// 方法ごとの結果
+    pub reports: Vec<StrategyMetrics>,
+}
@@ -0,0 +1,122 @@
+use super::command::Command;
+use crate::context::{Context, ContextError};
+use crate::parser::Statement;
+
+/// スクリプトの文を順に実行する
+///
+/// 関数定義・削除・import は context に反映する
+/// それ以外の文は、その時点の context と共に on_command に渡す
+pub fn run_script<I, F>(
+    context: &mut Context,
+    statements: I,
+    mut on_command: F,
+) -> Result<(), ContextError>
+where
+    I: IntoIterator<Item = Statement>,
+    F: FnMut(&Context, Statement),
+{
+    for statement in statements {
+        match &statement.command {
+            Command::Update(func) => context.def(func.clone())?,
+            Command::Del(id) => {
+                context.del(id);
+            }
+            Command::Import(module) => context.import(module)?,
+            _ => on_command(context, statement),
+        }
+    }
+    Ok(())
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{Aliases, Reducer, ReductionStrategy};
+    use crate::expr;
+    use crate::parser::parse_script;
+
+    #[test]
+    fn test_run_script() {
+        let script = "
+i(x) = x
+k(x, y) = x
+!k(i, :a)
+k = k
+!k(i, :a)
+";
+
+        let mut context = Context::new();
+        let mut results = Vec::new();
+        run_script(
+            &mut context,
+            parse_script(script).unwrap(),
+            |context, statement| match statement.command {
+                Command::EvalLast(e) => {
+                    let reducer = Reducer::new(
+                        context.clone(),
+                        Aliases::new(),
+                        e,
+                        ReductionStrategy::NormalOrder,
+                    );
+                    results.push(reducer.last().map(|result| result.expr));
+                }
+                _ => panic!("unexpected command: {}", statement.command),
+            },
+        )
+        .unwrap();
+
+        // 2つ目の評価は k が削除された後の context で行われる
+        assert_eq!(results, vec![Some("i".into()), None]);
+
+        assert!(context.get(&"i".into()).is_some());
+        assert!(context.get(&"k".into()).is_none());
+    }
+
+    #[test]
+    fn test_run_script_order() {
+        let script = "f = :a; ? f; f = :b; ? f";
+
+        let mut context = Context::new();
+        let mut bodies = Vec::new();
+        run_script(
+            &mut context,
+            parse_script(script).unwrap(),
+            |context, statement| {
+                if let Command::Query(id) = statement.command {
+                    bodies.push(context.get(&id).map(|f| f.body().clone()));
+                }
+            },
+        )
+        .unwrap();
+
+        assert_eq!(bodies, vec![Some(expr::s("a")), Some(expr::s("b"))]);
+    }
+
+    #[test]
+    fn test_run_script_import() {
+        let script = "import bool; ? NOT; NOT(x) = x";
+
+        let mut context = Context::new();
+        let mut queries = Vec::new();
+        let result = run_script(
+            &mut context,
+            parse_script(script).unwrap(),
+            |context, statement| {
+                if let Command::Query(id) = statement.command {
+                    queries.push(context.get(&id).is_some());
+                }
+            },
+        );
+
+        assert_eq!(queries, vec![true]);
+        assert_eq!(
+            result,
+            Err(ContextError::Imported {
+                name: "NOT".into(),
+                module: "bool".to_string()
+            })
+        );
+    }
+}
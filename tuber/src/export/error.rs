@@ -0,0 +1,62 @@
+use crate::expr::Identifier;
+use std::fmt::Display;
+
+/// 式を Lazy K のプログラムとして書き出せなかった理由を表現する
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportError {
+    /// s, k, i, ι 以外の自由変数やシンボルが残っている
+    Unresolved {
+        variables: Vec<Identifier>,
+        symbols: Vec<Identifier>,
+    },
+    /// ラムダ抽象が残っている
+    Lambda,
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Unresolved { variables, symbols } => {
+                let names: Vec<String> = variables
+                    .iter()
+                    .map(|id| format!("`{}`", id))
+                    .chain(symbols.iter().map(|id| format!("`:{}`", id)))
+                    .collect();
+                write!(
+                    f,
+                    "cannot export: only s, k, i and ι may appear, but found {}",
+                    names.join(", ")
+                )
+            }
+            ExportError::Lambda => write!(
+                f,
+                "cannot export: the expression still contains a lambda abstraction"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ExportError::Unresolved {
+                variables: vec!["TRUE".into(), "x".into()],
+                symbols: vec!["a".into()],
+            }
+            .to_string(),
+            "cannot export: only s, k, i and ι may appear, but found `TRUE`, `x`, `:a`"
+        );
+        assert_eq!(
+            ExportError::Lambda.to_string(),
+            "cannot export: the expression still contains a lambda abstraction"
+        );
+    }
+}
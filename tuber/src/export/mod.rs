@@ -0,0 +1,347 @@
+mod error;
+
+use crate::expr::{Expr, FreeVars, Identifier};
+pub use error::ExportError;
+
+/// Lazy K が受け付ける4つの構文
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LazyKSyntax {
+    /// `S`, `K`, `I` と括弧による適用 (`SII(SII)`)
+    Combinator,
+    /// `s`, `k`, `i` とバッククォートによる適用 (`` ``sii``sii ``)
+    Unlambda,
+    /// `i` (ι) と `*` による適用 (`*ii`)
+    Iota,
+    /// `0` と `1` の列
+    Jot,
+}
+
+/// s, k, i, ι だけからなる式を Lazy K のプログラムとして書き出す
+///
+/// s, k, i, ι は Context での定義に関わらず、それぞれ S, K, I, ι コンビネータとして扱う
+pub fn export(expr: &Expr, syntax: LazyKSyntax) -> Result<String, ExportError> {
+    let combinator = Combinator::try_from(expr)?;
+    let program = match syntax {
+        LazyKSyntax::Combinator => combinator.to_combinator(),
+        LazyKSyntax::Unlambda => combinator.to_unlambda(),
+        LazyKSyntax::Iota => combinator.to_iota(),
+        LazyKSyntax::Jot => combinator.to_jot(),
+    };
+    Ok(program)
+}
+
+// ========================================================================== //
+
+// 書き出しの対象となる式
+enum Combinator {
+    S,
+    K,
+    I,
+    Iota,
+    Apply(Box<Combinator>, Box<Combinator>),
+}
+
+impl TryFrom<&Expr> for Combinator {
+    type Error = ExportError;
+
+    fn try_from(expr: &Expr) -> Result<Self, Self::Error> {
+        let mut variables: Vec<Identifier> = FreeVars::from(expr)
+            .0
+            .into_iter()
+            .filter(|id| !["s", "k", "i", "ι"].contains(id))
+            .map(Identifier::from)
+            .collect();
+        variables.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        let mut symbols = Vec::new();
+        collect_symbols(expr, &mut symbols);
+
+        if !variables.is_empty() || !symbols.is_empty() {
+            return Err(ExportError::Unresolved { variables, symbols });
+        }
+
+        from_expr(expr)
+    }
+}
+
+fn collect_symbols(expr: &Expr, symbols: &mut Vec<Identifier>) {
+    match expr {
+        Expr::Variable(_) => {}
+        Expr::Symbol(id) => {
+            if !symbols.contains(id) {
+                symbols.push(id.clone());
+            }
+        }
+        Expr::Apply { lhs, rhs } => {
+            collect_symbols(lhs, symbols);
+            collect_symbols(rhs, symbols);
+        }
+        Expr::Lambda { body, .. }
+        | Expr::TypeLambda { body, .. }
+        | Expr::TypeApply { lhs: body, .. } => collect_symbols(body, symbols),
+        Expr::Let { value, body, .. } => {
+            collect_symbols(value, symbols);
+            collect_symbols(body, symbols);
+        }
+    }
+}
+
+fn from_expr(expr: &Expr) -> Result<Combinator, ExportError> {
+    match expr {
+        Expr::Variable(id) => match id.as_str() {
+            "s" => Ok(Combinator::S),
+            "k" => Ok(Combinator::K),
+            "i" => Ok(Combinator::I),
+            "ι" => Ok(Combinator::Iota),
+            _ => unreachable!("free variables are checked in advance"),
+        },
+        Expr::Apply { lhs, rhs } => Ok(Combinator::Apply(
+            Box::new(from_expr(lhs)?),
+            Box::new(from_expr(rhs)?),
+        )),
+        Expr::Lambda { .. } | Expr::Let { .. } => Err(ExportError::Lambda),
+        // 型は取り除く
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => from_expr(body),
+        Expr::Symbol(_) => unreachable!("symbols are checked in advance"),
+    }
+}
+
+impl Combinator {
+    fn apply(lhs: Combinator, rhs: Combinator) -> Combinator {
+        Combinator::Apply(Box::new(lhs), Box::new(rhs))
+    }
+
+    // ι = S(S I (K S))(K K)
+    fn iota() -> Combinator {
+        use Combinator::*;
+        Combinator::apply(
+            Combinator::apply(
+                S,
+                Combinator::apply(Combinator::apply(S, I), Combinator::apply(K, S)),
+            ),
+            Combinator::apply(K, K),
+        )
+    }
+
+    fn to_combinator(&self) -> String {
+        match self {
+            Combinator::S => "S".to_string(),
+            Combinator::K => "K".to_string(),
+            Combinator::I => "I".to_string(),
+            Combinator::Iota => Combinator::iota().to_combinator(),
+            Combinator::Apply(lhs, rhs) => match rhs.as_ref() {
+                Combinator::Apply(..) | Combinator::Iota => {
+                    format!("{}({})", lhs.to_combinator(), rhs.to_combinator())
+                }
+                _ => format!("{}{}", lhs.to_combinator(), rhs.to_combinator()),
+            },
+        }
+    }
+
+    fn to_unlambda(&self) -> String {
+        match self {
+            Combinator::S => "s".to_string(),
+            Combinator::K => "k".to_string(),
+            Combinator::I => "i".to_string(),
+            Combinator::Iota => Combinator::iota().to_unlambda(),
+            Combinator::Apply(lhs, rhs) => format!("`{}{}", lhs.to_unlambda(), rhs.to_unlambda()),
+        }
+    }
+
+    fn to_iota(&self) -> String {
+        match self {
+            // S = ι(ι(ι(ιι)))
+            Combinator::S => "*i*i*i*ii".to_string(),
+            // K = ι(ι(ιι))
+            Combinator::K => "*i*i*ii".to_string(),
+            // I = ιι
+            Combinator::I => "*ii".to_string(),
+            Combinator::Iota => "i".to_string(),
+            Combinator::Apply(lhs, rhs) => format!("*{}{}", lhs.to_iota(), rhs.to_iota()),
+        }
+    }
+
+    // [F0] = [F] S K, [F1] = λxy.[F](xy) なので、1 [F] [G] が F G になる
+    fn to_jot(&self) -> String {
+        match self {
+            Combinator::S => "11111000".to_string(),
+            Combinator::K => "11100".to_string(),
+            // I = S K K
+            Combinator::I => Combinator::apply(
+                Combinator::apply(Combinator::S, Combinator::K),
+                Combinator::K,
+            )
+            .to_jot(),
+            Combinator::Iota => Combinator::iota().to_jot(),
+            Combinator::Apply(lhs, rhs) => format!("1{}{}", lhs.to_jot(), rhs.to_jot()),
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{expand, Aliases, Reducer, ReductionStrategy};
+    use crate::context::Context;
+    use crate::expr;
+    use crate::parser::parse_expr;
+
+    // 書き出したプログラムを読み戻す (s, k, i, ι の変数として)
+    fn read(program: &str, syntax: LazyKSyntax) -> Expr {
+        let mut chars = program.chars().peekable();
+        let expr = match syntax {
+            LazyKSyntax::Combinator => read_combinator(&mut chars),
+            LazyKSyntax::Unlambda => read_unlambda(&mut chars),
+            LazyKSyntax::Iota => read_iota(&mut chars),
+            LazyKSyntax::Jot => read_jot(&mut chars),
+        };
+        assert_eq!(chars.next(), None, "{}", program);
+        expr
+    }
+
+    type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+    fn read_combinator(chars: &mut Chars) -> Expr {
+        let mut expr: Option<Expr> = None;
+        while let Some(&c) = chars.peek() {
+            let term = match c {
+                'S' | 'K' | 'I' => {
+                    chars.next();
+                    expr::v(c.to_ascii_lowercase().to_string().as_str())
+                }
+                '(' => {
+                    chars.next();
+                    let term = read_combinator(chars);
+                    assert_eq!(chars.next(), Some(')'));
+                    term
+                }
+                _ => break,
+            };
+            expr = Some(match expr {
+                Some(lhs) => expr::a(lhs, term),
+                None => term,
+            });
+        }
+        expr.unwrap()
+    }
+
+    fn read_unlambda(chars: &mut Chars) -> Expr {
+        match chars.next().unwrap() {
+            '`' => {
+                let lhs = read_unlambda(chars);
+                expr::a(lhs, read_unlambda(chars))
+            }
+            c => expr::v(c.to_string().as_str()),
+        }
+    }
+
+    fn read_iota(chars: &mut Chars) -> Expr {
+        match chars.next().unwrap() {
+            '*' => {
+                let lhs = read_iota(chars);
+                expr::a(lhs, read_iota(chars))
+            }
+            'i' => expr::v("ι"),
+            c => panic!("unexpected {}", c),
+        }
+    }
+
+    fn read_jot(chars: &mut Chars) -> Expr {
+        chars.fold(expr::v("i"), |e, c| match c {
+            '0' => expr::a(expr::a(e, "s"), "k"),
+            '1' => expr::a("s", expr::a("k", e)),
+            c => panic!("unexpected {}", c),
+        })
+    }
+
+    // 引数を与えて正規形まで簡約し、残ったコンビネータを展開する
+    fn reduce(expr: Expr, args: &[&str]) -> Expr {
+        let expr = args.iter().fold(expr, |e, arg| expr::a(e, expr::s(*arg)));
+        let mut reducer = Reducer::new(
+            Context::default(),
+            Aliases::new(),
+            expr,
+            ReductionStrategy::NormalOrder,
+        );
+        reducer.set_max_steps(Some(10000));
+        reducer.by_ref().for_each(drop);
+        expand(&Context::default(), reducer.expr())
+    }
+
+    const SYNTAXES: [LazyKSyntax; 4] = [
+        LazyKSyntax::Combinator,
+        LazyKSyntax::Unlambda,
+        LazyKSyntax::Iota,
+        LazyKSyntax::Jot,
+    ];
+
+    #[test]
+    fn test_export() {
+        let expr = parse_expr("s(i, i, s(i, i))").unwrap();
+        assert_eq!(
+            export(&expr, LazyKSyntax::Combinator),
+            Ok("SII(SII)".to_string())
+        );
+        assert_eq!(
+            export(&expr, LazyKSyntax::Unlambda),
+            Ok("```sii``sii".to_string())
+        );
+
+        let expr = parse_expr("k(i)").unwrap();
+        assert_eq!(
+            export(&expr, LazyKSyntax::Iota),
+            Ok("**i*i*ii*ii".to_string())
+        );
+        assert_eq!(
+            export(&expr, LazyKSyntax::Jot),
+            Ok(["1", "11100", "11", "11111000", "11100", "11100"].concat())
+        );
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let cases: [(&str, &[&str]); 5] = [
+            ("s", &["a", "b", "c"]),
+            ("k", &["a", "b"]),
+            ("i", &["a"]),
+            ("ι", &["a"]),
+            ("s(k(s(i)), k, ι(ι), s(k, k))", &["a", "b"]),
+        ];
+
+        for (source, args) in cases {
+            let expr = parse_expr(source).unwrap();
+            for syntax in SYNTAXES {
+                let program = export(&expr, syntax).unwrap();
+                assert_eq!(
+                    reduce(read(&program, syntax), args),
+                    reduce(expr.clone(), args),
+                    "{} {:?}",
+                    source,
+                    syntax
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unresolved() {
+        let expr = parse_expr("s(TRUE, :a, x => x(y), :a, k)").unwrap();
+        for syntax in SYNTAXES {
+            assert_eq!(
+                export(&expr, syntax),
+                Err(ExportError::Unresolved {
+                    variables: vec!["TRUE".into(), "y".into()],
+                    symbols: vec!["a".into()],
+                })
+            );
+        }
+
+        let expr = parse_expr("s(x => x)").unwrap();
+        assert_eq!(
+            export(&expr, LazyKSyntax::Combinator),
+            Err(ExportError::Lambda)
+        );
+    }
+}
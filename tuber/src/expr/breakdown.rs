@@ -17,7 +17,10 @@ impl Expr {
         let mut params: Vec<&Identifier> = Vec::new();
         let mut body: &Expr = self;
 
-        while let Expr::Lambda { param, body: next } = body {
+        while let Expr::Lambda {
+            param, body: next, ..
+        } = body
+        {
             params.push(param);
             body = next;
         }
@@ -0,0 +1,152 @@
+use super::expression::{a, l, ta, tl, v};
+use super::Expr;
+
+/// 自然数 n を Church 数 `f => x => f(…f(x)…)` にする
+pub fn church_numeral(n: usize) -> Expr {
+    let mut body = v("x");
+    for _ in 0..n {
+        body = a("f", body);
+    }
+    l("f", l("x", body))
+}
+
+/// 文字列を、各文字のコードポイントを Church 数で表したリストにする
+///
+/// リストは Scott エンコーディングで表す
+/// 空リストは `n => c => n`, 先頭 h と残り t からなるリストは `n => c => c(h, t)`
+pub fn scott_string(s: &str) -> Expr {
+    let mut list = l("n", l("c", "n"));
+    for c in s.chars().rev() {
+        let head = church_numeral(c as usize);
+        list = l("n", l("c", a(a("c", head), list)));
+    }
+    list
+}
+
+impl Expr {
+    /// Church 数であれば、その値を返す
+    pub fn as_church_numeral(&self) -> Option<usize> {
+        let Expr::Lambda { param: f, body, .. } = self else {
+            return None;
+        };
+        let Expr::Lambda { param: x, body, .. } = body.as_ref() else {
+            return None;
+        };
+        if f == x {
+            return None;
+        }
+
+        let mut n = 0;
+        let mut body = body.as_ref();
+        while let Expr::Apply { lhs, rhs } = body {
+            if !matches!(lhs.as_ref(), Expr::Variable(id) if id == f) {
+                return None;
+            }
+            n += 1;
+            body = rhs;
+        }
+
+        match body {
+            Expr::Variable(id) if id == x => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Church 数と認識できる部分式を、数字を名前とする変数に置き換える
+    ///
+    /// 簡約の経過を表示するためのもので、置き換えた後の式を簡約してはいけない
+    pub fn resugar_numerals(&self) -> Expr {
+        if let Some(n) = self.as_church_numeral() {
+            return v(n.to_string());
+        }
+
+        match self {
+            Expr::Variable(_) | Expr::Symbol(_) => self.clone(),
+            Expr::Apply { lhs, rhs } => a(lhs.resugar_numerals(), rhs.resugar_numerals()),
+            Expr::Lambda { param, ty, body } => Expr::Lambda {
+                param: param.clone(),
+                ty: ty.clone(),
+                body: Box::new(body.resugar_numerals()),
+            },
+            Expr::TypeLambda { param, body } => tl(param.clone(), body.resugar_numerals()),
+            Expr::TypeApply { lhs, ty } => ta(lhs.resugar_numerals(), (**ty).clone()),
+            Expr::Let {
+                recursive,
+                name,
+                value,
+                body,
+            } => Expr::Let {
+                recursive: *recursive,
+                name: name.clone(),
+                value: Box::new(value.resugar_numerals()),
+                body: Box::new(body.resugar_numerals()),
+            },
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_church_numeral() {
+        assert_eq!(church_numeral(0), expr::l("f", expr::l("x", "x")));
+        assert_eq!(
+            church_numeral(2),
+            expr::l("f", expr::l("x", expr::a("f", expr::a("f", "x"))))
+        );
+    }
+
+    #[test]
+    fn test_scott_string() {
+        assert_eq!(scott_string(""), expr::l("n", expr::l("c", "n")));
+        assert_eq!(
+            scott_string("a"),
+            expr::l(
+                "n",
+                expr::l(
+                    "c",
+                    expr::a(
+                        expr::a("c", church_numeral(97)),
+                        expr::l("n", expr::l("c", "n"))
+                    )
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_as_church_numeral() {
+        for n in [0, 1, 2, 42] {
+            assert_eq!(church_numeral(n).as_church_numeral(), Some(n));
+        }
+
+        assert_eq!(
+            expr::l("g", expr::l("y", expr::a("g", "y"))).as_church_numeral(),
+            Some(1)
+        );
+        assert_eq!(expr::l("x", expr::l("x", "x")).as_church_numeral(), None);
+        assert_eq!(expr::l("x", expr::l("y", "x")).as_church_numeral(), None);
+        assert_eq!(
+            expr::l("f", expr::l("x", expr::a("x", "f"))).as_church_numeral(),
+            None
+        );
+        assert_eq!(expr::v("f").as_church_numeral(), None);
+    }
+
+    #[test]
+    fn test_resugar_numerals() {
+        assert_eq!(
+            expr::a(church_numeral(3), expr::a(":f", church_numeral(0))).resugar_numerals(),
+            expr::a("3", expr::a(":f", "0"))
+        );
+        assert_eq!(
+            expr::l("x", expr::l("y", "x")).resugar_numerals(),
+            expr::l("x", expr::l("y", "x"))
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use super::{Expr, Identifier};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// de Bruijn インデックスによる式の表現
+///
+/// 束縛変数を名前ではなく、何番目に内側のラムダ抽象 (または let 式) に束縛されているか (0 始まり) で表す
+/// α同値な式は同じ表現になる。型注釈、型抽象、型適用は取り除く
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DeBruijn {
+    /// 束縛変数
+    Bound(usize),
+
+    /// 自由変数
+    Free(Identifier),
+
+    /// シンボル
+    Symbol(Identifier),
+
+    /// 適用
+    Apply {
+        lhs: Box<DeBruijn>,
+        rhs: Box<DeBruijn>,
+    },
+
+    /// ラムダ抽象
+    Lambda { body: Box<DeBruijn> },
+
+    /// let 式 (recursive なら value の中でも束縛される)
+    Let {
+        recursive: bool,
+        value: Box<DeBruijn>,
+        body: Box<DeBruijn>,
+    },
+}
+
+impl DeBruijn {
+    /// 名前付きの式に戻す
+    ///
+    /// 束縛変数の名前は、束縛の深さと自由変数を元に衝突しないように選ぶ
+    pub fn to_expr(&self) -> Expr {
+        let mut free_vars = HashSet::new();
+        self.free_vars(&mut free_vars);
+
+        let names = names(&free_vars);
+        let mut bound = Vec::new();
+        self.to_expr_(&names, &mut bound)
+    }
+
+    fn to_expr_(&self, names: &Names, bound: &mut Vec<Identifier>) -> Expr {
+        match self {
+            DeBruijn::Bound(index) => Expr::Variable(bound[bound.len() - 1 - index].clone()),
+            DeBruijn::Free(id) => Expr::Variable(id.clone()),
+            DeBruijn::Symbol(id) => Expr::Symbol(id.clone()),
+            DeBruijn::Apply { lhs, rhs } => Expr::Apply {
+                lhs: Box::new(lhs.to_expr_(names, bound)),
+                rhs: Box::new(rhs.to_expr_(names, bound)),
+            },
+            DeBruijn::Lambda { body } => {
+                let param = names.get(bound.len());
+                bound.push(param.clone());
+                let body = body.to_expr_(names, bound);
+                bound.pop();
+                Expr::Lambda {
+                    param,
+                    ty: None,
+                    body: Box::new(body),
+                }
+            }
+            DeBruijn::Let {
+                recursive,
+                value,
+                body,
+            } => {
+                let name = names.get(bound.len());
+                let value = if *recursive {
+                    bound.push(name.clone());
+                    let value = value.to_expr_(names, bound);
+                    bound.pop();
+                    value
+                } else {
+                    value.to_expr_(names, bound)
+                };
+                bound.push(name.clone());
+                let body = body.to_expr_(names, bound);
+                bound.pop();
+                Expr::Let {
+                    recursive: *recursive,
+                    name,
+                    value: Box::new(value),
+                    body: Box::new(body),
+                }
+            }
+        }
+    }
+
+    fn free_vars<'a>(&'a self, vars: &mut HashSet<&'a str>) {
+        match self {
+            DeBruijn::Free(id) => {
+                vars.insert(id.as_str());
+            }
+            DeBruijn::Bound(_) | DeBruijn::Symbol(_) => {}
+            DeBruijn::Apply { lhs, rhs } => {
+                lhs.free_vars(vars);
+                rhs.free_vars(vars);
+            }
+            DeBruijn::Lambda { body } => body.free_vars(vars),
+            DeBruijn::Let { value, body, .. } => {
+                value.free_vars(vars);
+                body.free_vars(vars);
+            }
+        }
+    }
+}
+
+impl From<&Expr> for DeBruijn {
+    fn from(expr: &Expr) -> Self {
+        from_expr(expr, &mut Vec::new())
+    }
+}
+
+impl From<Expr> for DeBruijn {
+    fn from(expr: Expr) -> Self {
+        DeBruijn::from(&expr)
+    }
+}
+
+impl From<&DeBruijn> for Expr {
+    fn from(de_bruijn: &DeBruijn) -> Self {
+        de_bruijn.to_expr()
+    }
+}
+
+impl From<DeBruijn> for Expr {
+    fn from(de_bruijn: DeBruijn) -> Self {
+        de_bruijn.to_expr()
+    }
+}
+
+fn from_expr<'a>(expr: &'a Expr, bound: &mut Vec<&'a Identifier>) -> DeBruijn {
+    match expr {
+        Expr::Variable(id) => match bound.iter().rev().position(|param| *param == id) {
+            Some(index) => DeBruijn::Bound(index),
+            None => DeBruijn::Free(id.clone()),
+        },
+        Expr::Symbol(id) => DeBruijn::Symbol(id.clone()),
+        Expr::Apply { lhs, rhs } => DeBruijn::Apply {
+            lhs: Box::new(from_expr(lhs, bound)),
+            rhs: Box::new(from_expr(rhs, bound)),
+        },
+        Expr::Lambda { param, body, .. } => {
+            bound.push(param);
+            let body = from_expr(body, bound);
+            bound.pop();
+            DeBruijn::Lambda {
+                body: Box::new(body),
+            }
+        }
+        Expr::TypeLambda { body, .. } => from_expr(body, bound),
+        Expr::TypeApply { lhs, .. } => from_expr(lhs, bound),
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            let value = if *recursive {
+                bound.push(name);
+                let value = from_expr(value, bound);
+                bound.pop();
+                value
+            } else {
+                from_expr(value, bound)
+            };
+            bound.push(name);
+            let body = from_expr(body, bound);
+            bound.pop();
+            DeBruijn::Let {
+                recursive: *recursive,
+                value: Box::new(value),
+                body: Box::new(body),
+            }
+        }
+    }
+}
+
+// ========================================================================== //
+
+impl Expr {
+    /// α同値 (束縛変数の名前の付け替えを除いて等しい) かどうかを判定する
+    pub fn alpha_eq(&self, other: &Expr) -> bool {
+        DeBruijn::from(self) == DeBruijn::from(other)
+    }
+
+    /// α同値な式に対して同じ値を返すハッシュ値
+    pub fn alpha_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        DeBruijn::from(self).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// ========================================================================== //
+
+// 束縛の深さごとの束縛変数の名前
+struct Names(Vec<Identifier>);
+
+impl Names {
+    fn get(&self, depth: usize) -> Identifier {
+        match self.0.get(depth) {
+            Some(name) => name.clone(),
+            None => format!("X{}", depth - self.0.len()).into(),
+        }
+    }
+}
+
+fn names(free_vars: &HashSet<&str>) -> Names {
+    let mut buffer = [0; 4];
+    let names = "xyzwvutsrqponmlkjihgfedcba"
+        .chars()
+        .map(|c| c.encode_utf8(&mut buffer).to_string())
+        .filter(|name| !free_vars.contains(name.as_str()))
+        .map(Identifier::from)
+        .collect();
+    Names(names)
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_from_expr() {
+        // ^x.^y.`x`yz => λ.λ.`1`0z
+        let e = expr::l("x", expr::l("y", expr::a("x", expr::a("y", "z"))));
+        assert_eq!(
+            DeBruijn::from(&e),
+            DeBruijn::Lambda {
+                body: Box::new(DeBruijn::Lambda {
+                    body: Box::new(DeBruijn::Apply {
+                        lhs: Box::new(DeBruijn::Bound(1)),
+                        rhs: Box::new(DeBruijn::Apply {
+                            lhs: Box::new(DeBruijn::Bound(0)),
+                            rhs: Box::new(DeBruijn::Free("z".into())),
+                        }),
+                    }),
+                }),
+            }
+        );
+
+        // ^x.^x.x => λ.λ.0
+        let e = expr::l("x", expr::l("x", "x"));
+        assert_eq!(
+            DeBruijn::from(&e),
+            DeBruijn::Lambda {
+                body: Box::new(DeBruijn::Lambda {
+                    body: Box::new(DeBruijn::Bound(0)),
+                }),
+            }
+        );
+
+        // `^x.x:x => `λ.0:x
+        let e = expr::a(expr::l("x", "x"), ":x");
+        assert_eq!(
+            DeBruijn::from(&e),
+            DeBruijn::Apply {
+                lhs: Box::new(DeBruijn::Lambda {
+                    body: Box::new(DeBruijn::Bound(0)),
+                }),
+                rhs: Box::new(DeBruijn::Symbol("x".into())),
+            }
+        );
+    }
+
+    #[test]
+    fn test_to_expr() {
+        // λ.λ.`1`0z => ^x.^y.`x`yz
+        let e = expr::l("a", expr::l("b", expr::a("a", expr::a("b", "z"))));
+        assert_eq!(
+            DeBruijn::from(&e).to_expr(),
+            expr::l("x", expr::l("y", expr::a("x", expr::a("y", "z"))))
+        );
+
+        // 自由変数と衝突する名前は避ける
+        let e = expr::l("a", expr::a("a", "x"));
+        assert_eq!(
+            DeBruijn::from(&e).to_expr(),
+            expr::l("y", expr::a("y", "x"))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let exprs = [
+            expr::l("x", expr::l("y", expr::a("y", "x"))),
+            expr::a(expr::l("Y", expr::l("Y0", "Y")), expr::l("x", ":x")),
+            expr::l("f", expr::a(expr::l("x", expr::a("f", "x")), "g")),
+        ];
+
+        for e in exprs {
+            let round_trip = DeBruijn::from(&e).to_expr();
+            assert!(round_trip.alpha_eq(&e));
+            assert_eq!(DeBruijn::from(&round_trip), DeBruijn::from(&e));
+        }
+    }
+
+    #[test]
+    fn test_alpha_eq() {
+        let e1 = expr::l("x", expr::l("y", expr::a(expr::a("x", "y"), "z")));
+        let e2 = expr::l("a", expr::l("b", expr::a(expr::a("a", "b"), "z")));
+        let e3 = expr::l("a", expr::l("b", expr::a(expr::a("b", "a"), "z")));
+        let e4 = expr::l("x", expr::l("y", expr::a(expr::a("x", "y"), "w")));
+
+        assert!(e1.alpha_eq(&e2));
+        assert!(!e1.alpha_eq(&e3));
+        assert!(!e1.alpha_eq(&e4));
+
+        // シンボルと自由変数は区別される
+        assert!(!expr::v("x").alpha_eq(&expr::s("x")));
+    }
+
+    #[test]
+    fn test_alpha_hash() {
+        let e1 = expr::l("x", expr::l("y", expr::a(expr::a("x", "y"), "z")));
+        let e2 = expr::l("a", expr::l("b", expr::a(expr::a("a", "b"), "z")));
+
+        assert_eq!(e1.alpha_hash(), e2.alpha_hash());
+    }
+}
@@ -0,0 +1,94 @@
+use super::{a, l, Expr};
+
+impl Expr {
+    /// let 式を含むかどうか
+    pub fn has_let(&self) -> bool {
+        match self {
+            Expr::Variable(_) | Expr::Symbol(_) => false,
+            Expr::Apply { lhs, rhs } => lhs.has_let() || rhs.has_let(),
+            Expr::Lambda { body, .. } | Expr::TypeLambda { body, .. } => body.has_let(),
+            Expr::TypeApply { lhs, .. } => lhs.has_let(),
+            Expr::Let { .. } => true,
+        }
+    }
+
+    /// let 式をラムダ抽象の適用に書き換える
+    ///
+    /// `let x = M in N` は `(x => N)(M)` に、`letrec f = M in N` は不動点コンビネータ Y を使って
+    /// `(f => N)(Y(f => M))` になる。Y は Context に依存しないようラムダ式のまま埋め込む
+    pub fn desugar_let(&mut self) {
+        match self {
+            Expr::Variable(_) | Expr::Symbol(_) => {}
+            Expr::Apply { lhs, rhs } => {
+                lhs.desugar_let();
+                rhs.desugar_let();
+            }
+            Expr::Lambda { body, .. } | Expr::TypeLambda { body, .. } => body.desugar_let(),
+            Expr::TypeApply { lhs, .. } => lhs.desugar_let(),
+            Expr::Let {
+                recursive,
+                name,
+                value,
+                body,
+            } => {
+                value.desugar_let();
+                body.desugar_let();
+
+                let value = std::mem::replace(value.as_mut(), Expr::Variable("_".into()));
+                let body = std::mem::replace(body.as_mut(), Expr::Variable("_".into()));
+                let value = if *recursive {
+                    a(fix(), l(name.clone(), value))
+                } else {
+                    value
+                };
+                *self = a(l(name.clone(), body), value);
+            }
+        }
+    }
+}
+
+// Y = f => (x => f(x(x)))(x => f(x(x)))
+fn fix() -> Expr {
+    let half = l("x", a("f", a("x", "x")));
+    l("f", a(half.clone(), half))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_desugar_let() {
+        // let x = :a in f(x) => (x => f(x))(:a)
+        let mut e = expr::let_("x", ":a", expr::a("f", "x"));
+        assert!(e.has_let());
+        e.desugar_let();
+        assert_eq!(e, expr::a(expr::l("x", expr::a("f", "x")), ":a"));
+        assert!(!e.has_let());
+
+        // letrec f = x => f(x) in f => (f => f)(Y(f => x => f(x)))
+        let mut e = expr::letrec("f", expr::l("x", expr::a("f", "x")), "f");
+        e.desugar_let();
+        assert_eq!(
+            e,
+            expr::a(
+                expr::l("f", "f"),
+                expr::a(fix(), expr::l("f", expr::l("x", expr::a("f", "x"))))
+            )
+        );
+
+        // 内側の let 式も書き換える
+        let mut e = expr::l("y", expr::let_("x", "y", expr::let_("z", "x", "z")));
+        e.desugar_let();
+        assert_eq!(
+            e,
+            expr::l(
+                "y",
+                expr::a(expr::l("x", expr::a(expr::l("z", "z"), "x")), "y")
+            )
+        );
+    }
+}
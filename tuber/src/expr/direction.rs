@@ -0,0 +1,14 @@
+/// 部分式をたどる1歩
+///
+/// 根からの Direction の並びで、ラムダ抽象の本体の中も含めた任意の部分式の位置を表す
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// 関数適用の関数側
+    Lhs,
+    /// 関数適用の引数側
+    Rhs,
+    /// ラムダ抽象や let 式の本体
+    Body,
+    /// let 式で束縛する値
+    Value,
+}
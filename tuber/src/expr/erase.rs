@@ -0,0 +1,72 @@
+use super::Expr;
+
+impl Expr {
+    /// 型注釈、型抽象、型適用のいずれかを含むかどうか
+    pub fn is_typed(&self) -> bool {
+        match self {
+            Expr::Variable(_) | Expr::Symbol(_) => false,
+            Expr::Apply { lhs, rhs } => lhs.is_typed() || rhs.is_typed(),
+            Expr::Lambda { ty, body, .. } => ty.is_some() || body.is_typed(),
+            Expr::TypeLambda { .. } | Expr::TypeApply { .. } => true,
+            Expr::Let { value, body, .. } => value.is_typed() || body.is_typed(),
+        }
+    }
+
+    /// 型注釈、型抽象、型適用を取り除き、型のない式にする
+    ///
+    /// 型と変数の名前は別々なので、取り除いても変数の束縛は変わらない
+    pub fn erase_types(&mut self) {
+        match self {
+            Expr::Variable(_) | Expr::Symbol(_) => {}
+            Expr::Apply { lhs, rhs } => {
+                lhs.erase_types();
+                rhs.erase_types();
+            }
+            Expr::Lambda { ty, body, .. } => {
+                *ty = None;
+                body.erase_types();
+            }
+            Expr::TypeLambda { body: inner, .. } | Expr::TypeApply { lhs: inner, .. } => {
+                inner.erase_types();
+                *self = std::mem::replace(inner.as_mut(), Expr::Variable("_".into()));
+            }
+            Expr::Let { value, body, .. } => {
+                value.erase_types();
+                body.erase_types();
+            }
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use crate::expr;
+    use crate::types::Type;
+
+    #[test]
+    fn test_erase_types() {
+        let nat = || Type::named("Nat");
+        let alpha = || Type::named("α");
+
+        // Λα. λx:α. λy:α -> Nat. `yx
+        let mut e = expr::tl(
+            "α",
+            expr::lt(
+                "x",
+                alpha(),
+                expr::lt("y", Type::arrow(alpha(), nat()), expr::a("y", "x")),
+            ),
+        );
+        assert!(e.is_typed());
+        e.erase_types();
+        assert_eq!(e, expr::l("x", expr::l("y", expr::a("y", "x"))));
+        assert!(!e.is_typed());
+
+        // `f[Nat] x
+        let mut e = expr::a(expr::ta("f", nat()), "x");
+        e.erase_types();
+        assert_eq!(e, expr::a("f", "x"));
+    }
+}
@@ -1,6 +1,8 @@
 use super::Identifier;
+use crate::types::Type;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Expr {
     /// 変数
     Variable(Identifier),
@@ -11,8 +13,29 @@ pub enum Expr {
     /// 適用
     Apply { lhs: Box<Expr>, rhs: Box<Expr> },
 
-    /// ラムダ抽象
-    Lambda { param: Identifier, body: Box<Expr> },
+    /// ラムダ抽象 (ty は引数の型注釈)
+    Lambda {
+        param: Identifier,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ty: Option<Box<Type>>,
+        body: Box<Expr>,
+    },
+
+    /// 型抽象
+    TypeLambda { param: Identifier, body: Box<Expr> },
+
+    /// 型適用
+    TypeApply { lhs: Box<Expr>, ty: Box<Type> },
+
+    /// 局所的な定義 (let name = value in body)
+    ///
+    /// recursive が真なら value の中でも name が束縛される (letrec)
+    Let {
+        recursive: bool,
+        name: Identifier,
+        value: Box<Expr>,
+        body: Box<Expr>,
+    },
 }
 
 impl From<&str> for Expr {
@@ -49,6 +72,52 @@ pub fn a<L: Into<Expr>, R: Into<Expr>>(lhs: L, rhs: R) -> Expr {
 pub fn l<P: Into<Identifier>, B: Into<Expr>>(param: P, body: B) -> Expr {
     Expr::Lambda {
         param: param.into(),
+        ty: None,
+        body: Box::new(body.into()),
+    }
+}
+
+/// 引数に型注釈の付いたラムダ抽象
+pub fn lt<P: Into<Identifier>, B: Into<Expr>>(param: P, ty: Type, body: B) -> Expr {
+    Expr::Lambda {
+        param: param.into(),
+        ty: Some(Box::new(ty)),
+        body: Box::new(body.into()),
+    }
+}
+
+pub fn tl<P: Into<Identifier>, B: Into<Expr>>(param: P, body: B) -> Expr {
+    Expr::TypeLambda {
+        param: param.into(),
+        body: Box::new(body.into()),
+    }
+}
+
+pub fn ta<L: Into<Expr>>(lhs: L, ty: Type) -> Expr {
+    Expr::TypeApply {
+        lhs: Box::new(lhs.into()),
+        ty: Box::new(ty),
+    }
+}
+
+pub fn let_<N: Into<Identifier>, V: Into<Expr>, B: Into<Expr>>(name: N, value: V, body: B) -> Expr {
+    Expr::Let {
+        recursive: false,
+        name: name.into(),
+        value: Box::new(value.into()),
+        body: Box::new(body.into()),
+    }
+}
+
+pub fn letrec<N: Into<Identifier>, V: Into<Expr>, B: Into<Expr>>(
+    name: N,
+    value: V,
+    body: B,
+) -> Expr {
+    Expr::Let {
+        recursive: true,
+        name: name.into(),
+        value: Box::new(value.into()),
         body: Box::new(body.into()),
     }
 }
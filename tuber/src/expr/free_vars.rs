@@ -31,7 +31,7 @@ fn free_vars<'a>(expr: &'a Expr, vars: &mut HashSet<&'a str>) {
             free_vars(rhs.as_ref(), vars);
         }
 
-        Expr::Lambda { param, body } => {
+        Expr::Lambda { param, body, .. } => {
             let mut body_vars: HashSet<&'a str> = HashSet::new();
             free_vars(body.as_ref(), &mut body_vars);
 
@@ -41,6 +41,33 @@ fn free_vars<'a>(expr: &'a Expr, vars: &mut HashSet<&'a str>) {
                 }
             }
         }
+
+        // 型と変数の名前は別々なので、型抽象は変数を束縛しない
+        Expr::TypeLambda { body, .. } => free_vars(body.as_ref(), vars),
+
+        Expr::TypeApply { lhs, .. } => free_vars(lhs.as_ref(), vars),
+
+        // name は本体 (letrec では値も) の中で束縛される
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            let mut inner_vars: HashSet<&'a str> = HashSet::new();
+            free_vars(body.as_ref(), &mut inner_vars);
+            if *recursive {
+                free_vars(value.as_ref(), &mut inner_vars);
+            } else {
+                free_vars(value.as_ref(), vars);
+            }
+
+            for var in inner_vars {
+                if var != name.as_str() {
+                    vars.insert(var);
+                }
+            }
+        }
     }
 }
 
@@ -1,15 +1,39 @@
 use super::bound_vars::BoundVars;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// ラムダ式や関数定義における識別子を表現する
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Identifier(String);
 
 impl Identifier {
+    /// モジュール名で修飾した識別子 (`bool::TRUE` など) を作る
+    pub fn qualified(module: &str, name: &str) -> Self {
+        Self(format!("{}::{}", module, name))
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
 
+    /// 修飾されている場合はモジュール名を返す
+    pub fn module(&self) -> Option<&str> {
+        self.0.split_once("::").map(|(module, _)| module)
+    }
+
+    /// モジュール名を除いた名前を返す
+    pub fn local_name(&self) -> &str {
+        self.0.split_once("::").map_or(&self.0, |(_, name)| name)
+    }
+
+    /// 数字だけからなる名前か
+    ///
+    /// 式の中では数値リテラルとして読まれるので、関数名には使えない
+    pub fn is_numeral(&self) -> bool {
+        self.0.chars().all(|c| c.is_ascii_digit())
+    }
+
     pub fn rename(&self, vars: &BoundVars) -> Self {
         let base_name = self.0.to_uppercase();
 
@@ -60,6 +84,18 @@ impl Display for Identifier {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_qualified() {
+        let id = Identifier::qualified("bool", "TRUE");
+        assert_eq!(id.as_str(), "bool::TRUE");
+        assert_eq!(id.module(), Some("bool"));
+        assert_eq!(id.local_name(), "TRUE");
+
+        let id = Identifier::from("TRUE");
+        assert_eq!(id.module(), None);
+        assert_eq!(id.local_name(), "TRUE");
+    }
+
     #[test]
     fn test_new_name() {
         let mut set = BoundVars::new();
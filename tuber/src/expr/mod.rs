@@ -1,12 +1,23 @@
 mod bound_vars;
 mod breakdown;
+mod church;
+mod de_bruijn;
+mod desugar;
+mod direction;
+mod erase;
 mod expression;
 mod free_vars;
 mod identifier;
 mod path;
+mod size;
+mod subexpr;
 mod substitute;
 
-pub use expression::{a, l, s, v, Expr};
+pub use bound_vars::BoundVars;
+pub use church::{church_numeral, scott_string};
+pub use de_bruijn::DeBruijn;
+pub use direction::Direction;
+pub use expression::{a, l, let_, letrec, lt, s, ta, tl, v, Expr};
 pub use free_vars::FreeVars;
 pub use identifier::Identifier;
-pub use path::{Path, PathBuilder};
+pub use path::Path;
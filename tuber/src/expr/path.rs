@@ -1,27 +1,29 @@
 #[derive(Clone, Debug, PartialEq)]
 pub enum Path {
     Arg(Index, Box<Path>),
+    /// 引数を伴わないラムダ抽象の本体の中 (強正規化のときのみ現れる)
+    Body(Box<Path>),
     Callee(Arity),
 }
 
 impl Path {
     pub fn get_arity(&self) -> Arity {
         match self {
-            Path::Arg(_, next) => next.get_arity(),
+            Path::Arg(_, next) | Path::Body(next) => next.get_arity(),
             Path::Callee(arity) => *arity,
         }
     }
 
     pub fn set_arity(&mut self, arity: Arity) {
         match self {
-            Path::Arg(_, next) => next.set_arity(arity),
+            Path::Arg(_, next) | Path::Body(next) => next.set_arity(arity),
             Path::Callee(old_arity) => *old_arity = arity,
         }
     }
 
     pub fn last_arg(&mut self) {
         match self {
-            Path::Arg(_, next) => next.last_arg(),
+            Path::Arg(_, next) | Path::Body(next) => next.last_arg(),
             Path::Callee(arity) => *self = Path::Arg(*arity, Box::new(Path::Callee(usize::MAX))),
         }
     }
@@ -41,6 +43,7 @@ impl From<Path> for Vec<usize> {
                     indices.push(index);
                     path = *next;
                 }
+                Path::Body(next) => path = *next,
                 Path::Callee(arity) => {
                     indices.push(arity);
                     break;
@@ -62,6 +65,7 @@ impl From<&Path> for Vec<usize> {
                     indices.push(*index);
                     path = next;
                 }
+                Path::Body(next) => path = next,
                 Path::Callee(arity) => {
                     indices.push(*arity);
                     break;
@@ -74,38 +78,6 @@ impl From<&Path> for Vec<usize> {
 
 // ========================================================================== //
 
-pub struct PathBuilder {
-    routes: Vec<Index>,
-    arity: Option<Arity>,
-}
-
-impl PathBuilder {
-    pub fn new() -> Self {
-        Self {
-            routes: Vec::new(),
-            arity: None,
-        }
-    }
-
-    pub fn add_route(&mut self, route: Index) {
-        self.routes.push(route);
-    }
-
-    pub fn set_arity(&mut self, arity: Arity) {
-        self.arity = Some(arity);
-    }
-
-    pub fn build(self) -> Path {
-        let mut path = Path::Callee(self.arity.unwrap());
-        for index in self.routes.into_iter().rev() {
-            path = Path::Arg(index, Box::new(path));
-        }
-        path
-    }
-}
-
-// ========================================================================== //
-
 #[cfg(test)]
 mod tests {
     use super::*;
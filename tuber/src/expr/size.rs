@@ -0,0 +1,35 @@
+use super::Expr;
+
+impl Expr {
+    /// 式を構成する項 (変数, シンボル, 適用, ラムダ抽象, 型抽象, 型適用, let 式) の個数
+    pub fn size(&self) -> usize {
+        match self {
+            Expr::Variable(_) => 1,
+            Expr::Symbol(_) => 1,
+            Expr::Apply { lhs, rhs } => 1 + lhs.size() + rhs.size(),
+            Expr::Lambda { body, .. } => 1 + body.size(),
+            Expr::TypeLambda { body, .. } => 1 + body.size(),
+            Expr::TypeApply { lhs, .. } => 1 + lhs.size(),
+            Expr::Let { value, body, .. } => 1 + value.size() + body.size(),
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use crate::expr;
+
+    #[test]
+    fn test_size() {
+        assert_eq!(expr::v("x").size(), 1);
+        assert_eq!(expr::s("x").size(), 1);
+        assert_eq!(expr::a("x", "y").size(), 3);
+        assert_eq!(expr::l("x", "x").size(), 2);
+        assert_eq!(
+            expr::l("x", expr::l("y", expr::a(expr::a("x", "y"), ":z"))).size(),
+            7
+        );
+    }
+}
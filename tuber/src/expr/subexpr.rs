@@ -0,0 +1,107 @@
+use super::{Expr, Path};
+
+impl Expr {
+    /// 経路の指す部分式を返す
+    ///
+    /// `Path::Callee(arity)` は先頭の項に arity 個の引数を与えた適用を指す
+    pub fn subexpr(&self, path: &Path) -> Option<&Expr> {
+        let len = self.unapply().1.len();
+        match path {
+            Path::Arg(index, next) => match self.lhs_nth(len.checked_sub(*index)?)? {
+                Expr::Apply { rhs, .. } => rhs.subexpr(next),
+                _ => None,
+            },
+            Path::Body(next) => match self {
+                Expr::Lambda { body, .. } => body.subexpr(next),
+                _ => None,
+            },
+            Path::Callee(arity) => self.lhs_nth(len.checked_sub(*arity)?),
+        }
+    }
+
+    /// 経路の指す部分式を可変借用する
+    pub fn subexpr_mut(&mut self, path: &Path) -> Option<&mut Expr> {
+        let len = self.unapply().1.len();
+        match path {
+            Path::Arg(index, next) => match self.lhs_nth_mut(len.checked_sub(*index)?)? {
+                Expr::Apply { rhs, .. } => rhs.subexpr_mut(next),
+                _ => None,
+            },
+            Path::Body(next) => match self {
+                Expr::Lambda { body, .. } => body.subexpr_mut(next),
+                _ => None,
+            },
+            Path::Callee(arity) => self.lhs_nth_mut(len.checked_sub(*arity)?),
+        }
+    }
+
+    // 関数適用の左側を depth 回たどった先の式
+    fn lhs_nth(&self, depth: usize) -> Option<&Expr> {
+        let mut expr = self;
+        for _ in 0..depth {
+            match expr {
+                Expr::Apply { lhs, .. } => expr = lhs,
+                _ => return None,
+            }
+        }
+        Some(expr)
+    }
+
+    fn lhs_nth_mut(&mut self, depth: usize) -> Option<&mut Expr> {
+        let mut expr = self;
+        for _ in 0..depth {
+            match expr {
+                Expr::Apply { lhs, .. } => expr = lhs,
+                _ => return None,
+            }
+        }
+        Some(expr)
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_subexpr() {
+        // f(x => g(y), z)
+        let expr = expr::a(expr::a("f", expr::l("x", expr::a(":g", ":y"))), "z");
+
+        assert_eq!(expr.subexpr(&Path::Callee(2)), Some(&expr));
+        assert_eq!(
+            expr.subexpr(&Path::Callee(1)),
+            Some(&expr::a("f", expr::l("x", expr::a(":g", ":y"))))
+        );
+        assert_eq!(expr.subexpr(&Path::Callee(0)), Some(&expr::v("f")));
+        assert_eq!(
+            expr.subexpr(&Path::Arg(2, Box::new(Path::Callee(0)))),
+            Some(&expr::v("z"))
+        );
+        assert_eq!(
+            expr.subexpr(&Path::Arg(
+                1,
+                Box::new(Path::Body(Box::new(Path::Callee(1))))
+            )),
+            Some(&expr::a(":g", ":y"))
+        );
+        assert_eq!(expr.subexpr(&Path::Callee(3)), None);
+        assert_eq!(expr.subexpr(&Path::Body(Box::new(Path::Callee(0)))), None);
+    }
+
+    #[test]
+    fn test_subexpr_mut() {
+        let mut expr = expr::a(expr::a("f", "x"), "y");
+
+        *expr
+            .subexpr_mut(&Path::Arg(1, Box::new(Path::Callee(0))))
+            .unwrap() = expr::s("a");
+        assert_eq!(expr, expr::a(expr::a("f", ":a"), "y"));
+
+        *expr.subexpr_mut(&Path::Callee(1)).unwrap() = expr::v("g");
+        assert_eq!(expr, expr::a("g", "y"));
+    }
+}
@@ -40,10 +40,7 @@ impl Expr {
 
             // param と同名の引数を持つラムダ抽象は内部に自由変数としての param を持たない
             // そのため即座に検索を打ち切って良い
-            Expr::Lambda {
-                param: ref p,
-                body: _,
-            } if p == param => {}
+            Expr::Lambda { param: ref p, .. } if p == param => {}
 
             // arg の中の自由変数とラムダ抽象の引数 p が衝突する場合
             // ラムダ抽象の引数 p を適切にリネームする必要がある (α変換)
@@ -51,11 +48,12 @@ impl Expr {
             Expr::Lambda {
                 param: ref mut p,
                 body,
+                ..
             } => {
                 if free_vars.contains(p) {
                     // p を適切にリネームする
-                    // リネーム後の名前はどの束縛変数とも被ってはいけない
-                    let new_p = p.rename(&bound_vars);
+                    // リネーム後の名前は束縛変数, arg や body の自由変数, param のどれとも被ってはいけない
+                    let new_p = fresh(p, param, free_vars, &[body], &bound_vars);
 
                     // body の中の全ての p をリネームした new_param に置き換える
                     replace(body, p, &new_p);
@@ -68,10 +66,75 @@ impl Expr {
                 // 再帰的に置換を行う
                 body.substitute_impl(param, arg, free_vars, bound_vars);
             }
+
+            // 型抽象は変数を束縛しないので、そのまま中に進む
+            Expr::TypeLambda { body, .. } => {
+                body.substitute_impl(param, arg, free_vars, bound_vars);
+            }
+
+            Expr::TypeApply { lhs, .. } => {
+                lhs.substitute_impl(param, arg, free_vars, bound_vars);
+            }
+
+            // let 式の name は本体 (letrec では値も) を束縛するので、ラムダ抽象と同様に扱う
+            Expr::Let {
+                recursive,
+                name,
+                value,
+                body,
+            } => {
+                if !*recursive {
+                    value.substitute_impl(param, arg, free_vars, bound_vars.clone());
+                }
+
+                if name == param {
+                    return;
+                }
+
+                if free_vars.contains(name) {
+                    let new_name = fresh(name, param, free_vars, &[value, body], &bound_vars);
+                    replace(body, name, &new_name);
+                    if *recursive {
+                        replace(value, name, &new_name);
+                    }
+                    *name = new_name;
+                }
+
+                bound_vars.insert(name.clone());
+
+                if *recursive {
+                    value.substitute_impl(param, arg, free_vars, bound_vars.clone());
+                }
+                body.substitute_impl(param, arg, free_vars, bound_vars);
+            }
         }
     }
 }
 
+/// 置換の途中で束縛変数 id をリネームする先の名前を得る
+///
+/// 新しい名前が arg の自由変数や param と被ると、置換後に束縛されてしまったり置換されてしまったりする
+/// また scopes の中の自由変数と被ると、元々自由だった変数を束縛してしまう
+fn fresh(
+    id: &Identifier,
+    param: &Identifier,
+    free_vars: &FreeVars,
+    scopes: &[&Expr],
+    bound_vars: &BoundVars,
+) -> Identifier {
+    let mut vars = bound_vars.clone();
+    vars.insert(param.clone());
+    for var in &free_vars.0 {
+        vars.insert(*var);
+    }
+    for scope in scopes {
+        for var in FreeVars::from(*scope).0 {
+            vars.insert(var);
+        }
+    }
+    id.rename(&vars)
+}
+
 /// 式の中の自由変数を別の識別子に置き換える
 fn replace(expr: &mut Expr, old: &Identifier, new: &Identifier) {
     match expr {
@@ -88,7 +151,7 @@ fn replace(expr: &mut Expr, old: &Identifier, new: &Identifier) {
             replace(rhs.as_mut(), old, new);
         }
 
-        Expr::Lambda { param, body } => {
+        Expr::Lambda { param, body, .. } => {
             if param != old {
                 replace(body.as_mut(), old, new);
             } else {
@@ -99,6 +162,24 @@ fn replace(expr: &mut Expr, old: &Identifier, new: &Identifier) {
                 // 存在しないことが確定するので、その時点で再起を打ち切っていい
             }
         }
+
+        Expr::TypeLambda { body, .. } => replace(body.as_mut(), old, new),
+
+        Expr::TypeApply { lhs, .. } => replace(lhs.as_mut(), old, new),
+
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            if !*recursive || name != old {
+                replace(value.as_mut(), old, new);
+            }
+            if name != old {
+                replace(body.as_mut(), old, new);
+            }
+        }
     }
 }
 
@@ -122,6 +203,20 @@ mod tests {
         assert_eq!(e, expr::l("Y", expr::l("Y0", expr::a("y", "Y"))));
     }
 
+    #[test]
+    /// リネーム後の名前は arg の自由変数や body の自由変数, param と衝突しない
+    fn test_substitute_fresh_name() {
+        // ^X.x [x := `yX] => ^X0.`yX
+        let mut e = expr::l("X", "x");
+        e.substitute(&"x".into(), &expr::a("y", "X"));
+        assert_eq!(e, expr::l("X0", expr::a("y", "X")));
+
+        // ^x.`Xx [X := x] => ^X0.`xX0
+        let mut e = expr::l("x", expr::a("X", "x"));
+        e.substitute(&"X".into(), &"x".into());
+        assert_eq!(e, expr::l("X0", expr::a("x", "X0")));
+    }
+
     #[test]
     /// ラムダ抽象の中で束縛されている変数は置換されない
     fn test_replace_1() {
@@ -25,6 +25,13 @@ pub enum Compact<'a> {
         body: Box<Compact<'a>>,
         tag: Tag,
     },
+    Let {
+        recursive: bool,
+        name: &'a str,
+        value: Box<Compact<'a>>,
+        body: Box<Compact<'a>>,
+        tag: Tag,
+    },
 }
 
 // ========================================================================== //
@@ -74,6 +81,26 @@ fn from_expr<'a>(expr: &'a Expr, tag: &Tag) -> Compact<'a> {
                 tag,
             }
         }
+
+        // 簡約の経過は型を取り除いた式で表示する
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => from_expr(body, tag),
+
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            let tag = tag.push(0);
+
+            Compact::Let {
+                recursive: *recursive,
+                name: name.as_str(),
+                value: Box::new(from_expr(value, &tag)),
+                body: Box::new(from_expr(body, &tag)),
+                tag,
+            }
+        }
     }
 }
 
@@ -87,7 +114,7 @@ impl<'a> Compact<'a> {
                     .iter()
                     .filter_map(|path| match path {
                         Path::Arg(index, next) => Some((*index, next as &Path)),
-                        Path::Callee(_) => None,
+                        Path::Body(_) | Path::Callee(_) => None,
                     })
                     .collect::<Vec<(usize, &Path)>>(),
             );
@@ -109,7 +136,7 @@ impl<'a> Compact<'a> {
             let arities: Vec<_> = split
                 .iter()
                 .filter_map(|path| match path {
-                    Path::Arg(_, _) => None,
+                    Path::Arg(_, _) | Path::Body(_) => None,
                     Path::Callee(arity) => Some(*arity),
                 })
                 .collect();
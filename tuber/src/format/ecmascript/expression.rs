@@ -56,12 +56,29 @@ impl From<Compact<'_>> for Formed {
                 Formed { expr, mapping }
             }
 
+            Compact::Let {
+                recursive,
+                name,
+                value,
+                body,
+                tag,
+            } => {
+                let keyword = if recursive { "letrec" } else { "let" };
+                let value_str = Formed::from(*value).expr;
+                let body_str = Formed::from(*body).expr;
+                let expr = format!("{} {} = {} in {}", keyword, name, value_str, body_str);
+
+                let mapping = vec![tag; expr.chars().count()];
+
+                Formed { expr, mapping }
+            }
+
             Compact::Apply { callee, args, tag } => {
                 let mut expr = String::new();
                 let mut mapping: Vec<Tag> = Vec::new();
 
                 match *callee {
-                    Compact::Lambda { .. } => {
+                    Compact::Lambda { .. } | Compact::Let { .. } => {
                         let mut formed = Formed::from(*callee);
                         expr = expr + "(" + &formed.expr + ")";
                         mapping.push(tag.clone());
@@ -110,7 +110,7 @@ fn tokenize<'a>(expr: &'a Expr, tag: &Tag) -> (Vec<Token<'a>>, Vec<Tag>) {
             (vec![token], vec![tag])
         }
 
-        Expr::Lambda { param, body } => {
+        Expr::Lambda { param, body, .. } => {
             let ident = Ident::Variable(param.as_str());
             let param_token = if ident.is_lower() {
                 Token::LowerIdent(ident)
@@ -127,6 +127,35 @@ fn tokenize<'a>(expr: &'a Expr, tag: &Tag) -> (Vec<Token<'a>>, Vec<Tag>) {
 
             (tokens, tags)
         }
+
+        // 簡約の経過は型を取り除いた式で表示する
+        Expr::TypeLambda { body, .. } | Expr::TypeApply { lhs: body, .. } => tokenize(body, tag),
+
+        // let 式はラムダ抽象と同様に全体で1つの項として扱う
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            let ident = Ident::Variable(name.as_str());
+            let name_token = if ident.is_lower() {
+                Token::LowerIdent(ident)
+            } else {
+                Token::UpperIdent(ident)
+            };
+            let (mut value_tokens, _) = tokenize(value, &Tag::new());
+            let (mut body_tokens, _) = tokenize(body, &Tag::new());
+
+            let mut tokens = vec![Token::Let(*recursive), name_token, Token::Equal];
+            tokens.append(&mut value_tokens);
+            tokens.push(Token::In);
+            tokens.append(&mut body_tokens);
+
+            let tags = vec![tag.push(0); tokens.len()];
+
+            (tokens, tags)
+        }
     }
 }
 
@@ -137,6 +166,9 @@ enum Token<'a> {
     Apply,
     Lambda,
     Dot,
+    Let(bool),
+    Equal,
+    In,
 }
 
 impl std::fmt::Display for Token<'_> {
@@ -147,6 +179,10 @@ impl std::fmt::Display for Token<'_> {
             Token::Apply => write!(f, "`"),
             Token::Lambda => write!(f, "λ"),
             Token::Dot => write!(f, "."),
+            Token::Let(false) => write!(f, "let "),
+            Token::Let(true) => write!(f, "letrec "),
+            Token::Equal => write!(f, " = "),
+            Token::In => write!(f, " in "),
         }
     }
 }
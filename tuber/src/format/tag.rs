@@ -89,10 +89,11 @@ impl Path {
             path = &**next;
         }
 
-        let arity: usize = if let Path::Callee(arity) = path {
-            *arity
-        } else {
-            panic!("Path::range() called on non-callee path");
+        // ラムダ抽象の本体は区別して表示しないので、ラムダ抽象全体の範囲とする
+        let arity: usize = match path {
+            Path::Callee(arity) => *arity,
+            Path::Body(_) => 0,
+            Path::Arg(..) => unreachable!(),
         };
 
         (prefix, arity)
@@ -1,18 +1,35 @@
 use crate::expr::{self, Expr, Identifier};
+use serde::{Deserialize, Serialize};
 
 /// 定義済み関数を表現する
 ///
 /// 関数とラムダ抽象はよく似ているが、関数が 0 以上の arity を持つ点で異なる
-#[derive(Debug, Clone, PartialEq)]
+/// data 宣言から生成した関数は、宣言したデータ型の名前を origin にもつ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Func {
     name: Identifier,
     params: Vec<Identifier>,
     body: Expr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    origin: Option<Identifier>,
 }
 
 impl Func {
     pub fn new(name: Identifier, params: Vec<Identifier>, body: Expr) -> Self {
-        Self { name, params, body }
+        Self {
+            name,
+            params,
+            body,
+            origin: None,
+        }
+    }
+
+    /// 関数を生成したデータ型の名前を付ける
+    pub fn with_origin(self, origin: Identifier) -> Self {
+        Self {
+            origin: Some(origin),
+            ..self
+        }
     }
 
     pub fn name(&self) -> &str {
@@ -27,6 +44,10 @@ impl Func {
         &self.body
     }
 
+    pub fn origin(&self) -> Option<&Identifier> {
+        self.origin.as_ref()
+    }
+
     /// 関数の引数の個数
     ///
     /// 0 以上の整数値を返す
@@ -54,6 +75,7 @@ where
         name: name.into(),
         params: params.into_iter().map(|i| i.into()).collect(),
         body: body.into(),
+        origin: None,
     }
 }
 
@@ -1,21 +1,34 @@
 mod calc;
 mod context;
+mod data;
 mod engine;
+mod export;
 mod expr;
 mod format;
 mod func;
 mod parser;
 mod style;
 mod to_string;
+mod types;
 
 pub use calc::{
-    expand, unlambda_iota, unlambda_recursive, unlambda_recursive_, Aliases, RecursiveStrategy,
-    ReduceResult, Reducer,
+    compare_unlambda, count_steps, decode, expand, sample_args, unlambda_iota, unlambda_kiselyov,
+    unlambda_recursive, unlambda_recursive_, unlambda_turner, AbstractionRule, Aliases, Decoded,
+    LazyK, LazyKError, Metrics, RecursiveStrategy, ReduceResult, Reducer, ReductionBackend,
+    ReductionHistory, ReductionStrategy, ReductionTarget, StrategyMetrics, Termination,
+    UnlambdaStep, UnlambdaTrace, UnlambdaTraceError, COMPARED_STRATEGIES,
 };
-pub use context::Context;
-pub use engine::Command;
-pub use expr::{Expr, Identifier, Path};
+pub use context::{Context, ContextError, Module, BUILTIN_MODULES};
+pub use data::{Constructor, DataError, DataType, Encoding, Field};
+pub use engine::{run_script, Command, Comparison, Engine, Output, Reduction, DEFAULT_MAX_STEPS};
+pub use export::{export, ExportError, LazyKSyntax};
+pub use expr::{church_numeral, scott_string, DeBruijn, Direction, Expr, Identifier, Path};
 pub use format::{ecmascript_format, lazy_k_format, Formed, Tag};
 pub use func::Func;
-pub use parser::{parse_command, parse_expr, parse_update_or_delete};
+pub use parser::{
+    parse_command, parse_definitions, parse_expr, parse_expr_with_combinator_style,
+    parse_expr_with_iota_style, parse_expr_with_jot_style, parse_script, parse_update_or_delete,
+    Grammar, ParseError, Span, Statement,
+};
 pub use to_string::{DisplayStyle, Format};
+pub use types::{infer_type, Type, TypeError};
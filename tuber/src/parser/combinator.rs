@@ -0,0 +1,97 @@
+use crate::expr::{self, Expr};
+use crate::parser::error::{Grammar, ParseError};
+use combine::parser::char::{char, spaces};
+use combine::parser::choice::choice;
+use combine::{many1, parser, EasyParser, Parser, Stream};
+
+/// Lazy K の SKI コンビネータ記法 (`S(KS)K`, `SII(SII)` など) で書かれた式を解析する
+///
+/// S, K, I は大文字・小文字を区別せず、それぞれ変数 s, k, i になる
+pub fn parse_expr(s: &str) -> Result<Expr, ParseError> {
+    let (expr, rest) = expr()
+        .easy_parse(s)
+        .map_err(|e| ParseError::from_errors(s, Grammar::Combinator, e))?;
+
+    if rest.is_empty() {
+        Ok(expr)
+    } else {
+        Err(ParseError::unexpected_rest(s, Grammar::Combinator, rest))
+    }
+}
+
+pub fn expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: combine::ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    expr_()
+}
+
+parser! {
+    fn expr_[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: combine::ParseError<char, Input::Range, Input::Position>,
+    ]
+    {
+        // 並んだ項は左結合の関数適用
+        spaces().with(many1(term().skip(spaces()))).map(|terms: Vec<Expr>| {
+            terms
+                .into_iter()
+                .reduce(expr::a)
+                .expect("many1 yields at least one term")
+        })
+    }
+}
+
+fn term<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: combine::ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((
+        choice((char('S'), char('s'))).map(|_| expr::v("s")),
+        choice((char('K'), char('k'))).map(|_| expr::v("k")),
+        choice((char('I'), char('i'))).map(|_| expr::v("i")),
+        char('(').with(expr()).skip(char(')')),
+    ))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(parse_expr("S"), Ok(expr::v("s")));
+        assert_eq!(
+            parse_expr("SII(SII)"),
+            Ok(expr::a(
+                expr::a(expr::a("s", "i"), "i"),
+                expr::a(expr::a("s", "i"), "i")
+            ))
+        );
+        assert_eq!(
+            parse_expr("S(KS)K"),
+            Ok(expr::a(expr::a("s", expr::a("k", "s")), "k"))
+        );
+        assert_eq!(
+            parse_expr(" s ( k s ) k"),
+            Ok(expr::a(expr::a("s", expr::a("k", "s")), "k"))
+        );
+        assert_eq!(parse_expr("((K))I"), Ok(expr::a("k", "i")));
+    }
+
+    #[test]
+    fn test_parse_expr_error() {
+        assert!(parse_expr("").is_err());
+        assert!(parse_expr("()").is_err());
+        assert!(parse_expr("S(K").is_err());
+
+        let err = parse_expr("SKX").unwrap_err();
+        assert_eq!(err.span.start, 2);
+        assert_eq!(err.grammars, vec![Grammar::Combinator]);
+    }
+}
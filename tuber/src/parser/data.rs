@@ -0,0 +1,120 @@
+use combine::parser::char::{char, spaces};
+use combine::parser::choice::choice;
+use combine::{attempt, many, optional, sep_by1, ParseError, Parser, Stream};
+
+use super::identifier::identifier;
+use super::utils::keyword;
+use crate::data::{Constructor, DataType, Encoding, Field};
+use crate::engine::Command;
+
+/// `data TREE = LEAF | NODE l:TREE v r:TREE` のようにデータ型を宣言するコマンド
+///
+/// `data church TREE = ...` のように型名の前にエンコーディング (church, scott, parigot) を書ける
+/// ECMAScript スタイルと Lazy K スタイルで共通の構文
+pub fn data<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    keyword("data")
+        .with(optional(encoding()))
+        .and(identifier())
+        .skip(spaces().with(char('=')))
+        .and(sep_by1(constructor(), attempt(spaces().with(char('|')))))
+        .map(|((encoding, name), constructors)| {
+            Command::Data(DataType::new(
+                name,
+                encoding.unwrap_or_default(),
+                constructors,
+            ))
+        })
+}
+
+fn encoding<Input>() -> impl Parser<Input, Output = Encoding>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((
+        keyword("church").map(|_| Encoding::Church),
+        keyword("scott").map(|_| Encoding::Scott),
+        keyword("parigot").map(|_| Encoding::Parigot),
+    ))
+}
+
+fn constructor<Input>() -> impl Parser<Input, Output = Constructor>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    identifier()
+        .and(many(attempt(field())))
+        .map(|(name, fields)| Constructor::new(name, fields))
+}
+
+fn field<Input>() -> impl Parser<Input, Output = Field>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    identifier()
+        .and(optional(
+            attempt(spaces().with(char(':'))).with(identifier()),
+        ))
+        .map(|(name, ty)| Field::new(name, ty))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+    use combine::EasyParser;
+
+    #[test]
+    fn test_data() {
+        assert_eq!(
+            data().easy_parse("data TREE = LEAF | NODE l:TREE v r:TREE"),
+            Ok((
+                Command::Data(data::new(
+                    "TREE",
+                    Encoding::Scott,
+                    vec![
+                        ("LEAF", vec![]),
+                        (
+                            "NODE",
+                            vec![("l", "TREE").into(), Field::from("v"), ("r", "TREE").into()]
+                        ),
+                    ]
+                )),
+                ""
+            ))
+        );
+        assert_eq!(
+            data().easy_parse("data church NAT = Z|S n : NAT"),
+            Ok((
+                Command::Data(data::new(
+                    "NAT",
+                    Encoding::Church,
+                    vec![("Z", vec![]), ("S", vec![("n", "NAT")])]
+                )),
+                ""
+            ))
+        );
+        assert_eq!(
+            data().easy_parse("data parigot PAIR = PAIR xy"),
+            Ok((
+                Command::Data(data::new(
+                    "PAIR",
+                    Encoding::Parigot,
+                    vec![("PAIR", vec!["x", "y"])]
+                )),
+                ""
+            ))
+        );
+
+        assert!(data().easy_parse("dataTREE = LEAF").is_err());
+        assert!(data().easy_parse("data TREE =").is_err());
+    }
+}
@@ -1,10 +1,13 @@
-use combine::parser::char::{char, digit, spaces, string};
+use combine::parser::char::{char, digit, space, spaces, string};
 use combine::parser::choice::choice;
 use combine::{
-    attempt, count_min_max, eof, many, many1, optional, parser, ParseError, Parser, Stream,
+    attempt, count_min_max, eof, look_ahead, many, many1, optional, parser, skip_many1, ParseError,
+    Parser, Stream,
 };
 
-use super::super::identifier::identifier;
+use super::super::data::data;
+use super::super::identifier::{function_name, identifier, qualified_identifier};
+use super::super::import::import;
 use super::expression::expr;
 use crate::engine::Command;
 use crate::expr::{Expr, Identifier};
@@ -18,6 +21,10 @@ where
         From<::std::num::ParseIntError>,
 {
     choice((
+        attempt(import()),
+        attempt(data()),
+        attempt(metrics()),
+        attempt(type_of()),
         attempt(update()),
         eval(),
         attempt(eval_head()),
@@ -40,11 +47,13 @@ where
     <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
         From<::std::num::ParseIntError>,
 {
+    // `f = f` は削除、`f = (f)` は自身を参照する関数の定義
     def_lhs()
         .skip(spaces().with(char('=')))
+        .and(spaces().with(look_ahead(optional(char('(')))))
         .and(expr())
-        .map(|((i, is), rhs)| match rhs {
-            Expr::Variable(j) if is.is_empty() && i == j => Command::Del(i),
+        .map(|(((i, is), paren), rhs)| match rhs {
+            Expr::Variable(j) if is.is_empty() && i == j && paren.is_none() => Command::Del(i),
             _ => Command::Update(func::new(i, is, rhs)),
         })
 }
@@ -68,7 +77,7 @@ parser! {
             From<::std::num::ParseIntError>,
     ]
     {
-        identifier()
+        function_name()
             .and(spaces().with(optional(params())))
             .map(|(i, is)| (i, is.unwrap_or_default()))
     }
@@ -157,7 +166,7 @@ where
 {
     spaces()
         .skip(char('?'))
-        .with(identifier())
+        .with(qualified_identifier())
         .map(Command::Query)
 }
 
@@ -178,7 +187,7 @@ where
     <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
         From<::std::num::ParseIntError>,
 {
-    let level = count_min_max(1, 4, char('~')).map(|str: String| str.len() as u8);
+    let level = count_min_max(1, 6, char('~')).map(|str: String| str.len() as u8);
 
     spaces()
         .with(level)
@@ -186,6 +195,34 @@ where
         .map(|(level, expr)| Command::Unlambda(level, expr))
 }
 
+fn metrics<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    spaces()
+        .with(string("metrics"))
+        .with(skip_many1(space()))
+        .with(expr())
+        .map(Command::Metrics)
+}
+
+fn type_of<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    spaces()
+        .with(string(":t"))
+        .with(skip_many1(space()))
+        .with(expr())
+        .map(Command::TypeOf)
+}
+
 // ========================================================================== //
 
 #[cfg(test)]
@@ -284,6 +321,11 @@ mod tests {
         );
 
         assert_eq!(command().easy_parse("?"), Ok((Command::Context, "")));
+
+        assert_eq!(
+            command().easy_parse("import bool"),
+            Ok((Command::Import("bool".to_string()), ""))
+        );
     }
 
     #[test]
@@ -399,6 +441,39 @@ mod tests {
             Ok((Command::Unlambda(4, expr::l("x", "x")), ""))
         );
 
-        assert!(unlambda().easy_parse("~~~~~x=>x").is_err());
+        assert_eq!(
+            unlambda().easy_parse("~~~~~x=>x"),
+            Ok((Command::Unlambda(5, expr::l("x", "x")), ""))
+        );
+
+        assert_eq!(
+            unlambda().easy_parse("~~~~~~x=>x"),
+            Ok((Command::Unlambda(6, expr::l("x", "x")), ""))
+        );
+
+        assert!(unlambda().easy_parse("~~~~~~~x=>x").is_err());
+    }
+
+    #[test]
+    fn test_metrics() {
+        assert_eq!(
+            command().easy_parse("metrics x=>x"),
+            Ok((Command::Metrics(expr::l("x", "x")), ""))
+        );
+        assert!(command().easy_parse("metrics").is_err());
+    }
+
+    #[test]
+    fn test_type_of() {
+        assert_eq!(
+            command().easy_parse(":t x=>x"),
+            Ok((Command::TypeOf(expr::l("x", "x")), ""))
+        );
+
+        // `:t` だけならシンボル
+        assert!(!matches!(
+            command().easy_parse(":t"),
+            Ok((Command::TypeOf(_), _))
+        ));
     }
 }
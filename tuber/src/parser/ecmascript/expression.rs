@@ -1,9 +1,15 @@
-use super::super::identifier::identifier;
-use super::super::utils::{parens, token};
+use super::super::identifier::{identifier, qualified_identifier};
+use super::super::literal::literal;
+use super::super::types::{ty, type_param};
+use super::super::utils::{keyword, parens, token};
 use crate::expr::{self, Expr, Identifier};
+use crate::types::Type;
 use combine::parser::char::{char, spaces, string};
 use combine::parser::choice::choice;
-use combine::{attempt, many, many1, optional, parser, ParseError, Parser, Stream};
+use combine::parser::token::one_of;
+use combine::{
+    attempt, look_ahead, many, many1, optional, parser, sep_by1, ParseError, Parser, Stream,
+};
 
 pub fn expr<Input>() -> impl Parser<Input, Output = Expr>
 where
@@ -25,10 +31,15 @@ parser! {
     ]
     {
         spaces().with(choice((
+            attempt(let_expr()),
             attempt(apply()),
             attempt(lambda()),
+            attempt(type_lambda()),
+            literal(),
             symbol(),
             var(),
+            // 引数を伴わない `(x)` や `((x) => x)` もパーレンを剥がして読む
+            parens(expr()),
         )))
     }
 }
@@ -40,7 +51,7 @@ where
     Input: Stream<Token = char>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
 {
-    identifier().map(expr::v)
+    qualified_identifier().map(expr::v)
 }
 
 fn symbol<Input>() -> impl Parser<Input, Output = Expr>
@@ -65,13 +76,23 @@ parser! {
         spaces()
             .with(callable())
             .and(
-                many1(spaces().with(args()))
+                // 空白の後に引数が続かない場合 (`f(x) in ...` など) は空白を読まずに止まる
+                many1(attempt(spaces().skip(look_ahead(one_of("(<".chars())))).with(args()))
             )
             .map(|(mut e, argss)| {
-                let _: Vec<Vec<Expr>> = argss;
+                let _: Vec<Args> = argss;
                 for args in argss {
-                    for arg in args {
-                        e = expr::a(e, arg);
+                    match args {
+                        Args::Exprs(args) => {
+                            for arg in args {
+                                e = expr::a(e, arg);
+                            }
+                        }
+                        Args::Types(tys) => {
+                            for ty in tys {
+                                e = expr::ta(e, ty);
+                            }
+                        }
                     }
                 }
                 e
@@ -105,28 +126,42 @@ parser! {
             // つまり、パーレンで囲まれていない形での関数適用は弾く
             // ここで関数適用を弾いておかないと左再帰で無限ループしてしまう
             attempt(lambda()),
+            attempt(type_lambda()),
+            literal(),
             symbol(),
             var(),
         )))
     }
 }
 
-fn args<Input>() -> impl Parser<Input, Output = Vec<Expr>>
+// 関数適用の引数 (`(x, y)` または型の引数 `<Nat>`)
+enum Args {
+    Exprs(Vec<Expr>),
+    Types(Vec<Type>),
+}
+
+fn args<Input>() -> impl Parser<Input, Output = Args>
 where
     Input: Stream<Token = char>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
     <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
         From<::std::num::ParseIntError>,
 {
-    spaces()
-        .with(parens(
-            optional(many(attempt(token(expr()).skip(char(','))))).and(token(expr())),
-        ))
-        .map(|(es, e)| {
-            let mut es: Vec<Expr> = es.unwrap_or_default();
-            es.push(e);
-            es
-        })
+    // 各引数を一度だけパースする (バックトラックすると入れ子の深さに対して指数時間になる)
+    spaces().with(choice((
+        parens(sep_by1(token(expr()), char(','))).map(Args::Exprs),
+        angles(sep_by1(token(ty()), char(','))).map(Args::Types),
+    )))
+}
+
+fn angles<Input, Output>(
+    parser: impl Parser<Input, Output = Output>,
+) -> impl Parser<Input, Output = Output>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    char('<').with(parser).skip(char('>'))
 }
 
 // ========================================================================== //
@@ -144,33 +179,107 @@ parser! {
             .with(
                 choice((
                     params(),
-                    identifier().map(|i| vec![i])),
+                    identifier().map(|i| vec![(i, None)])),
                 ))
                 .skip(token(string("=>"))
             )
             .and(expr())
             .map(|(params, mut body)| {
-                for param in params.into_iter().rev() {
-                    body = expr::l(param, body);
+                for (param, ty) in params.into_iter().rev() {
+                    body = match ty {
+                        Some(ty) => expr::lt(param, ty, body),
+                        None => expr::l(param, body),
+                    };
                 }
                 body
             })
     }
 }
 
-fn params<Input>() -> impl Parser<Input, Output = Vec<Identifier>>
+fn params<Input>() -> impl Parser<Input, Output = Vec<(Identifier, Option<Type>)>>
 where
     Input: Stream<Token = char>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
     <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
         From<::std::num::ParseIntError>,
 {
-    parens(optional(many(attempt(token(identifier()).skip(char(','))))).and(token(identifier())))
-        .map(|(is, i)| {
-            let mut is: Vec<Identifier> = is.unwrap_or_default();
-            is.push(i);
-            is
+    parens(optional(many(attempt(token(param()).skip(char(','))))).and(token(param()))).map(
+        |(ps, p)| {
+            let mut ps: Vec<(Identifier, Option<Type>)> = ps.unwrap_or_default();
+            ps.push(p);
+            ps
+        },
+    )
+}
+
+// 型注釈の付いていてもよい引数 (`x` または `x: Nat`)
+fn param<Input>() -> impl Parser<Input, Output = (Identifier, Option<Type>)>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    identifier().and(optional(attempt(spaces().with(char(':'))).with(ty())))
+}
+
+// ========================================================================== //
+
+parser! {
+    fn type_lambda[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+            From<::std::num::ParseIntError>,
+    ]
+    {
+        // <α, β>(x: α) => x のように型の引数をラムダ抽象の前に書く
+        // 本体がラムダ抽象でない場合は <α> => M と書く
+        spaces()
+            .with(angles(sep_by1(token(type_param()), char(','))))
+            .and(choice((
+                attempt(lambda()),
+                token(string("=>")).with(expr()),
+            )))
+            .map(|(params, mut body): (Vec<Identifier>, Expr)| {
+                for param in params.into_iter().rev() {
+                    body = expr::tl(param, body);
+                }
+                body
+            })
+    }
+}
+
+// ========================================================================== //
+
+parser! {
+    fn let_expr[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+            From<::std::num::ParseIntError>,
+    ]
+    {
+        // let x = M in N または letrec f = M in N
+        choice((
+            keyword("letrec").map(|_| true),
+            keyword("let").map(|_| false),
+        ))
+        .and(identifier())
+        .skip(spaces().with(char('=')))
+        .and(expr())
+        .skip(keyword("in"))
+        .and(expr())
+        .map(|(((recursive, name), value), body)| {
+            if recursive {
+                expr::letrec(name, value, body)
+            } else {
+                expr::let_(name, value, body)
+            }
         })
+    }
 }
 
 // ========================================================================== //
@@ -178,12 +287,35 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::expr::{church_numeral, scott_string};
     use combine::EasyParser;
 
     #[test]
     fn test_expr() {
         assert_eq!(expr().easy_parse("a"), Ok((expr::v("a"), "")));
         assert_eq!(expr().easy_parse("a(b)"), Ok((expr::a("a", "b"), "")));
+        assert_eq!(expr().easy_parse("(a)"), Ok((expr::v("a"), "")));
+        assert_eq!(expr().easy_parse("((x) => x)"), Ok((expr::l("x", "x"), "")));
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(
+            expr().easy_parse("ADD(2, 21)"),
+            Ok((
+                expr::a(expr::a("ADD", church_numeral(2)), church_numeral(21)),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("3(f)"),
+            Ok((expr::a(church_numeral(3), "f"), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("f(\"ab\")"),
+            Ok((expr::a("f", scott_string("ab")), ""))
+        );
+        assert_eq!(expr().easy_parse("X2"), Ok((expr::v("X2"), "")));
     }
 
     #[test]
@@ -254,4 +386,90 @@ mod tests {
             Ok((expr::l("a", expr::l("b", "c")), ""))
         );
     }
+
+    #[test]
+    fn test_typed() {
+        let nat = || Type::named("Nat");
+        let alpha = || Type::named("α");
+
+        assert_eq!(
+            expr().easy_parse("(x: Nat) => x"),
+            Ok((expr::lt("x", nat(), "x"), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("(f: Nat -> Nat, x) => f(x)"),
+            Ok((
+                expr::lt(
+                    "f",
+                    Type::arrow(nat(), nat()),
+                    expr::l("x", expr::a("f", "x"))
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("<α, β>(x: α) => x"),
+            Ok((
+                expr::tl("α", expr::tl("β", expr::lt("x", alpha(), "x"))),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("<α> => ID"),
+            Ok((expr::tl("α", "ID"), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("PAIR<Nat, α>(x, y)"),
+            Ok((
+                expr::a(
+                    expr::a(expr::ta(expr::ta("PAIR", nat()), alpha()), "x"),
+                    "y"
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("(<α>(x: α) => x)<Nat>(y)"),
+            Ok((
+                expr::a(
+                    expr::ta(expr::tl("α", expr::lt("x", alpha(), "x")), nat()),
+                    "y"
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn test_let() {
+        assert_eq!(
+            expr().easy_parse("let x = f(y) in g(x)"),
+            Ok((expr::let_("x", expr::a("f", "y"), expr::a("g", "x")), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("letrec f = x => f(x) in let y = f in y"),
+            Ok((
+                expr::letrec(
+                    "f",
+                    expr::l("x", expr::a("f", "x")),
+                    expr::let_("y", "f", "y")
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("(let x = :a in x)(:b)"),
+            Ok((expr::a(expr::let_("x", ":a", "x"), ":b"), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("x => let ID = y => y in ID(x)"),
+            Ok((
+                expr::l("x", expr::let_("ID", expr::l("y", "y"), expr::a("ID", "x"))),
+                ""
+            ))
+        );
+
+        // let で始まっていても let 式でなければ変数として読む
+        assert_eq!(expr().easy_parse("l(e)"), Ok((expr::a("l", "e"), "")));
+    }
 }
@@ -0,0 +1,251 @@
+use crate::engine::Command;
+use combine::easy::{Error, Errors, Info};
+use combine::stream::PointerOffset;
+use std::fmt::{self, Display};
+
+/// 構文解析に用いた文法
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Grammar {
+    EcmaScript,
+    LazyK,
+    Combinator,
+    Iota,
+    Jot,
+}
+
+impl Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Grammar::EcmaScript => write!(f, "ECMAScript"),
+            Grammar::LazyK => write!(f, "Lazy K"),
+            Grammar::Combinator => write!(f, "SKI combinators"),
+            Grammar::Iota => write!(f, "Iota"),
+            Grammar::Jot => write!(f, "Jot"),
+        }
+    }
+}
+
+/// 入力文字列中の範囲 (start..end)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// 構文解析のエラー
+///
+/// 複数の文法を試した場合は、最も先まで読み進められた位置のエラーを報告する
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// エラー箇所の範囲 (バイト単位)
+    pub span: Span,
+
+    /// エラー箇所の範囲 (文字単位)
+    pub char_span: Span,
+
+    /// 予期しないトークン (入力の終端に達した場合は None)
+    pub unexpected: Option<String>,
+
+    /// 期待されたトークン
+    pub expected: Vec<String>,
+
+    /// 試した文法
+    pub grammars: Vec<Grammar>,
+}
+
+impl ParseError {
+    /// combine のエラーから ParseError を作る
+    pub(crate) fn from_errors(
+        input: &str,
+        grammar: Grammar,
+        errors: Errors<char, &str, PointerOffset<str>>,
+    ) -> ParseError {
+        let errors = errors.map_position(|p| p.translate_position(input));
+        let mut error = ParseError::new(input, grammar, errors.position);
+
+        for e in errors.errors {
+            match e {
+                Error::Unexpected(Info::Static("end of input")) => {}
+                Error::Unexpected(info) => error.unexpected = Some(info_to_string(info)),
+                Error::Expected(info) => push_unique(&mut error.expected, info_to_string(info)),
+                // このクレートのパーサーは Message や Other を生成しない
+                Error::Message(_) | Error::Other(_) => {}
+            }
+        }
+
+        error
+    }
+
+    /// 入力の途中で構文解析が終わってしまった場合のエラーを作る
+    pub(crate) fn unexpected_rest(input: &str, grammar: Grammar, rest: &str) -> ParseError {
+        let mut error = ParseError::new(input, grammar, input.len() - rest.len());
+        error.expected.push("end of input".to_string());
+        error
+    }
+
+    /// 関数定義・削除以外のコマンドが与えられた場合のエラーを作る
+    pub(crate) fn unexpected_command(
+        input: &str,
+        grammar: Grammar,
+        command: &Command,
+    ) -> ParseError {
+        let mut error = ParseError::new(input, grammar, 0);
+        error.span.end = input.len();
+        error.char_span.end = input.chars().count();
+        error.unexpected = Some(command.to_string());
+        error.expected.push("update or delete command".to_string());
+        error
+    }
+
+    /// 部分文字列を解析した結果のエラーの位置を、元の文字列における位置にずらす
+    pub(crate) fn offset(mut self, bytes: usize, chars: usize) -> ParseError {
+        self.span.start += bytes;
+        self.span.end += bytes;
+        self.char_span.start += chars;
+        self.char_span.end += chars;
+        self
+    }
+
+    /// 別の文法で試した結果のエラーと統合する
+    ///
+    /// より先まで読み進められた方のエラーを残し、同じ位置であれば期待されたトークンを合わせる
+    pub(crate) fn merge(self, other: ParseError) -> ParseError {
+        let mut grammars = self.grammars.clone();
+        for grammar in &other.grammars {
+            if !grammars.contains(grammar) {
+                grammars.push(*grammar);
+            }
+        }
+
+        let mut merged = if self.span.start < other.span.start {
+            other
+        } else if other.span.start < self.span.start {
+            self
+        } else {
+            let mut merged = self;
+            for expected in other.expected {
+                push_unique(&mut merged.expected, expected);
+            }
+            merged
+        };
+
+        merged.grammars = grammars;
+        merged
+    }
+
+    fn new(input: &str, grammar: Grammar, position: usize) -> ParseError {
+        let unexpected = input[position..].chars().next();
+        let width = unexpected.map_or(0, char::len_utf8);
+        let char_start = input[..position].chars().count();
+
+        ParseError {
+            span: Span {
+                start: position,
+                end: position + width,
+            },
+            char_span: Span {
+                start: char_start,
+                end: char_start + unexpected.map_or(0, |_| 1),
+            },
+            unexpected: unexpected.map(|c| c.to_string()),
+            expected: Vec::new(),
+            grammars: vec![grammar],
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Parse error at {}", self.char_span.start)?;
+
+        match &self.unexpected {
+            Some(token) => write!(f, ": unexpected `{}`", token)?,
+            None => write!(f, ": unexpected end of input")?,
+        }
+
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", self.expected.join(" or "))?;
+        }
+
+        let grammars: Vec<_> = self.grammars.iter().map(Grammar::to_string).collect();
+        write!(f, " (tried {})", grammars.join(", "))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn info_to_string(info: Info<char, &str>) -> String {
+    match info {
+        Info::Token(c) => c.to_string(),
+        Info::Range(s) => s.to_string(),
+        Info::Owned(s) => s,
+        Info::Static(s) => s.to_string(),
+    }
+}
+
+fn push_unique(v: &mut Vec<String>, s: String) {
+    if !v.contains(&s) {
+        v.push(s);
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_command, parse_expr};
+
+    #[test]
+    fn test_span() {
+        // Lazy K の文法で入力の終端まで読み進めたところでエラーになる
+        let err = parse_expr("```sii").unwrap_err();
+        assert_eq!(err.span, Span { start: 6, end: 6 });
+        assert_eq!(err.unexpected, None);
+        assert!(err.expected.contains(&"`".to_string()));
+
+        let err = parse_expr("x(y))").unwrap_err();
+        assert_eq!(err.span, Span { start: 4, end: 5 });
+        assert_eq!(err.unexpected, Some(")".to_string()));
+        assert_eq!(err.expected, vec!["end of input".to_string()]);
+    }
+
+    #[test]
+    fn test_char_span() {
+        // 多バイト文字の後ろでも文字単位の位置を報告する
+        let err = parse_expr("λx.x)").unwrap_err();
+        assert_eq!(err.char_span, Span { start: 4, end: 5 });
+        assert_eq!(err.span, Span { start: 5, end: 6 });
+    }
+
+    #[test]
+    fn test_grammars() {
+        let err = parse_command("x(y))").unwrap_err();
+        assert_eq!(err.grammars, vec![Grammar::EcmaScript, Grammar::LazyK]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let input = "abcd";
+        let e1 = ParseError::new(input, Grammar::EcmaScript, 1);
+        let e2 = ParseError::new(input, Grammar::LazyK, 2);
+
+        let merged = e1.clone().merge(e2.clone());
+        assert_eq!(merged.span, Span { start: 2, end: 3 });
+        assert_eq!(merged.grammars, vec![Grammar::EcmaScript, Grammar::LazyK]);
+
+        let merged = e2.merge(e1);
+        assert_eq!(merged.span, Span { start: 2, end: 3 });
+        assert_eq!(merged.grammars, vec![Grammar::LazyK, Grammar::EcmaScript]);
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "x)";
+        let err = ParseError::unexpected_rest(input, Grammar::EcmaScript, ")");
+        assert_eq!(
+            err.to_string(),
+            "Parse error at 1: unexpected `)`, expected end of input (tried ECMAScript)"
+        );
+    }
+}
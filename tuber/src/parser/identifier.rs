@@ -1,6 +1,8 @@
-use combine::parser::char::{char, digit, lower, spaces, upper};
+use combine::error::StreamError;
+use combine::parser::char::{char, digit, lower, spaces, string};
 use combine::parser::choice::choice;
-use combine::{many1, ParseError, Parser, Stream};
+use combine::stream::StreamErrorFor;
+use combine::{attempt, many, many1, optional, satisfy, ParseError, Parser, Stream};
 
 use crate::expr::Identifier;
 
@@ -12,6 +14,51 @@ where
     spaces().with(choice((short_identifier(), iota(), long_identifier())))
 }
 
+/// 関数定義の左辺に書く関数名
+///
+/// 数字だけからなる名前は式の中で数値リテラルとして読まれ、参照できないので拒否する
+pub fn function_name<Input>() -> impl Parser<Input, Output = Identifier>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    identifier().and_then(|id| {
+        if id.is_numeral() {
+            Err(StreamErrorFor::<Input>::expected_static_message(
+                "function name that is not a numeral",
+            ))
+        } else {
+            Ok(id)
+        }
+    })
+}
+
+/// モジュール名で修飾されていてもよい識別子 (`bool::TRUE` など)
+pub fn qualified_identifier<Input>() -> impl Parser<Input, Output = Identifier>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    spaces()
+        .with(optional(attempt(module_name().skip(string("::")))))
+        .and(identifier())
+        .map(|(module, id)| match module {
+            Some(module) => Identifier::qualified(&module, id.as_str()),
+            None => id,
+        })
+}
+
+/// 英小文字で始まり、英小文字・数字・`_` が続くモジュール名
+pub fn module_name<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    lower()
+        .and(many(choice((lower(), digit(), char('_')))))
+        .map(|(c, cs): (char, String)| format!("{}{}", c, cs))
+}
+
 fn short_identifier<Input>() -> impl Parser<Input, Output = Identifier>
 where
     Input: Stream<Token = char>,
@@ -29,7 +76,13 @@ where
     Input: Stream<Token = char>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
 {
-    many1(choice((digit(), upper(), char('_')))).map(|s: String| s.into())
+    // Λ は型抽象に使うので、直後に続いても識別子には含めない (`:_Λα.x`)
+    many1(choice((
+        digit(),
+        satisfy(|c: char| c.is_uppercase() && c != 'Λ'),
+        char('_'),
+    )))
+    .map(|s: String| s.into())
 }
 
 fn iota<Input>() -> impl Parser<Input, Output = Identifier>
@@ -59,6 +112,58 @@ mod tests {
         assert!(identifier().easy_parse("^abc").is_err());
     }
 
+    #[test]
+    fn test_function_name() {
+        assert_eq!(function_name().easy_parse("f"), Ok(("f".into(), "")));
+        assert_eq!(function_name().easy_parse("N42"), Ok(("N42".into(), "")));
+
+        assert!(function_name().easy_parse("21").is_err());
+    }
+
+    #[test]
+    fn test_qualified_identifier() {
+        assert_eq!(
+            qualified_identifier().easy_parse("bool::TRUE"),
+            Ok(("bool::TRUE".into(), ""))
+        );
+        assert_eq!(
+            qualified_identifier().easy_parse("comb::sk"),
+            Ok(("comb::s".into(), "k"))
+        );
+        assert_eq!(
+            qualified_identifier().easy_parse("nat::42"),
+            Ok(("nat::42".into(), ""))
+        );
+        assert_eq!(
+            qualified_identifier().easy_parse("sii"),
+            Ok(("s".into(), "ii"))
+        );
+        assert_eq!(
+            qualified_identifier().easy_parse("TRUE"),
+            Ok(("TRUE".into(), ""))
+        );
+
+        assert!(qualified_identifier().easy_parse("bool::").is_err());
+        assert_eq!(
+            qualified_identifier().easy_parse("BOOL::TRUE"),
+            Ok(("BOOL".into(), "::TRUE"))
+        );
+    }
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(
+            module_name().easy_parse("bool"),
+            Ok(("bool".to_string(), ""))
+        );
+        assert_eq!(
+            module_name().easy_parse("u8_x::"),
+            Ok(("u8_x".to_string(), "::"))
+        );
+
+        assert!(module_name().easy_parse("Bool").is_err());
+    }
+
     #[test]
     fn test_short_identifier() {
         assert_eq!(short_identifier().easy_parse("a"), Ok(("a".into(), "")));
@@ -75,5 +180,9 @@ mod tests {
             long_identifier().easy_parse("ABCabc"),
             Ok(("ABC".into(), "abc"))
         );
+        assert_eq!(
+            long_identifier().easy_parse("_Λβ.x"),
+            Ok(("_".into(), "Λβ.x"))
+        );
     }
 }
@@ -0,0 +1,43 @@
+use combine::parser::char::{space, spaces, string};
+use combine::{skip_many1, ParseError, Parser, Stream};
+
+use super::identifier::module_name;
+use crate::engine::Command;
+
+/// `import bool` のようにモジュールを import するコマンド
+///
+/// ECMAScript スタイルと Lazy K スタイルで共通の構文
+pub fn import<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    spaces()
+        .with(string("import"))
+        .with(skip_many1(space()))
+        .with(module_name())
+        .map(Command::Import)
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::EasyParser;
+
+    #[test]
+    fn test_import() {
+        assert_eq!(
+            import().easy_parse("import bool"),
+            Ok((Command::Import("bool".to_string()), ""))
+        );
+        assert_eq!(
+            import().easy_parse("  import   nat "),
+            Ok((Command::Import("nat".to_string()), " "))
+        );
+
+        assert!(import().easy_parse("importbool").is_err());
+        assert!(import().easy_parse("import Bool").is_err());
+    }
+}
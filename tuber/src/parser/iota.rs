@@ -0,0 +1,74 @@
+use crate::expr::{self, Expr};
+use crate::parser::error::{Grammar, ParseError};
+use combine::parser::char::{char, spaces};
+use combine::parser::choice::choice;
+use combine::{parser, EasyParser, Parser, Stream};
+
+/// Lazy K の Iota 記法 (`*` による関数適用と ι を表す `i`) で書かれた式を解析する
+///
+/// `i` は変数 ι になる
+pub fn parse_expr(s: &str) -> Result<Expr, ParseError> {
+    let (expr, rest) = expr()
+        .easy_parse(s)
+        .map_err(|e| ParseError::from_errors(s, Grammar::Iota, e))?;
+
+    if rest.is_empty() {
+        Ok(expr)
+    } else {
+        Err(ParseError::unexpected_rest(s, Grammar::Iota, rest))
+    }
+}
+
+pub fn expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: combine::ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    expr_()
+}
+
+parser! {
+    fn expr_[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: combine::ParseError<char, Input::Range, Input::Position>,
+    ]
+    {
+        spaces().with(choice((
+            char('i').map(|_| expr::v("ι")),
+            char('*')
+                .with(expr())
+                .and(expr())
+                .map(|(lhs, rhs)| expr::a(lhs, rhs)),
+        )))
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(parse_expr("i"), Ok(expr::v("ι")));
+        assert_eq!(parse_expr("*ii"), Ok(expr::a("ι", "ι")));
+        assert_eq!(
+            parse_expr("*i*i*ii"),
+            Ok(expr::a("ι", expr::a("ι", expr::a("ι", "ι"))))
+        );
+        assert_eq!(parse_expr(" * *ii i"), Ok(expr::a(expr::a("ι", "ι"), "ι")));
+    }
+
+    #[test]
+    fn test_parse_expr_error() {
+        assert!(parse_expr("").is_err());
+        assert!(parse_expr("*i").is_err());
+        assert!(parse_expr("ii").is_err());
+
+        let err = parse_expr("*ik").unwrap_err();
+        assert_eq!(err.span.start, 2);
+        assert_eq!(err.grammars, vec![Grammar::Iota]);
+    }
+}
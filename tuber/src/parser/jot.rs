@@ -0,0 +1,61 @@
+use crate::expr::{self, Expr};
+use crate::parser::error::{Grammar, ParseError};
+use combine::parser::char::{char, spaces};
+use combine::parser::choice::choice;
+use combine::{many1, EasyParser, Parser, Stream};
+
+/// Jot の `0` と `1` の列で書かれたプログラムを s, k, i からなる式に変換する
+///
+/// 空のプログラム (I) は他の文法と同様に受け付けない
+pub fn parse_expr(s: &str) -> Result<Expr, ParseError> {
+    let (expr, rest) = expr()
+        .easy_parse(s)
+        .map_err(|e| ParseError::from_errors(s, Grammar::Jot, e))?;
+
+    if rest.is_empty() {
+        Ok(expr)
+    } else {
+        Err(ParseError::unexpected_rest(s, Grammar::Jot, rest))
+    }
+}
+
+pub fn expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: combine::ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    let bit = choice((char('0'), char('1'))).skip(spaces());
+    spaces().with(many1(bit)).map(|bits: String| {
+        // [F0] = [F] S K, [F1] = λxy.[F](xy) = S(K[F])
+        bits.chars().fold(expr::v("i"), |f, bit| match bit {
+            '0' => expr::a(expr::a(f, "s"), "k"),
+            _ => expr::a("s", expr::a("k", f)),
+        })
+    })
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(parse_expr("0"), Ok(expr::a(expr::a("i", "s"), "k")));
+        assert_eq!(parse_expr("1"), Ok(expr::a("s", expr::a("k", "i"))));
+        assert_eq!(
+            parse_expr(" 1 0 "),
+            Ok(expr::a(expr::a(expr::a("s", expr::a("k", "i")), "s"), "k"))
+        );
+    }
+
+    #[test]
+    fn test_parse_expr_error() {
+        assert!(parse_expr("").is_err());
+
+        let err = parse_expr("102").unwrap_err();
+        assert_eq!(err.span.start, 2);
+        assert_eq!(err.grammars, vec![Grammar::Jot]);
+    }
+}
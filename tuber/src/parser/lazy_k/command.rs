@@ -1,8 +1,10 @@
-use combine::parser::char::{char, digit, spaces, string};
+use combine::parser::char::{char, digit, space, spaces, string};
 use combine::parser::choice::choice;
-use combine::{attempt, count_min_max, eof, many1, parser, ParseError, Parser, Stream};
+use combine::{attempt, count_min_max, eof, many1, parser, skip_many1, ParseError, Parser, Stream};
 
-use super::super::identifier::identifier;
+use super::super::data::data;
+use super::super::identifier::{function_name, identifier, qualified_identifier};
+use super::super::import::import;
 use super::expression::expr;
 use crate::engine::Command;
 use crate::expr::{Expr, Identifier};
@@ -16,6 +18,10 @@ where
         From<::std::num::ParseIntError>,
 {
     choice((
+        attempt(import()),
+        attempt(data()),
+        attempt(metrics()),
+        attempt(type_of()),
         attempt(update()),
         eval(),
         attempt(eval_head()),
@@ -74,7 +80,7 @@ parser! {
                     lhs.1.push(i);
                     lhs
                 }),
-            identifier().map(|i| (i, vec![])),
+            function_name().map(|i| (i, vec![])),
         )))
     }
 }
@@ -140,7 +146,7 @@ where
 {
     spaces()
         .skip(char('?'))
-        .with(identifier())
+        .with(qualified_identifier())
         .map(Command::Query)
 }
 
@@ -161,7 +167,7 @@ where
     <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
         From<::std::num::ParseIntError>,
 {
-    let level = count_min_max(1, 4, char('~')).map(|str: String| str.len() as u8);
+    let level = count_min_max(1, 6, char('~')).map(|str: String| str.len() as u8);
 
     spaces()
         .with(level)
@@ -169,6 +175,34 @@ where
         .map(|(level, expr)| Command::Unlambda(level, expr))
 }
 
+fn metrics<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    spaces()
+        .with(string("metrics"))
+        .with(skip_many1(space()))
+        .with(expr())
+        .map(Command::Metrics)
+}
+
+fn type_of<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    spaces()
+        .with(string(":t"))
+        .with(skip_many1(space()))
+        .with(expr())
+        .map(Command::TypeOf)
+}
+
 // ========================================================================== //
 
 #[cfg(test)]
@@ -267,6 +301,11 @@ mod tests {
         );
 
         assert_eq!(command().easy_parse("?"), Ok((Command::Context, "")));
+
+        assert_eq!(
+            command().easy_parse("import bool"),
+            Ok((Command::Import("bool".to_string()), ""))
+        );
     }
 
     #[test]
@@ -374,6 +413,39 @@ mod tests {
             Ok((Command::Unlambda(4, expr::l("x", "x")), ""))
         );
 
-        assert!(unlambda().easy_parse("~~~~~^x.x").is_err());
+        assert_eq!(
+            unlambda().easy_parse("~~~~~^x.x"),
+            Ok((Command::Unlambda(5, expr::l("x", "x")), ""))
+        );
+
+        assert_eq!(
+            unlambda().easy_parse("~~~~~~^x.x"),
+            Ok((Command::Unlambda(6, expr::l("x", "x")), ""))
+        );
+
+        assert!(unlambda().easy_parse("~~~~~~~^x.x").is_err());
+    }
+
+    #[test]
+    fn test_metrics() {
+        assert_eq!(
+            command().easy_parse("metrics `sk"),
+            Ok((Command::Metrics(expr::a("s", "k")), ""))
+        );
+        assert!(command().easy_parse("metrics").is_err());
+    }
+
+    #[test]
+    fn test_type_of() {
+        assert_eq!(
+            command().easy_parse(":t `sk"),
+            Ok((Command::TypeOf(expr::a("s", "k")), ""))
+        );
+
+        // `:t` だけならシンボル
+        assert!(!matches!(
+            command().easy_parse(":t"),
+            Ok((Command::TypeOf(_), _))
+        ));
     }
 }
@@ -1,8 +1,12 @@
-use super::super::identifier::identifier;
+use super::super::identifier::{identifier, qualified_identifier};
+use super::super::literal::literal;
+use super::super::types::{ty, type_param};
+use super::super::utils::keyword;
 use crate::expr::{self, Expr, Identifier};
+use crate::types::Type;
 use combine::parser::char::{char, spaces};
 use combine::parser::choice::choice;
-use combine::{attempt, many1, parser, ParseError, Parser, Stream};
+use combine::{attempt, many1, optional, parser, ParseError, Parser, Stream};
 
 pub fn expr<Input>() -> impl Parser<Input, Output = Expr>
 where
@@ -26,6 +30,9 @@ parser! {
         spaces().with(choice((
             apply(),
             lambda(),
+            type_lambda(),
+            attempt(let_expr()),
+            literal(),
             symbol(),
             var(),
         )))
@@ -39,7 +46,7 @@ where
     Input: Stream<Token = char>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
 {
-    identifier().map(expr::v)
+    qualified_identifier().map(expr::v)
 }
 
 fn symbol<Input>() -> impl Parser<Input, Output = Expr>
@@ -59,14 +66,30 @@ parser! {
             From<::std::num::ParseIntError>,
     ]
     {
+        // 右側に [T] と書くと型の適用になる
         spaces()
             .with(char('`'))
             .with(expr())
-            .and(expr())
-            .map(|(lhs, rhs)| expr::a(lhs, rhs))
+            .and(choice((
+                attempt(spaces().with(char('[')))
+                    .with(ty())
+                    .skip(spaces().with(char(']')))
+                    .map(Arg::Type),
+                expr().map(Arg::Expr),
+            )))
+            .map(|(lhs, rhs)| match rhs {
+                Arg::Expr(rhs) => expr::a(lhs, rhs),
+                Arg::Type(ty) => expr::ta(lhs, ty),
+            })
     }
 }
 
+// 関数適用の右側
+enum Arg {
+    Expr(Expr),
+    Type(Type),
+}
+
 parser! {
     fn lambda[Input]()(Input) -> Expr
     where [
@@ -84,22 +107,86 @@ parser! {
             )
             .and(expr())
             .map(|(params, mut body)| {
-                for param in params.into_iter().rev() {
-                    body = expr::l(param, body)
+                for (param, ty) in params.into_iter().rev() {
+                    body = match ty {
+                        Some(ty) => expr::lt(param, ty, body),
+                        None => expr::l(param, body),
+                    }
                 }
                 body
             })
     }
 }
 
-fn params<Input>() -> impl Parser<Input, Output = Vec<Identifier>>
+// 各引数には型注釈を付けてもよい (`λx:Nat y.M`)
+fn params<Input>() -> impl Parser<Input, Output = Vec<(Identifier, Option<Type>)>>
 where
     Input: Stream<Token = char>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
     <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
         From<::std::num::ParseIntError>,
 {
-    many1(attempt(spaces().with(identifier())))
+    many1(attempt(
+        spaces()
+            .with(identifier())
+            .and(optional(attempt(spaces().with(char(':'))).with(ty()))),
+    ))
+}
+
+parser! {
+    fn type_lambda[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+            From<::std::num::ParseIntError>,
+    ]
+    {
+        spaces()
+            .with(char('Λ'))
+            .with(
+                many1(attempt(spaces().with(type_param())))
+                .skip(spaces().with(char('.')))
+            )
+            .and(expr())
+            .map(|(params, mut body): (Vec<Identifier>, Expr)| {
+                for param in params.into_iter().rev() {
+                    body = expr::tl(param, body)
+                }
+                body
+            })
+    }
+}
+
+// ========================================================================== //
+
+parser! {
+    fn let_expr[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+            From<::std::num::ParseIntError>,
+    ]
+    {
+        // let x = M in N または letrec f = M in N
+        choice((
+            keyword("letrec").map(|_| true),
+            keyword("let").map(|_| false),
+        ))
+        .and(identifier())
+        .skip(spaces().with(char('=')))
+        .and(expr())
+        .skip(keyword("in"))
+        .and(expr())
+        .map(|(((recursive, name), value), body)| {
+            if recursive {
+                expr::letrec(name, value, body)
+            } else {
+                expr::let_(name, value, body)
+            }
+        })
+    }
 }
 
 // ========================================================================== //
@@ -107,6 +194,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::expr::{church_numeral, scott_string};
     use combine::EasyParser;
 
     #[test]
@@ -115,6 +203,22 @@ mod tests {
         assert_eq!(expr().easy_parse("`ab"), Ok((expr::a("a", "b"), "")));
     }
 
+    #[test]
+    fn test_literal() {
+        assert_eq!(
+            expr().easy_parse("``ADD 2 21"),
+            Ok((
+                expr::a(expr::a("ADD", church_numeral(2)), church_numeral(21)),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("`f\"ab\""),
+            Ok((expr::a("f", scott_string("ab")), ""))
+        );
+        assert_eq!(expr().easy_parse("X2"), Ok((expr::v("X2"), "")));
+    }
+
     #[test]
     fn test_var() {
         assert!(var().easy_parse(":abc").is_err());
@@ -205,4 +309,60 @@ mod tests {
             Ok((expr::l("a", expr::l("b", expr::l("c", "d"))), ""))
         );
     }
+
+    #[test]
+    fn test_typed() {
+        let nat = || Type::named("Nat");
+        let alpha = || Type::named("α");
+
+        assert_eq!(
+            expr().easy_parse("λx:Nat.x"),
+            Ok((expr::lt("x", nat(), "x"), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("^F:Nat -> Nat x.`F x"),
+            Ok((
+                expr::lt(
+                    "F",
+                    Type::arrow(nat(), nat()),
+                    expr::l("x", expr::a("F", "x"))
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("Λαβ.λx:α.x"),
+            Ok((
+                expr::tl("α", expr::tl("β", expr::lt("x", alpha(), "x"))),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("``ID[Nat]X"),
+            Ok((expr::a(expr::ta("ID", nat()), "X"), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("` ID [ ∀α. α ]"),
+            Ok((expr::ta("ID", Type::forall("α", alpha())), ""))
+        );
+    }
+
+    #[test]
+    fn test_let() {
+        assert_eq!(
+            expr().easy_parse("let x = `fy in `gx"),
+            Ok((expr::let_("x", expr::a("f", "y"), expr::a("g", "x")), ""))
+        );
+        assert_eq!(
+            expr().easy_parse("letrec F = λx.`Fx in `F X"),
+            Ok((
+                expr::letrec("F", expr::l("x", expr::a("F", "x")), expr::a("F", "X")),
+                ""
+            ))
+        );
+        assert_eq!(
+            expr().easy_parse("`let x = :a in x:b"),
+            Ok((expr::a(expr::let_("x", ":a", "x"), ":b"), ""))
+        );
+    }
 }
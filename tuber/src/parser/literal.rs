@@ -0,0 +1,119 @@
+use combine::error::StreamError;
+use combine::parser::char::{char, digit, upper};
+use combine::parser::choice::choice;
+use combine::stream::StreamErrorFor;
+use combine::{
+    attempt, between, many, many1, none_of, not_followed_by, ParseError, Parser, Stream,
+};
+
+use crate::expr::{church_numeral, scott_string, Expr};
+
+/// 数値リテラル・文字列リテラル
+///
+/// 数値は Church 数に、文字列は Church 数で表した文字のリストに脱糖する
+pub fn literal<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    choice((numeral(), string_literal()))
+}
+
+/// 数値リテラルと文字列リテラル中の文字の上限
+///
+/// Church 数は値と同じ深さの式になるので、これより大きい数は構文解析の時点で拒否する
+/// 任意の大きさの数値リテラルは受け付けない (README にも記載している意図的な制限)
+pub const MAX_NUMERAL: usize = 1000;
+
+// `42` は数値リテラル, `42A` や `4_2` は識別子
+fn numeral<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    attempt(many1(digit()).skip(not_followed_by(choice((upper(), char('_'))))))
+        .and_then(|s: String| s.parse::<usize>())
+        .and_then(|n| match n {
+            0..=MAX_NUMERAL => Ok(church_numeral(n)),
+            _ => Err(StreamErrorFor::<Input>::expected_static_message(
+                "numeral up to 1000 (larger literals are not supported)",
+            )),
+        })
+}
+
+fn string_literal<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    let escaped = char('\\').with(choice((char('"'), char('\\'), char('n').map(|_| '\n'))));
+
+    between(
+        char('"'),
+        char('"'),
+        many(choice((escaped, none_of("\"\\".chars())))),
+    )
+    .and_then(|s: String| {
+        // 文字も Church 数になるので、数値リテラルと同じ上限を超える文字は拒否する
+        if s.chars().all(|c| c as usize <= MAX_NUMERAL) {
+            Ok(scott_string(&s))
+        } else {
+            Err(StreamErrorFor::<Input>::expected_static_message(
+                "characters up to U+03E8 in a string literal",
+            ))
+        }
+    })
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::EasyParser;
+
+    #[test]
+    fn test_numeral() {
+        assert_eq!(literal().easy_parse("0"), Ok((church_numeral(0), "")));
+        assert_eq!(literal().easy_parse("21"), Ok((church_numeral(21), "")));
+        assert_eq!(literal().easy_parse("3x"), Ok((church_numeral(3), "x")));
+
+        assert!(literal().easy_parse("42A").is_err());
+        assert!(literal().easy_parse("4_2").is_err());
+        assert!(literal().easy_parse("99999999999999999999999").is_err());
+
+        assert_eq!(
+            literal().easy_parse("1000"),
+            Ok((church_numeral(MAX_NUMERAL), ""))
+        );
+        assert!(literal().easy_parse("1001").is_err());
+        assert!(literal().easy_parse("100000000000").is_err());
+    }
+
+    #[test]
+    fn test_string_literal() {
+        assert_eq!(literal().easy_parse(r#""""#), Ok((scott_string(""), "")));
+        assert_eq!(
+            literal().easy_parse(r#""ab"c"#),
+            Ok((scott_string("ab"), "c"))
+        );
+        assert_eq!(
+            literal().easy_parse(r#""a\"\\\n""#),
+            Ok((scott_string("a\"\\\n"), ""))
+        );
+
+        assert!(literal().easy_parse(r#""ab"#).is_err());
+
+        // U+03E8 (1000) までの文字は受け付け、それより大きい文字は拒否する
+        assert_eq!(
+            literal().easy_parse("\"é\u{3E8}\""),
+            Ok((scott_string("é\u{3E8}"), ""))
+        );
+        assert!(literal().easy_parse("\"あ\"").is_err());
+        assert!(literal().easy_parse("\"😀\"").is_err());
+    }
+}
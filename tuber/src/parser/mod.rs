@@ -1,28 +1,174 @@
+mod combinator;
+mod data;
 mod ecmascript;
+mod error;
 mod identifier;
+mod import;
+mod iota;
+mod jot;
 mod lazy_k;
+mod literal;
+mod script;
+mod types;
 mod utils;
 
 use crate::engine::Command;
 use crate::expr::Expr;
-use anyhow::Result;
+pub use combinator::parse_expr as parse_expr_with_combinator_style;
 pub use ecmascript::parse_command as parse_command_with_ecmascript_style;
 pub use ecmascript::parse_expr as parse_expr_with_ecmascript_style;
 pub use ecmascript::parse_update_or_delete as parse_update_or_delete_with_ecmascript_style;
+pub use error::{Grammar, ParseError, Span};
+pub use iota::parse_expr as parse_expr_with_iota_style;
+pub use jot::parse_expr as parse_expr_with_jot_style;
 pub use lazy_k::parse_command as parse_command_with_lazy_k_style;
 pub use lazy_k::parse_expr as parse_expr_with_lazy_k_style;
 pub use lazy_k::parse_update_or_delete as parse_update_or_delete_with_lazy_k_style;
+pub use script::{parse_definitions, parse_script, Statement};
 
-pub fn parse_expr(input: &str) -> Result<Expr> {
-    parse_expr_with_ecmascript_style(input).or_else(|_err| parse_expr_with_lazy_k_style(input))
+/// 式を解析する
+///
+/// ECMAScript 風と Lazy K 風の文法で読めない場合に限り、
+/// 他の処理系から貼り付けたプログラムとして SKI コンビネータ記法・Iota・Jot の順に試す
+/// (`SKK` は識別子、`101` は数値リテラルとして読まれる)
+///
+/// ただし `0` と `1` だけからなり、`0` で始まるか4桁以上ある入力は最初から Jot として読む
+/// (`1000` のように上限以下の数値リテラルとして読める入力は除く)
+pub fn parse_expr(input: &str) -> Result<Expr, ParseError> {
+    if is_jot(input) {
+        return parse_expr_with_jot_style(input);
+    }
+
+    parse_expr_with_ecmascript_style(input)
+        .or_else(|err| parse_expr_with_lazy_k_style(input).map_err(|e| err.merge(e)))
+        .or_else(|err| parse_expr_with_combinator_style(input).map_err(|e| err.merge(e)))
+        .or_else(|err| parse_expr_with_iota_style(input).map_err(|e| err.merge(e)))
+        .or_else(|err| parse_expr_with_jot_style(input).map_err(|e| err.merge(e)))
+}
+
+// 数値リテラルとしては読まない 0 と 1 の列か
+fn is_jot(input: &str) -> bool {
+    let bits: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+    bits.iter().all(|c| *c == '0' || *c == '1')
+        && (bits.len() >= 4 || (bits.len() >= 2 && bits[0] == '0'))
+        && !is_numeral(input.trim())
+}
+
+// 上限以下の数値リテラルとして読める入力か
+fn is_numeral(input: &str) -> bool {
+    !input.starts_with('0')
+        && input.chars().all(|c| c.is_ascii_digit())
+        && input
+            .parse::<usize>()
+            .is_ok_and(|n| n <= literal::MAX_NUMERAL)
 }
 
-pub fn parse_command(input: &str) -> Result<Command> {
+/// コマンドを解析する
+///
+/// parse_expr と同じく、Jot として読む入力は最初から Jot の式として評価する
+pub fn parse_command(input: &str) -> Result<Command, ParseError> {
+    if is_jot(input) {
+        return parse_expr_with_jot_style(input).map(Command::Eval);
+    }
+
     parse_command_with_ecmascript_style(input)
-        .or_else(|_err| parse_command_with_lazy_k_style(input))
+        .or_else(|err| parse_command_with_lazy_k_style(input).map_err(|e| err.merge(e)))
 }
 
-pub fn parse_update_or_delete(input: &str) -> Result<Command> {
+pub fn parse_update_or_delete(input: &str) -> Result<Command, ParseError> {
     parse_update_or_delete_with_ecmascript_style(input)
-        .or_else(|_err| parse_update_or_delete_with_lazy_k_style(input))
+        .or_else(|err| parse_update_or_delete_with_lazy_k_style(input).map_err(|e| err.merge(e)))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{Aliases, Reducer, ReductionStrategy};
+    use crate::context::Context;
+    use crate::expr;
+
+    #[test]
+    fn test_parse_expr_detects_esolang_syntax() {
+        assert_eq!(
+            parse_expr("S(KS)K"),
+            Ok(expr::a(expr::a("s", expr::a("k", "s")), "k"))
+        );
+        assert_eq!(parse_expr("*i*ii"), Ok(expr::a("ι", expr::a("ι", "ι"))));
+        assert_eq!(
+            parse_expr("1 0"),
+            Ok(expr::a(expr::a(expr::a("s", expr::a("k", "i")), "s"), "k"))
+        );
+
+        // 既存の文法で読める入力はそのまま読む
+        assert_eq!(parse_expr("SKK"), Ok(expr::v("SKK")));
+        assert_eq!(parse_expr("10"), Ok(expr::church_numeral(10)));
+        assert_eq!(parse_expr("101"), Ok(expr::church_numeral(101)));
+        assert_eq!(parse_expr("i"), Ok(expr::v("i")));
+    }
+
+    #[test]
+    fn test_parse_expr_prefers_jot() {
+        // 0 で始まるか4桁以上の 0 と 1 の列は数値リテラルではなく Jot として読む
+        for input in ["11100", "0101", "01", "1001", "1111111111111111111111111"] {
+            assert_eq!(parse_expr(input), parse_expr_with_jot_style(input));
+            assert_eq!(
+                parse_command(input),
+                parse_expr_with_jot_style(input).map(Command::Eval)
+            );
+        }
+
+        // 上限以下の数値リテラルは Jot として読まない
+        assert_eq!(parse_expr("1000"), Ok(expr::church_numeral(1000)));
+        assert_eq!(
+            parse_command("1000"),
+            Ok(Command::Eval(expr::church_numeral(1000)))
+        );
+
+        // Jot の 11100 は K と同じ振る舞いをする
+        let k = parse_expr("11100").unwrap();
+        let reducer = Reducer::new(
+            Context::default(),
+            Aliases::new(),
+            expr::a(expr::a(k, ":a"), ":b"),
+            ReductionStrategy::NormalOrder,
+        );
+        assert_eq!(reducer.last().map(|result| result.expr), Some(expr::s("a")));
+
+        assert_ne!(parse_expr("0101"), Ok(expr::church_numeral(101)));
+        assert_eq!(parse_expr("0"), Ok(expr::church_numeral(0)));
+    }
+
+    #[test]
+    fn test_parse_expr_rejects_large_numeral() {
+        assert_eq!(parse_expr("999"), Ok(expr::church_numeral(999)));
+
+        assert!(parse_expr("123456").is_err());
+        assert!(parse_expr("99999999999").is_err());
+    }
+
+    #[test]
+    fn test_parse_command_rejects_large_character() {
+        assert!(parse_command("\"café\"").is_ok());
+
+        assert!(parse_command("\"あ\"").is_err());
+        assert!(parse_command("\"😀\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_expr_error_lists_grammars() {
+        let err = parse_expr("*i)").unwrap_err();
+        assert_eq!(err.span.start, 2);
+        assert_eq!(
+            err.grammars,
+            vec![
+                Grammar::EcmaScript,
+                Grammar::LazyK,
+                Grammar::Combinator,
+                Grammar::Iota,
+                Grammar::Jot
+            ]
+        );
+    }
 }
@@ -0,0 +1,208 @@
+use super::error::{ParseError, Span};
+use super::{parse_command, parse_update_or_delete};
+use crate::engine::Command;
+
+/// スクリプト中の1つの文
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statement {
+    pub command: Command,
+
+    /// 文の範囲 (バイト単位)
+    pub span: Span,
+
+    /// 文の範囲 (文字単位)
+    pub char_span: Span,
+}
+
+/// 複数の文からなるスクリプトを解析する
+///
+/// 文は改行または `;` で区切る
+/// `#` から行末まではコメントとして読み飛ばす
+/// 文字列リテラル `"…"` の中の `;` と `#` は区切りやコメントとして扱わない
+pub fn parse_script(input: &str) -> Result<Vec<Statement>, ParseError> {
+    parse_statements(input, parse_command)
+}
+
+/// 関数定義・削除のみからなるスクリプトを解析する
+///
+/// 区切りとコメントの扱いは parse_script と同じ
+pub fn parse_definitions(input: &str) -> Result<Vec<Statement>, ParseError> {
+    parse_statements(input, parse_update_or_delete)
+}
+
+fn parse_statements<F>(input: &str, parse: F) -> Result<Vec<Statement>, ParseError>
+where
+    F: Fn(&str) -> Result<Command, ParseError>,
+{
+    split(input)
+        .into_iter()
+        .map(|(span, char_span)| {
+            let source = &input[span.start..span.end];
+            match parse(source) {
+                Ok(command) => Ok(Statement {
+                    command,
+                    span,
+                    char_span,
+                }),
+                Err(err) => Err(err.offset(span.start, char_span.start)),
+            }
+        })
+        .collect()
+}
+
+// 入力を文ごとに区切り、前後の空白を除いた範囲を返す
+fn split(input: &str) -> Vec<(Span, Span)> {
+    let mut statements = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut end = (0, 0);
+    let mut in_comment = false;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (chars, (bytes, c)) in input.char_indices().enumerate() {
+        if c == '\n' || (!in_comment && !in_string && c == ';') {
+            if let Some(start) = start.take() {
+                statements.push(span(start, end));
+            }
+            in_comment = false;
+            in_string = false;
+            escaped = false;
+        } else if in_comment || (!in_string && c.is_whitespace()) {
+            // 読み飛ばす
+        } else if !in_string && c == '#' {
+            in_comment = true;
+        } else {
+            // 文字列リテラルの中ではエスケープされていない `"` まで区切りとコメントを無視する
+            if escaped {
+                escaped = false;
+            } else if in_string && c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = !in_string;
+            }
+            start.get_or_insert((bytes, chars));
+            end = (bytes + c.len_utf8(), chars + 1);
+        }
+    }
+
+    if let Some(start) = start {
+        statements.push(span(start, end));
+    }
+
+    statements
+}
+
+fn span(start: (usize, usize), end: (usize, usize)) -> (Span, Span) {
+    (
+        Span {
+            start: start.0,
+            end: end.0,
+        },
+        Span {
+            start: start.1,
+            end: end.1,
+        },
+    )
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::command;
+    use crate::expr;
+    use crate::func;
+
+    #[test]
+    fn test_parse_script() {
+        let script = "\
+# 恒等関数
+i(x) = x
+k(x, y) = x; s(x, y, z) = x(z)(y(z))  # コメント
+
+!  s(k, k, :a)
+";
+
+        let statements = parse_script(script).unwrap();
+        let commands: Vec<_> = statements.iter().map(|s| s.command.clone()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                command::update(func::new("i", vec!["x"], "x")),
+                command::update(func::new("k", vec!["x", "y"], "x")),
+                command::update(func::new(
+                    "s",
+                    vec!["x", "y", "z"],
+                    expr::a(expr::a("x", "z"), expr::a("y", "z"))
+                )),
+                command::eval_last(expr::a(expr::a(expr::a("s", "k"), "k"), ":a")),
+            ]
+        );
+
+        let sources: Vec<_> = statements
+            .iter()
+            .map(|s| &script[s.span.start..s.span.end])
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                "i(x) = x",
+                "k(x, y) = x",
+                "s(x, y, z) = x(z)(y(z))",
+                "!  s(k, k, :a)"
+            ]
+        );
+
+        // 文字単位の範囲はマルチバイト文字を 1 文字と数える
+        assert_eq!(statements[0].char_span, Span { start: 7, end: 15 });
+        assert_eq!(statements[0].span, Span { start: 15, end: 23 });
+    }
+
+    #[test]
+    fn test_parse_script_empty() {
+        assert_eq!(parse_script(""), Ok(vec![]));
+        assert_eq!(parse_script("\n ; # comment\n;;"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_definitions() {
+        let statements = parse_definitions("i(x) = x\n``kxy = x\ni = i\nf = (f)").unwrap();
+        let commands: Vec<_> = statements.into_iter().map(|s| s.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                command::update(func::new("i", vec!["x"], "x")),
+                command::update(func::new("k", vec!["x", "y"], "x")),
+                command::del("i"),
+                command::update(func::new("f", Vec::<&str>::new(), "f")),
+            ]
+        );
+
+        assert!(parse_definitions("i(x) = x\n!i(:a)").is_err());
+    }
+
+    #[test]
+    fn test_parse_script_string_literal() {
+        let statements = parse_script("!\"a;b\"; !\"#\" # comment\n!\"\\\";\"").unwrap();
+        let commands: Vec<_> = statements.into_iter().map(|s| s.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                command::eval_last(expr::scott_string("a;b")),
+                command::eval_last(expr::scott_string("#")),
+                command::eval_last(expr::scott_string("\";")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_script_error() {
+        let script = "i(x) = x\nk(x, y) = x)";
+        let err = parse_script(script).unwrap_err();
+
+        assert_eq!(err.span, Span { start: 20, end: 21 });
+        assert_eq!(err.char_span, Span { start: 20, end: 21 });
+        assert_eq!(err.unexpected, Some(")".to_string()));
+    }
+}
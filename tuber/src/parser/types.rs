@@ -0,0 +1,151 @@
+use super::utils::parens;
+use crate::expr::Identifier;
+use crate::types::Type;
+use combine::parser::char::{alpha_num, char, spaces, string, upper};
+use combine::parser::choice::choice;
+use combine::{attempt, many, many1, optional, parser, satisfy, ParseError, Parser, Stream};
+
+/// 型注釈に書く型
+///
+/// `Nat` のような英大文字で始まる名前、型抽象で束縛された `α` のようなギリシャ文字、
+/// 右結合の関数型 `A -> B`、全称型 `∀α. T` からなる
+pub fn ty<Input>() -> impl Parser<Input, Output = Type>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    ty_()
+}
+
+parser! {
+    fn ty_[Input]()(Input) -> Type
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+            From<::std::num::ParseIntError>,
+    ]
+    {
+        spaces().with(choice((forall(), arrow())))
+    }
+}
+
+/// 型抽象で束縛する名前 (λ を除くギリシャ文字の小文字1文字)
+pub fn type_param<Input>() -> impl Parser<Input, Output = Identifier>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    satisfy(|c: char| ('α'..='ω').contains(&c) && c != 'λ').map(|c: char| c.to_string().into())
+}
+
+fn type_name<Input>() -> impl Parser<Input, Output = Identifier>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    upper()
+        .and(many(choice((alpha_num(), char('_')))))
+        .map(|(c, cs): (char, String)| format!("{}{}", c, cs).into())
+}
+
+fn forall<Input>() -> impl Parser<Input, Output = Type>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    char('∀')
+        .with(many1(attempt(spaces().with(type_param()))))
+        .skip(spaces().with(char('.')))
+        .and(ty())
+        .map(|(params, body): (Vec<Identifier>, Type)| {
+            params
+                .into_iter()
+                .rev()
+                .fold(body, |body, param| Type::forall(param, body))
+        })
+}
+
+fn arrow<Input>() -> impl Parser<Input, Output = Type>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    atom()
+        .and(optional(attempt(spaces().with(string("->"))).with(ty())))
+        .map(|(param, result)| match result {
+            Some(result) => Type::arrow(param, result),
+            None => param,
+        })
+}
+
+fn atom<Input>() -> impl Parser<Input, Output = Type>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    choice((
+        type_param().map(Type::Named),
+        type_name().map(Type::Named),
+        parens(ty()),
+    ))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::EasyParser;
+
+    #[test]
+    fn test_ty() {
+        let nat = || Type::named("Nat");
+        let alpha = || Type::named("α");
+
+        assert_eq!(ty().easy_parse("Nat"), Ok((nat(), "")));
+        assert_eq!(ty().easy_parse("α"), Ok((alpha(), "")));
+        assert_eq!(
+            ty().easy_parse("Nat -> α -> Nat"),
+            Ok((Type::arrow(nat(), Type::arrow(alpha(), nat())), ""))
+        );
+        assert_eq!(
+            ty().easy_parse("(Nat->α)->Nat"),
+            Ok((Type::arrow(Type::arrow(nat(), alpha()), nat()), ""))
+        );
+        assert_eq!(
+            ty().easy_parse("∀α β. α -> β"),
+            Ok((
+                Type::forall(
+                    "α",
+                    Type::forall("β", Type::arrow(alpha(), Type::named("β")))
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            ty().easy_parse("(∀α. α) -> Nat"),
+            Ok((Type::arrow(Type::forall("α", alpha()), nat()), ""))
+        );
+
+        // 後ろに続く文字は残す
+        assert_eq!(ty().easy_parse("Nat.x"), Ok((nat(), ".x")));
+        assert_eq!(ty().easy_parse("Nat, y"), Ok((nat(), ", y")));
+    }
+
+    #[test]
+    fn test_ty_error() {
+        assert!(ty().easy_parse("nat").is_err());
+        assert!(ty().easy_parse("λ").is_err());
+        assert!(ty().easy_parse("Nat ->").is_err());
+        assert!(ty().easy_parse("∀. Nat").is_err());
+    }
+}
@@ -1,5 +1,5 @@
-use combine::parser::char::{char, spaces};
-use combine::{ParseError, Parser, Stream};
+use combine::parser::char::{alpha_num, char, spaces, string};
+use combine::{attempt, not_followed_by, ParseError, Parser, Stream};
 
 pub fn token<Input, Output>(
     parser: impl Parser<Input, Output = Output>,
@@ -13,6 +13,19 @@ where
     spaces().with(parser).skip(spaces())
 }
 
+/// 英数字が直後に続かない予約語 (`let` や `in`)
+pub fn keyword<Input>(word: &'static str) -> impl Parser<Input, Output = &'static str>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    attempt(
+        spaces()
+            .with(string(word))
+            .skip(not_followed_by(alpha_num())),
+    )
+}
+
 pub fn parens<Input, Output>(
     parser: impl Parser<Input, Output = Output>,
 ) -> impl Parser<Input, Output = Output>
@@ -42,6 +55,15 @@ mod tests {
         assert!(token(char('a')).easy_parse("b").is_err());
     }
 
+    #[test]
+    fn test_keyword() {
+        assert_eq!(keyword("in").easy_parse(" in x"), Ok(("in", " x")));
+        assert_eq!(keyword("in").easy_parse("in(x)"), Ok(("in", "(x)")));
+
+        assert!(keyword("in").easy_parse("inx").is_err());
+        assert!(keyword("let").easy_parse("letrec").is_err());
+    }
+
     #[test]
     fn test_parens() {
         assert_eq!(parens(char('a')).easy_parse("(a)"), Ok(('a', "")));
@@ -1,4 +1,5 @@
 use crate::expr::Expr;
+use crate::types::Type;
 
 pub fn to_string(expr: &Expr) -> String {
     Compact::new(expr).to_string()
@@ -9,8 +10,16 @@ pub fn to_string(expr: &Expr) -> String {
 enum Compact<'a> {
     Variable(&'a str),
     Symbol(&'a str),
-    Apply(Box<Compact<'a>>, Vec<Compact<'a>>),
-    Lambda(Vec<&'a str>, Box<Compact<'a>>),
+    Apply(Box<Compact<'a>>, Vec<Arg<'a>>),
+    Lambda(Vec<(&'a str, Option<&'a Type>)>, Box<Compact<'a>>),
+    TypeLambda(Vec<&'a str>, Box<Compact<'a>>),
+    Let(bool, &'a str, Box<Compact<'a>>, Box<Compact<'a>>),
+}
+
+// 関数適用の引数 (式または型)
+enum Arg<'a> {
+    Expr(Compact<'a>),
+    Type(&'a Type),
 }
 
 impl<'a> Compact<'a> {
@@ -26,29 +35,55 @@ impl<'a> Compact<'a> {
                 Compact::Symbol(label)
             }
 
-            Expr::Apply { lhs, rhs } => {
-                let e1 = Compact::new(lhs);
-                let e2 = Compact::new(rhs);
-                match e1 {
-                    Compact::Apply(e1, mut es) => {
-                        es.push(e2);
-                        Compact::Apply(e1, es)
+            Expr::Apply { lhs, rhs } => Compact::new(lhs).push_arg(Arg::Expr(Compact::new(rhs))),
+
+            Expr::TypeApply { lhs, ty } => Compact::new(lhs).push_arg(Arg::Type(ty)),
+
+            Expr::Lambda { param, ty, body } => {
+                let param = (param.as_str(), ty.as_deref());
+                let body = Compact::new(body);
+                match body {
+                    Compact::Lambda(mut params, body) => {
+                        params.push(param);
+                        Compact::Lambda(params, body)
                     }
-                    _ => Compact::Apply(Box::new(e1), vec![e2]),
+                    _ => Compact::Lambda(vec![param], Box::new(body)),
                 }
             }
 
-            Expr::Lambda { param, body } => {
+            Expr::TypeLambda { param, body } => {
                 let param = param.as_str();
                 let body = Compact::new(body);
                 match body {
-                    Compact::Lambda(mut params, body) => {
+                    Compact::TypeLambda(mut params, body) => {
                         params.push(param);
-                        Compact::Lambda(params, body)
+                        Compact::TypeLambda(params, body)
                     }
-                    _ => Compact::Lambda(vec![param], Box::new(body)),
+                    _ => Compact::TypeLambda(vec![param], Box::new(body)),
                 }
             }
+
+            Expr::Let {
+                recursive,
+                name,
+                value,
+                body,
+            } => Compact::Let(
+                *recursive,
+                name.as_str(),
+                Box::new(Compact::new(value)),
+                Box::new(Compact::new(body)),
+            ),
+        }
+    }
+
+    fn push_arg(self, arg: Arg<'a>) -> Compact<'a> {
+        match self {
+            Compact::Apply(e, mut args) => {
+                args.push(arg);
+                Compact::Apply(e, args)
+            }
+            e => Compact::Apply(Box::new(e), vec![arg]),
         }
     }
 
@@ -59,45 +94,96 @@ impl<'a> Compact<'a> {
             Compact::Symbol(label) => format!(":{}", label),
 
             Compact::Apply(e, args) => {
-                let args = args
-                    .iter()
-                    .map(|arg| arg.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ");
+                // 連続する式の引数は (x, y)、型の引数は <A, B> にまとめる
+                let mut groups: Vec<(bool, Vec<String>)> = Vec::new();
+                for arg in args {
+                    let (is_type, arg) = match arg {
+                        Arg::Expr(e) => (false, e.to_string()),
+                        Arg::Type(ty) => (true, ty.to_string()),
+                    };
+                    match groups.last_mut() {
+                        Some((last, group)) if *last == is_type => group.push(arg),
+                        _ => groups.push((is_type, vec![arg])),
+                    }
+                }
+                let args = groups
+                    .into_iter()
+                    .map(|(is_type, group)| {
+                        if is_type {
+                            format!("<{}>", group.join(", "))
+                        } else {
+                            format!("({})", group.join(", "))
+                        }
+                    })
+                    .collect::<String>();
 
                 match **e {
                     Compact::Variable(label) => {
-                        format!("{}({})", label, args)
+                        format!("{}{}", label, args)
                     }
                     Compact::Symbol(label) => {
-                        format!(":{}({})", label, args)
+                        format!(":{}{}", label, args)
                     }
                     _ => {
-                        format!("({})({})", e.to_string(), args)
+                        format!("({}){}", e.to_string(), args)
                     }
                 }
             }
 
             Compact::Lambda(params, body) => {
-                if params.len() == 1 {
-                    format!("{} => {}", params[0], body.to_string())
+                if let [(param, None)] = params.as_slice() {
+                    format!("{} => {}", param, body.to_string())
                 } else {
-                    format!(
-                        "({}) => {}",
-                        params
-                            .iter()
-                            .map(|arg| arg.to_string())
-                            .rev()
-                            .collect::<Vec<_>>()
-                            .join(", "),
-                        body.to_string()
-                    )
+                    format!("{} => {}", params_to_string(params), body.to_string())
+                }
+            }
+
+            Compact::TypeLambda(params, body) => {
+                let params = params
+                    .iter()
+                    .rev()
+                    .map(|param| param.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                // 型抽象の直後のラムダ抽象は引数を括弧で囲み、<α>(x: α) => x のように書く
+                match body.as_ref() {
+                    Compact::Lambda(lambda_params, lambda_body) => format!(
+                        "<{}>{} => {}",
+                        params,
+                        params_to_string(lambda_params),
+                        lambda_body.to_string()
+                    ),
+                    _ => format!("<{}> => {}", params, body.to_string()),
                 }
             }
+
+            Compact::Let(recursive, name, value, body) => format!(
+                "{} {} = {} in {}",
+                if *recursive { "letrec" } else { "let" },
+                name,
+                value.to_string(),
+                body.to_string()
+            ),
         }
     }
 }
 
+fn params_to_string(params: &[(&str, Option<&Type>)]) -> String {
+    format!(
+        "({})",
+        params
+            .iter()
+            .rev()
+            .map(|(param, ty)| match ty {
+                Some(ty) => format!("{}: {}", param, ty),
+                None => param.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
 // ========================================================================== //
 
 #[cfg(test)]
@@ -134,4 +220,60 @@ mod tests {
         let e = expr::a(expr::l("x", "a"), "y");
         assert_eq!(to_string(&e), "(x => a)(y)");
     }
+
+    #[test]
+    fn test_to_string_typed() {
+        let nat = || Type::named("Nat");
+        let alpha = || Type::named("α");
+
+        let e = expr::lt("x", nat(), "x");
+        assert_eq!(to_string(&e), "(x: Nat) => x");
+
+        let e = expr::lt(
+            "f",
+            Type::arrow(nat(), nat()),
+            expr::l("x", expr::a("f", "x")),
+        );
+        assert_eq!(to_string(&e), "(f: Nat -> Nat, x) => f(x)");
+
+        let e = expr::tl("α", expr::tl("β", expr::lt("x", alpha(), "x")));
+        assert_eq!(to_string(&e), "<α, β>(x: α) => x");
+
+        let e = expr::tl("α", expr::l("x", "x"));
+        assert_eq!(to_string(&e), "<α>(x) => x");
+
+        let e = expr::tl("α", "ID");
+        assert_eq!(to_string(&e), "<α> => ID");
+
+        let e = expr::a(
+            expr::a(expr::ta(expr::ta("PAIR", nat()), alpha()), "x"),
+            "y",
+        );
+        assert_eq!(to_string(&e), "PAIR<Nat, α>(x, y)");
+
+        let e = expr::ta(expr::a("f", "x"), nat());
+        assert_eq!(to_string(&e), "f(x)<Nat>");
+
+        let e = expr::a(
+            expr::ta(expr::tl("α", expr::lt("x", alpha(), "x")), nat()),
+            "y",
+        );
+        assert_eq!(to_string(&e), "(<α>(x: α) => x)<Nat>(y)");
+    }
+
+    #[test]
+    fn test_to_string_let() {
+        let e = expr::let_("x", expr::a("f", "y"), expr::a("g", "x"));
+        assert_eq!(to_string(&e), "let x = f(y) in g(x)");
+
+        let e = expr::letrec(
+            "f",
+            expr::l("x", expr::a("f", "x")),
+            expr::let_("y", "f", "y"),
+        );
+        assert_eq!(to_string(&e), "letrec f = x => f(x) in let y = f in y");
+
+        let e = expr::a(expr::let_("x", ":a", "x"), ":b");
+        assert_eq!(to_string(&e), "(let x = :a in x)(:b)");
+    }
 }
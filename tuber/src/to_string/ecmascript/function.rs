@@ -1,8 +1,12 @@
 use super::expression;
+use crate::expr::Expr;
 use crate::func::Func;
 
 pub fn to_string(func: &Func) -> String {
-    if func.arity() == 0 {
+    if is_self_reference(func) {
+        // `f = f` は削除として読まれるので、括弧で囲んで定義として読めるようにする
+        format!("{} = ({})", func.name(), func.name())
+    } else if func.arity() == 0 {
         format!("{} = {}", func.name(), expression::to_string(func.body()))
     } else {
         format!(
@@ -18,6 +22,11 @@ pub fn to_string(func: &Func) -> String {
     }
 }
 
+/// 引数がなく、本体が自身の名前だけの関数 (`f = (f)`) か
+pub fn is_self_reference(func: &Func) -> bool {
+    func.arity() == 0 && matches!(func.body(), Expr::Variable(id) if id.as_str() == func.name())
+}
+
 // ========================================================================== //
 
 #[cfg(test)]
@@ -12,6 +12,7 @@ pub fn to_string(command: &Command) -> String {
         Command::EvalTail(len, e) => format!("!-{} {}", len, expression::to_string(e)),
         Command::Query(i) => format!("? {}", i),
         Command::Context => "?".to_string(),
+        Command::Import(module) => format!("import {}", module),
         Command::Unlambda(level, e) => {
             format!(
                 "{} {}",
@@ -19,6 +20,9 @@ pub fn to_string(command: &Command) -> String {
                 expression::to_string(e)
             )
         }
+        Command::Metrics(e) => format!("metrics {}", expression::to_string(e)),
+        Command::TypeOf(e) => format!(":t {}", expression::to_string(e)),
+        Command::Data(data) => data.to_string(),
     }
 }
 
@@ -125,6 +129,12 @@ mod tests {
         assert_eq!(to_string(&command), "?");
     }
 
+    #[test]
+    fn test_import() {
+        let command = command::import("bool");
+        assert_eq!(to_string(&command), "import bool");
+    }
+
     #[test]
     fn test_unlambda() {
         let command = command::unlambda(1, expr::l("x", "y"));
@@ -139,4 +149,10 @@ mod tests {
         let command = command::unlambda(4, expr::l("x", "y"));
         assert_eq!(to_string(&command), "~~~~ λx.y");
     }
+
+    #[test]
+    fn test_metrics() {
+        let command = command::metrics(expr::l("x", "y"));
+        assert_eq!(to_string(&command), "metrics λx.y");
+    }
 }
@@ -1,4 +1,5 @@
 use crate::expr::Expr;
+use crate::types::Type;
 use regex::Regex;
 use std::fmt::Display;
 
@@ -12,7 +13,7 @@ fn tokens(expr: &Expr) -> Vec<Token<'_>> {
     match expr {
         Expr::Variable(i) => {
             let label = i.as_str();
-            if is_upper_ident(label) {
+            if is_upper_ident(i.local_name()) {
                 vec![Token::UpperIdent(Ident::Variable(label))]
             } else {
                 vec![Token::LowerIdent(Ident::Variable(label))]
@@ -21,7 +22,7 @@ fn tokens(expr: &Expr) -> Vec<Token<'_>> {
 
         Expr::Symbol(i) => {
             let label = i.as_str();
-            if is_upper_ident(label) {
+            if is_upper_ident(i.local_name()) {
                 vec![Token::UpperIdent(Ident::Symbol(label))]
             } else {
                 vec![Token::LowerIdent(Ident::Symbol(label))]
@@ -36,11 +37,14 @@ fn tokens(expr: &Expr) -> Vec<Token<'_>> {
             rhs
         }
 
-        Expr::Lambda { param, body } => {
+        Expr::Lambda { param, ty, body } => {
             let mut body = tokens(body);
             body.push(Token::Dot);
+            if let Some(ty) = ty {
+                body.push(Token::Annotation(ty));
+            }
             let label = param.as_str();
-            if is_upper_ident(label) {
+            if is_upper_ident(param.local_name()) {
                 body.push(Token::UpperIdent(Ident::Variable(label)));
             } else {
                 body.push(Token::LowerIdent(Ident::Variable(label)));
@@ -48,6 +52,43 @@ fn tokens(expr: &Expr) -> Vec<Token<'_>> {
             body.push(Token::Lambda);
             body
         }
+
+        Expr::TypeLambda { param, body } => {
+            let mut body = tokens(body);
+            body.push(Token::Dot);
+            body.push(Token::TypeParam(param.as_str()));
+            body.push(Token::TypeLambda);
+            body
+        }
+
+        Expr::TypeApply { lhs, ty } => {
+            let mut lhs = tokens(lhs);
+            let mut tokens = vec![Token::TypeArg(ty)];
+            tokens.append(&mut lhs);
+            tokens.push(Token::Apply);
+            tokens
+        }
+
+        Expr::Let {
+            recursive,
+            name,
+            value,
+            body,
+        } => {
+            let mut body = tokens(body);
+            let mut value = tokens(value);
+            body.push(Token::In);
+            body.append(&mut value);
+            body.push(Token::Equal);
+            let label = name.as_str();
+            if is_upper_ident(name.local_name()) {
+                body.push(Token::UpperIdent(Ident::Variable(label)));
+            } else {
+                body.push(Token::LowerIdent(Ident::Variable(label)));
+            }
+            body.push(Token::Let(*recursive));
+            body
+        }
     }
 }
 
@@ -67,6 +108,15 @@ fn tokens_to_string(tokens: &mut Vec<Token>) -> String {
                         str.push_str(format!("{} ", ident1).as_str());
                         tokens.push(t2);
                     }
+                    // 修飾された識別子の前に英小文字が続くとモジュール名の一部になってしまう
+                    (
+                        Token::LowerIdent(ident1),
+                        Token::UpperIdent(Ident::Variable(label))
+                        | Token::LowerIdent(Ident::Variable(label)),
+                    ) if label.contains("::") => {
+                        str.push_str(format!("{} ", ident1).as_str());
+                        tokens.push(t2);
+                    }
                     (t1, _) => {
                         str.push_str(format!("{}", t1).as_str());
                         tokens.push(t2);
@@ -92,6 +142,13 @@ enum Token<'a> {
     Apply,
     Lambda,
     Dot,
+    Annotation(&'a Type),
+    TypeLambda,
+    TypeParam(&'a str),
+    TypeArg(&'a Type),
+    Let(bool),
+    Equal,
+    In,
 }
 
 impl Display for Token<'_> {
@@ -101,6 +158,14 @@ impl Display for Token<'_> {
             Token::Apply => write!(f, "`"),
             Token::Lambda => write!(f, "λ"),
             Token::Dot => write!(f, "."),
+            Token::Annotation(ty) => write!(f, ":{}", ty),
+            Token::TypeLambda => write!(f, "Λ"),
+            Token::TypeParam(param) => write!(f, "{}", param),
+            Token::TypeArg(ty) => write!(f, "[{}]", ty),
+            Token::Let(false) => write!(f, "let "),
+            Token::Let(true) => write!(f, "letrec "),
+            Token::Equal => write!(f, " = "),
+            Token::In => write!(f, " in "),
         }
     }
 }
@@ -141,6 +206,50 @@ mod tests {
         assert_eq!(to_string(&expr::a("X", ":Y")), "`X:Y");
         assert_eq!(to_string(&expr::a(":X", "Y")), "`:X Y");
         assert_eq!(to_string(&expr::a(":X", ":Y")), "`:X:Y");
+        assert_eq!(to_string(&expr::a("bool::NOT", "Y")), "`bool::NOT Y");
+        assert_eq!(to_string(&expr::a("x", "bool::NOT")), "`x bool::NOT");
+        assert_eq!(to_string(&expr::a("comb::s", "x")), "`comb::sx");
+        assert_eq!(to_string(&expr::a("X", "comb::s")), "`Xcomb::s");
+    }
+
+    #[test]
+    fn test_to_string_typed() {
+        let nat = || Type::named("Nat");
+        let alpha = || Type::named("α");
+
+        assert_eq!(to_string(&expr::lt("x", nat(), "x")), "λx:Nat.x");
+        assert_eq!(
+            to_string(&expr::lt("F", Type::arrow(nat(), nat()), expr::a("F", "X"))),
+            "λF:Nat -> Nat.`F X"
+        );
+        assert_eq!(
+            to_string(&expr::tl("α", expr::lt("x", alpha(), "x"))),
+            "Λα.λx:α.x"
+        );
+        assert_eq!(
+            to_string(&expr::a(expr::ta("ID", nat()), "X")),
+            "``ID[Nat]X"
+        );
+    }
+
+    #[test]
+    fn test_to_string_let() {
+        assert_eq!(
+            to_string(&expr::let_("x", expr::a("f", "y"), expr::a("g", "x"))),
+            "let x = `fy in `gx"
+        );
+        assert_eq!(
+            to_string(&expr::letrec(
+                "F",
+                expr::l("x", expr::a("F", "x")),
+                expr::a("F", "X")
+            )),
+            "letrec F = λx.`Fx in `F X"
+        );
+        assert_eq!(
+            to_string(&expr::a(expr::let_("x", ":a", "x"), ":b")),
+            "`let x = :a in x:b"
+        );
     }
 
     #[test]
@@ -1,8 +1,14 @@
+use super::super::ecmascript;
 use crate::func::Func;
 use regex::Regex;
 use std::fmt::Display;
 
 pub fn to_string(func: &Func) -> String {
+    // Lazy K スタイルには括弧がないので、`f = f` と区別できる ECMAScript スタイルの形で書く
+    if ecmascript::function::is_self_reference(func) {
+        return ecmascript::function::to_string(func);
+    }
+
     let mut tokens = tokens(func);
     format!(
         "{}{} = {}",
@@ -0,0 +1,167 @@
+use super::Type;
+use crate::expr::{Direction, Expr, Identifier};
+use crate::to_string::{DisplayStyle, Format};
+use std::fmt::Display;
+
+/// 式に型が付かなかった理由を表現する
+///
+/// path は型の付かなかった部分式の位置 (式の根からたどる Direction の並び)
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeError {
+    /// 単一化に無限の型が必要になった (`x => x(x)` など)
+    Infinite {
+        path: Vec<Direction>,
+        term: Box<Expr>,
+        var: Type,
+        ty: Type,
+    },
+
+    /// 型が一致しなかった (`((x: Nat) => x)(TRUE)` など)
+    Mismatch {
+        path: Vec<Direction>,
+        term: Box<Expr>,
+        expected: Type,
+        actual: Type,
+    },
+
+    /// 全称型でない式に型を適用した
+    NotPolymorphic {
+        path: Vec<Direction>,
+        term: Box<Expr>,
+        ty: Type,
+    },
+
+    /// 型の付かない関数を使っている
+    Func {
+        path: Vec<Direction>,
+        id: Identifier,
+        error: Box<TypeError>,
+    },
+
+    /// 再帰的に定義された関数を使っている
+    Recursive {
+        path: Vec<Direction>,
+        id: Identifier,
+    },
+}
+
+impl TypeError {
+    pub fn path(&self) -> &[Direction] {
+        match self {
+            TypeError::Infinite { path, .. }
+            | TypeError::Mismatch { path, .. }
+            | TypeError::NotPolymorphic { path, .. }
+            | TypeError::Func { path, .. }
+            | TypeError::Recursive { path, .. } => path,
+        }
+    }
+}
+
+/// 部分式は Expr の Display と同じく Lazy K 風の文法で表示する
+impl Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(&DisplayStyle::LazyK))
+    }
+}
+
+/// 部分式を利用者が使っている文法で表示する
+impl Format for TypeError {
+    fn format(&self, style: &DisplayStyle) -> String {
+        match self {
+            TypeError::Infinite { term, var, ty, .. } => format!(
+                "cannot construct the infinite type {} = {} in {}",
+                var,
+                ty,
+                term.format(style)
+            ),
+            TypeError::Mismatch {
+                term,
+                expected,
+                actual,
+                ..
+            } => format!(
+                "couldn't match expected type {} with actual type {} in {}",
+                expected,
+                actual,
+                term.format(style)
+            ),
+            TypeError::NotPolymorphic { term, ty, .. } => format!(
+                "cannot apply a type to {} of type {}, which is not polymorphic",
+                term.format(style),
+                ty
+            ),
+            TypeError::Func { id, error, .. } => {
+                format!("`{}` has no type: {}", id, error.format(style))
+            }
+            TypeError::Recursive { id, .. } => format!("`{}` is defined recursively", id),
+        }
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_display() {
+        let error = TypeError::Infinite {
+            path: vec![Direction::Body],
+            term: Box::new(expr::a("x", "x")),
+            var: Type::Var(0),
+            ty: Type::arrow(Type::Var(0), Type::Var(1)),
+        };
+        assert_eq!(
+            error.to_string(),
+            "cannot construct the infinite type a = a -> b in `xx"
+        );
+
+        let error = TypeError::Func {
+            path: vec![Direction::Lhs],
+            id: "F".into(),
+            error: Box::new(TypeError::Recursive {
+                path: vec![Direction::Body],
+                id: "F".into(),
+            }),
+        };
+        assert_eq!(
+            error.to_string(),
+            "`F` has no type: `F` is defined recursively"
+        );
+        assert_eq!(error.path(), &[Direction::Lhs]);
+
+        let error = TypeError::Mismatch {
+            path: vec![],
+            term: Box::new(expr::a("f", "x")),
+            expected: Type::named("Nat"),
+            actual: Type::arrow(Type::Var(0), Type::Var(0)),
+        };
+        assert_eq!(
+            error.to_string(),
+            "couldn't match expected type Nat with actual type a -> a in `fx"
+        );
+        assert_eq!(
+            error.format(&DisplayStyle::EcmaScript),
+            "couldn't match expected type Nat with actual type a -> a in f(x)"
+        );
+
+        let error = TypeError::Func {
+            path: vec![],
+            id: "F".into(),
+            error: Box::new(TypeError::Infinite {
+                path: vec![Direction::Body],
+                term: Box::new(expr::a("x", "x")),
+                var: Type::Var(0),
+                ty: Type::arrow(Type::Var(0), Type::Var(1)),
+            }),
+        };
+        assert_eq!(
+            error.format(&DisplayStyle::EcmaScript),
+            "`F` has no type: cannot construct the infinite type a = a -> b in x(x)"
+        );
+    }
+}
@@ -0,0 +1,591 @@
+use super::ty::Renamer;
+use super::{Type, TypeError};
+use crate::context::Context;
+use crate::expr::{Direction, Expr, Identifier};
+use std::collections::{HashMap, HashSet};
+
+/// 式の主要型を Hindley–Milner の型推論で求める
+///
+/// Context で定義された関数と let 式で束縛した変数は、使うたびに別々の型変数で具体化する (let 多相)
+/// 定義されていない変数やシンボルは、同じ名前ごとに1つの未知の型をもつとみなす
+///
+/// 型注釈のあるラムダ抽象の引数は注釈の型をもち、型抽象 Λα. M は ∀α. T 型、
+/// 型適用 M[T] は M の全称型を T で具体化した型をもつ (System F)
+pub fn infer_type(context: &Context, expr: &Expr) -> Result<Type, TypeError> {
+    let mut inference = Inference::new(context);
+    let ty = inference.infer(expr, &mut Vec::new(), &mut Vec::new())?;
+    Ok(inference.resolve(&ty).normalize())
+}
+
+struct Inference<'a> {
+    context: &'a Context,
+
+    /// 型変数ごとに単一化で決まった型
+    bindings: Vec<Option<Type>>,
+
+    /// 型を推論した関数の、一般化した型
+    funcs: HashMap<Identifier, Result<Type, TypeError>>,
+
+    /// 型を推論している途中の関数 (再帰の検出に使う)
+    visiting: Vec<Identifier>,
+
+    /// 定義されていない変数とシンボルの型
+    variables: HashMap<Identifier, Type>,
+    symbols: HashMap<Identifier, Type>,
+
+    /// 外側の型抽象で束縛された名前と、型の中でそれを表す名前
+    ///
+    /// 同じ名前の型抽象が入れ子になった場合は、内側の名前を付け替えて区別する
+    type_params: Vec<(Identifier, Identifier)>,
+
+    /// 付け替えた名前の個数
+    renamed: usize,
+}
+
+impl<'a> Inference<'a> {
+    fn new(context: &'a Context) -> Self {
+        Self {
+            context,
+            bindings: Vec::new(),
+            funcs: HashMap::new(),
+            visiting: Vec::new(),
+            variables: HashMap::new(),
+            symbols: HashMap::new(),
+            type_params: Vec::new(),
+            renamed: 0,
+        }
+    }
+
+    // bound は外側のラムダ抽象や let 式で束縛された変数とその型、path は expr の位置
+    fn infer(
+        &mut self,
+        expr: &Expr,
+        bound: &mut Vec<Binding>,
+        path: &mut Vec<Direction>,
+    ) -> Result<Type, TypeError> {
+        match expr {
+            Expr::Variable(id) => {
+                if let Some(binding) = bound.iter().rev().find(|binding| &binding.id == id) {
+                    let generic = binding.generic.clone();
+                    return Ok(self.instantiate(&binding.ty, |n| generic.contains(&n)));
+                }
+                if self.context.get(id).is_some() {
+                    let ty = self.func_type(id, path)?;
+                    return Ok(self.instantiate(&ty, |_| true));
+                }
+                Ok(match self.variables.get(id) {
+                    Some(ty) => ty.clone(),
+                    None => {
+                        let ty = self.fresh();
+                        self.variables.insert(id.clone(), ty.clone());
+                        ty
+                    }
+                })
+            }
+            Expr::Symbol(id) => Ok(match self.symbols.get(id) {
+                Some(ty) => ty.clone(),
+                None => {
+                    let ty = self.fresh();
+                    self.symbols.insert(id.clone(), ty.clone());
+                    ty
+                }
+            }),
+            Expr::Apply { lhs, rhs } => {
+                path.push(Direction::Lhs);
+                let lhs_ty = self.infer(lhs, bound, path)?;
+                path.pop();
+
+                path.push(Direction::Rhs);
+                let rhs_ty = self.infer(rhs, bound, path)?;
+                path.pop();
+
+                // 引数から決まる型を実際の型、関数の型を期待される型として単一化する
+                let result = self.fresh();
+                self.unify(&Type::arrow(rhs_ty, result.clone()), &lhs_ty)
+                    .map_err(|error| self.type_error(error, expr, path))?;
+                Ok(result)
+            }
+            Expr::Lambda { param, ty, body } => {
+                let param_ty = match ty {
+                    Some(ty) => self.annotation(ty),
+                    None => self.fresh(),
+                };
+                bound.push(Binding::new(param.clone(), param_ty.clone()));
+                path.push(Direction::Body);
+                let body_ty = self.infer(body, bound, path);
+                path.pop();
+                bound.pop();
+                Ok(Type::arrow(param_ty, body_ty?))
+            }
+            Expr::TypeLambda { param, body } => {
+                let name = if self.type_params.iter().any(|(_, name)| name == param) {
+                    self.rename(param)
+                } else {
+                    param.clone()
+                };
+                self.type_params.push((param.clone(), name.clone()));
+                path.push(Direction::Body);
+                let body_ty = self.infer(body, bound, path);
+                path.pop();
+                self.type_params.pop();
+                Ok(Type::forall(name, self.resolve(&body_ty?)))
+            }
+            Expr::TypeApply { lhs, ty } => {
+                path.push(Direction::Lhs);
+                let lhs_ty = self.infer(lhs, bound, path);
+                path.pop();
+
+                match self.resolve(&lhs_ty?) {
+                    Type::Forall(param, body) => Ok(body.substitute(&param, &self.annotation(ty))),
+                    lhs_ty => Err(TypeError::NotPolymorphic {
+                        path: path.clone(),
+                        term: lhs.clone(),
+                        ty: lhs_ty.normalize(),
+                    }),
+                }
+            }
+            Expr::Let {
+                recursive,
+                name,
+                value,
+                body,
+            } => {
+                // letrec の値の中では name は単相的な型をもつ
+                path.push(Direction::Value);
+                let value_ty = if *recursive {
+                    let ty = self.fresh();
+                    bound.push(Binding::new(name.clone(), ty.clone()));
+                    let value_ty = self.infer(value, bound, path);
+                    bound.pop();
+                    value_ty.and_then(|value_ty| {
+                        self.unify(&value_ty, &ty)
+                            .map_err(|error| self.type_error(error, value, path))
+                            .map(|_| ty)
+                    })
+                } else {
+                    self.infer(value, bound, path)
+                };
+                path.pop();
+
+                let value_ty = self.resolve(&value_ty?);
+                let generic = self.generalize(&value_ty, bound);
+                bound.push(Binding {
+                    id: name.clone(),
+                    ty: value_ty,
+                    generic,
+                });
+                path.push(Direction::Body);
+                let body_ty = self.infer(body, bound, path);
+                path.pop();
+                bound.pop();
+                body_ty
+            }
+        }
+    }
+
+    fn type_error(&self, error: UnifyError, expr: &Expr, path: &[Direction]) -> TypeError {
+        let mut renamer = Renamer::default();
+        match error {
+            UnifyError::Infinite(var, ty) => TypeError::Infinite {
+                path: path.to_vec(),
+                term: Box::new(expr.clone()),
+                var: renamer.rename(&var),
+                ty: renamer.rename(&ty),
+            },
+            UnifyError::Mismatch(actual, expected) => TypeError::Mismatch {
+                path: path.to_vec(),
+                term: Box::new(expr.clone()),
+                expected: renamer.rename(&self.resolve(&expected)),
+                actual: renamer.rename(&self.resolve(&actual)),
+            },
+        }
+    }
+
+    // ty の型変数のうち、外側で束縛された変数や未定義の変数とシンボルの型に現れないもの
+    fn generalize(&self, ty: &Type, bound: &[Binding]) -> HashSet<usize> {
+        let mut vars = HashSet::new();
+        self.collect_vars(ty, &mut vars);
+
+        let mut fixed = HashSet::new();
+        for binding in bound {
+            let mut binding_vars = HashSet::new();
+            self.collect_vars(&binding.ty, &mut binding_vars);
+            fixed.extend(binding_vars.difference(&binding.generic));
+        }
+        for ty in self.variables.values().chain(self.symbols.values()) {
+            self.collect_vars(ty, &mut fixed);
+        }
+
+        vars.retain(|n| !fixed.contains(n));
+        vars
+    }
+
+    fn collect_vars(&self, ty: &Type, vars: &mut HashSet<usize>) {
+        match self.prune(ty) {
+            Type::Var(n) => {
+                vars.insert(n);
+            }
+            Type::Named(_) => {}
+            Type::Arrow(param, result) => {
+                self.collect_vars(&param, vars);
+                self.collect_vars(&result, vars);
+            }
+            Type::Forall(_, body) => self.collect_vars(&body, vars),
+        }
+    }
+
+    // 型注釈の中の、型抽象で束縛された名前を付け替えた名前に置き換える
+    fn annotation(&self, ty: &Type) -> Type {
+        self.type_params
+            .iter()
+            .rev()
+            .filter(|(param, name)| param != name)
+            .fold(ty.clone(), |ty, (param, name)| {
+                ty.substitute(param, &Type::Named(name.clone()))
+            })
+    }
+
+    // 他の名前と衝突しない名前 (α'1 など) を作る
+    fn rename(&mut self, param: &Identifier) -> Identifier {
+        self.renamed += 1;
+        format!("{}'{}", param.as_str(), self.renamed).into()
+    }
+
+    // 関数の定義から型を推論し、一般化した型を返す
+    fn func_type(&mut self, id: &Identifier, path: &[Direction]) -> Result<Type, TypeError> {
+        if self.visiting.contains(id) {
+            return Err(TypeError::Recursive {
+                path: path.to_vec(),
+                id: id.clone(),
+            });
+        }
+
+        let result = match self.funcs.get(id) {
+            Some(result) => result.clone(),
+            None => {
+                let expr = Expr::from(self.context.get(id).unwrap().clone());
+
+                // 定義の中の未定義の変数やシンボルは、使う側の式のものとは別に扱う
+                let variables = std::mem::take(&mut self.variables);
+                let symbols = std::mem::take(&mut self.symbols);
+                let type_params = std::mem::take(&mut self.type_params);
+                self.visiting.push(id.clone());
+
+                let result = self
+                    .infer(&expr, &mut Vec::new(), &mut Vec::new())
+                    .map(|ty| self.resolve(&ty).normalize());
+
+                self.visiting.pop();
+                self.variables = variables;
+                self.symbols = symbols;
+                self.type_params = type_params;
+
+                self.funcs.insert(id.clone(), result.clone());
+                result
+            }
+        };
+
+        result.map_err(|error| TypeError::Func {
+            path: path.to_vec(),
+            id: id.clone(),
+            error: Box::new(error),
+        })
+    }
+
+    fn fresh(&mut self) -> Type {
+        self.bindings.push(None);
+        Type::Var(self.bindings.len() - 1)
+    }
+
+    // 一般化した型の型変数のうち generic なものを、新しい型変数に置き換える
+    fn instantiate(&mut self, ty: &Type, generic: impl Fn(usize) -> bool) -> Type {
+        fn go(
+            inference: &mut Inference,
+            ty: &Type,
+            generic: &dyn Fn(usize) -> bool,
+            vars: &mut HashMap<usize, Type>,
+        ) -> Type {
+            match ty {
+                Type::Var(n) if !generic(*n) => ty.clone(),
+                Type::Var(n) => match vars.get(n) {
+                    Some(var) => var.clone(),
+                    None => {
+                        let var = inference.fresh();
+                        vars.insert(*n, var.clone());
+                        var
+                    }
+                },
+                Type::Named(_) => ty.clone(),
+                Type::Arrow(param, result) => {
+                    let param = go(inference, param, generic, vars);
+                    Type::arrow(param, go(inference, result, generic, vars))
+                }
+                Type::Forall(param, body) => {
+                    Type::forall(param.clone(), go(inference, body, generic, vars))
+                }
+            }
+        }
+        go(self, ty, &generic, &mut HashMap::new())
+    }
+
+    // 型変数に決まった型をたどり、先頭が決まっていない型変数か関数型になるまで進める
+    fn prune(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        while let Type::Var(n) = ty {
+            match &self.bindings[n] {
+                Some(bound) => ty = bound.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    // 決まった型をすべて代入する
+    fn resolve(&self, ty: &Type) -> Type {
+        match self.prune(ty) {
+            Type::Arrow(param, result) => Type::arrow(self.resolve(&param), self.resolve(&result)),
+            Type::Forall(param, body) => Type::forall(param, self.resolve(&body)),
+            ty => ty,
+        }
+    }
+
+    fn occurs(&self, var: usize, ty: &Type) -> bool {
+        match self.prune(ty) {
+            Type::Var(n) => n == var,
+            Type::Named(_) => false,
+            Type::Arrow(param, result) => self.occurs(var, &param) || self.occurs(var, &result),
+            Type::Forall(_, body) => self.occurs(var, &body),
+        }
+    }
+
+    // a を実際の型、b を期待される型として単一化する
+    fn unify(&mut self, a: &Type, b: &Type) -> Result<(), UnifyError> {
+        match (self.prune(a), self.prune(b)) {
+            (Type::Var(x), Type::Var(y)) if x == y => Ok(()),
+            (Type::Var(x), ty) | (ty, Type::Var(x)) => {
+                if self.occurs(x, &ty) {
+                    return Err(UnifyError::Infinite(Type::Var(x), self.resolve(&ty)));
+                }
+                self.bindings[x] = Some(ty);
+                Ok(())
+            }
+            (Type::Named(x), Type::Named(y)) if x == y => Ok(()),
+            (Type::Arrow(a_param, a_result), Type::Arrow(b_param, b_result)) => {
+                self.unify(&a_param, &b_param)?;
+                self.unify(&a_result, &b_result)
+            }
+            // 全称型どうしは、束縛された名前を同じ新しい名前に揃えて比べる
+            (Type::Forall(a_param, a_body), Type::Forall(b_param, b_body)) => {
+                let name = Type::Named(self.rename(&a_param));
+                let a_body = a_body.substitute(&a_param, &name);
+                let b_body = b_body.substitute(&b_param, &name);
+                self.unify(&a_body, &b_body)
+            }
+            (a, b) => Err(UnifyError::Mismatch(a, b)),
+        }
+    }
+}
+
+// 外側で束縛された変数
+struct Binding {
+    id: Identifier,
+    ty: Type,
+
+    /// let 式で一般化した型変数
+    generic: HashSet<usize>,
+}
+
+impl Binding {
+    fn new(id: Identifier, ty: Type) -> Self {
+        Self {
+            id,
+            ty,
+            generic: HashSet::new(),
+        }
+    }
+}
+
+// 単一化に失敗した理由
+enum UnifyError {
+    /// 出現検査に失敗した型変数と型
+    Infinite(Type, Type),
+
+    /// 一致しなかった実際の型と期待される型
+    Mismatch(Type, Type),
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func;
+    use crate::parser::parse_expr;
+
+    fn infer(source: &str) -> Result<String, TypeError> {
+        infer_type(&Context::default(), &parse_expr(source).unwrap()).map(|ty| ty.to_string())
+    }
+
+    #[test]
+    fn test_infer_type() {
+        assert_eq!(infer("x => x"), Ok("a -> a".to_string()));
+        assert_eq!(infer("k"), Ok("a -> b -> a".to_string()));
+        assert_eq!(
+            infer("s"),
+            Ok("(a -> b -> c) -> (a -> b) -> a -> c".to_string())
+        );
+        assert_eq!(
+            infer("f => x => f(f(x))"),
+            Ok("(a -> a) -> a -> a".to_string())
+        );
+        assert_eq!(infer("2"), Ok("(a -> a) -> a -> a".to_string()));
+        assert_eq!(
+            infer("CONS"),
+            Ok("a -> b -> (a -> b -> c) -> c".to_string())
+        );
+    }
+
+    #[test]
+    fn test_let_polymorphism() {
+        // 定義済み関数は使うたびに別の型で具体化される
+        assert_eq!(infer("s(k, k)"), Ok("a -> a".to_string()));
+        assert_eq!(infer("k(i, i(:a))"), Ok("a -> a".to_string()));
+
+        // ラムダ抽象で束縛された変数は具体化されない
+        assert!(infer("(f => f(f))(i)").is_err());
+
+        // let 式で束縛された変数は具体化される
+        assert_eq!(infer("let f = x => x in f(f)"), Ok("a -> a".to_string()));
+        assert_eq!(
+            infer("y => let f = x => y in f(f)"),
+            Ok("a -> a".to_string())
+        );
+        assert_eq!(
+            infer("let k = x => y => x in k(k(:a), k)"),
+            Ok("a -> b".to_string())
+        );
+
+        // 外側の変数の型に現れる型変数は一般化しない
+        assert!(infer("y => let f = y in f(f)").is_err());
+    }
+
+    #[test]
+    fn test_letrec() {
+        // 値の中では単相的に扱う
+        assert_eq!(infer("letrec f = x => f(x) in f"), Ok("a -> b".to_string()));
+        assert_eq!(infer("letrec f = x => f(x) in f(:a)"), Ok("a".to_string()));
+
+        let error = infer("letrec f = f(f) in f").unwrap_err();
+        assert_eq!(error.path(), &[Direction::Value]);
+    }
+
+    #[test]
+    fn test_free_names() {
+        // 同じ名前のシンボルや未定義の変数は同じ型をもつ
+        assert_eq!(infer(":f(:x)"), Ok("a".to_string()));
+        assert!(infer(":f(:f)").is_err());
+        assert!(infer("x(x)").is_err());
+        assert_eq!(infer("f(x, y)"), Ok("a".to_string()));
+    }
+
+    #[test]
+    fn test_infinite() {
+        assert_eq!(
+            infer("y => x => x(x)"),
+            Err(TypeError::Infinite {
+                path: vec![Direction::Body, Direction::Body],
+                term: Box::new(parse_expr("x(x)").unwrap()),
+                var: Type::Var(0),
+                ty: Type::arrow(Type::Var(0), Type::Var(1)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_untypeable_func() {
+        let error = infer("Y(:f)").unwrap_err();
+        assert!(matches!(&error, TypeError::Func { id, .. } if id.as_str() == "Y"));
+        assert_eq!(error.path(), &[Direction::Lhs]);
+
+        let mut context = Context::default();
+        context
+            .def(func::new("LOOP", vec!["x"], parse_expr("LOOP(x)").unwrap()))
+            .unwrap();
+        let error = infer_type(&context, &parse_expr("i(LOOP)").unwrap()).unwrap_err();
+        assert_eq!(
+            error,
+            TypeError::Func {
+                path: vec![Direction::Rhs],
+                id: "LOOP".into(),
+                error: Box::new(TypeError::Recursive {
+                    path: vec![Direction::Body, Direction::Lhs],
+                    id: "LOOP".into(),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_annotation() {
+        assert_eq!(infer("(x: Nat) => x"), Ok("Nat -> Nat".to_string()));
+        assert_eq!(
+            infer("(f: Nat -> Nat, x) => f(x)"),
+            Ok("(Nat -> Nat) -> Nat -> Nat".to_string())
+        );
+        assert_eq!(infer("((x: Nat) => x)(:a)"), Ok("Nat".to_string()));
+
+        let error = infer("((x: Nat) => x)((y: Bool) => y)").unwrap_err();
+        assert_eq!(
+            error,
+            TypeError::Mismatch {
+                path: vec![],
+                term: Box::new(parse_expr("((x: Nat) => x)((y: Bool) => y)").unwrap()),
+                expected: Type::named("Nat"),
+                actual: Type::arrow(Type::named("Bool"), Type::named("Bool")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_system_f() {
+        assert_eq!(infer("<α>(x: α) => x"), Ok("∀α. α -> α".to_string()));
+        assert_eq!(
+            infer("<α, β>(x: α, y: β) => x"),
+            Ok("∀α. ∀β. α -> β -> α".to_string())
+        );
+        assert_eq!(infer("(<α>(x: α) => x)<Nat>"), Ok("Nat -> Nat".to_string()));
+        assert_eq!(
+            infer("(<α>(x: α) => x)<Nat -> Nat>((x: Nat) => x)"),
+            Ok("Nat -> Nat".to_string())
+        );
+
+        // 引数に全称型を要求する (ランク2)
+        assert_eq!(
+            infer("(f: ∀α. α -> α) => f<Nat>"),
+            Ok("(∀α. α -> α) -> Nat -> Nat".to_string())
+        );
+        assert_eq!(
+            infer("((f: ∀α. α -> α) => f<Nat>)(<β>(y: β) => y)"),
+            Ok("Nat -> Nat".to_string())
+        );
+
+        // 束縛された名前の異なる型が混ざらないよう、内側の名前を付け替える
+        assert_eq!(
+            infer("<α>(x: α) => <α>(y: α) => x"),
+            Ok("∀α. α -> ∀α'1. α'1 -> α".to_string())
+        );
+    }
+
+    #[test]
+    fn test_not_polymorphic() {
+        assert_eq!(
+            infer("((x: Nat) => x)<Nat>"),
+            Err(TypeError::NotPolymorphic {
+                path: vec![],
+                term: Box::new(parse_expr("(x: Nat) => x").unwrap()),
+                ty: Type::arrow(Type::named("Nat"), Type::named("Nat")),
+            })
+        );
+        assert!(infer("(<α>(x: α) => x)<Nat>(<β>(y: β) => y)").is_err());
+    }
+}
@@ -0,0 +1,7 @@
+mod error;
+mod infer;
+mod ty;
+
+pub use error::TypeError;
+pub use infer::infer_type;
+pub use ty::Type;
@@ -0,0 +1,210 @@
+use crate::expr::Identifier;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+/// 型
+///
+/// 推論で求まる型は型変数と関数型だけからなる。表示すると型変数は a, b, c, … と名付けられる
+/// 型注釈には名前の付いた型 (`Nat` や型抽象で束縛された `α`) と全称型も書ける
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Type {
+    /// 推論に用いる型変数
+    Var(usize),
+
+    /// 名前の付いた型
+    Named(Identifier),
+
+    /// 関数型
+    Arrow(Box<Type>, Box<Type>),
+
+    /// 全称型 (∀α. T)
+    Forall(Identifier, Box<Type>),
+}
+
+impl Type {
+    pub fn arrow(param: Type, result: Type) -> Type {
+        Type::Arrow(Box::new(param), Box::new(result))
+    }
+
+    pub fn forall<Id: Into<Identifier>>(param: Id, body: Type) -> Type {
+        Type::Forall(param.into(), Box::new(body))
+    }
+
+    pub fn named<Id: Into<Identifier>>(name: Id) -> Type {
+        Type::Named(name.into())
+    }
+
+    /// 自由な名前 id を ty で置き換える
+    ///
+    /// ty の中の名前が全称型に捕まらないよう、束縛された名前を付け替えることがある
+    pub fn substitute(&self, id: &Identifier, ty: &Type) -> Type {
+        match self {
+            Type::Var(_) => self.clone(),
+            Type::Named(name) if name == id => ty.clone(),
+            Type::Named(_) => self.clone(),
+            Type::Arrow(param, result) => {
+                Type::arrow(param.substitute(id, ty), result.substitute(id, ty))
+            }
+            Type::Forall(param, _) if param == id => self.clone(),
+            Type::Forall(param, body) => {
+                let names = ty.free_names();
+                if !names.contains(param) {
+                    return Type::forall(param.clone(), body.substitute(id, ty));
+                }
+
+                let body_names = body.free_names();
+                let mut fresh = param.clone();
+                let mut i = 0;
+                while names.contains(&fresh) || body_names.contains(&fresh) {
+                    i += 1;
+                    fresh = format!("{}{}", param.as_str(), i).into();
+                }
+                let body = body.substitute(param, &Type::Named(fresh.clone()));
+                Type::forall(fresh, body.substitute(id, ty))
+            }
+        }
+    }
+
+    /// 全称型に束縛されていない名前
+    pub fn free_names(&self) -> HashSet<Identifier> {
+        let mut names = HashSet::new();
+        self.collect_free_names(&mut Vec::new(), &mut names);
+        names
+    }
+
+    fn collect_free_names(&self, bound: &mut Vec<Identifier>, names: &mut HashSet<Identifier>) {
+        match self {
+            Type::Var(_) => {}
+            Type::Named(name) => {
+                if !bound.contains(name) {
+                    names.insert(name.clone());
+                }
+            }
+            Type::Arrow(param, result) => {
+                param.collect_free_names(bound, names);
+                result.collect_free_names(bound, names);
+            }
+            Type::Forall(param, body) => {
+                bound.push(param.clone());
+                body.collect_free_names(bound, names);
+                bound.pop();
+            }
+        }
+    }
+
+    // 型変数を現れる順に 0, 1, 2, … と付け直す
+    pub(crate) fn normalize(&self) -> Type {
+        Renamer::default().rename(self)
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Var(n) => write!(f, "{}", var_name(*n)),
+            Type::Named(name) => write!(f, "{}", name.as_str()),
+            Type::Arrow(param, result) => match param.as_ref() {
+                // 関数型は右結合で全称型は右端まで続くので、左側の関数型と全称型だけ括弧で囲む
+                Type::Arrow(..) | Type::Forall(..) => write!(f, "({}) -> {}", param, result),
+                Type::Var(_) | Type::Named(_) => write!(f, "{} -> {}", param, result),
+            },
+            Type::Forall(param, body) => write!(f, "∀{}. {}", param.as_str(), body),
+        }
+    }
+}
+
+// a, b, …, z, a1, b1, …
+fn var_name(n: usize) -> String {
+    let letter = (b'a' + (n % 26) as u8) as char;
+    match n / 26 {
+        0 => letter.to_string(),
+        suffix => format!("{}{}", letter, suffix),
+    }
+}
+
+/// 複数の型にまたがって型変数を現れる順に付け直す
+#[derive(Default)]
+pub(crate) struct Renamer(HashMap<usize, usize>);
+
+impl Renamer {
+    pub(crate) fn rename(&mut self, ty: &Type) -> Type {
+        match ty {
+            Type::Var(n) => {
+                let next = self.0.len();
+                Type::Var(*self.0.entry(*n).or_insert(next))
+            }
+            Type::Named(_) => ty.clone(),
+            Type::Arrow(param, result) => {
+                let param = self.rename(param);
+                Type::arrow(param, self.rename(result))
+            }
+            Type::Forall(param, body) => Type::forall(param.clone(), self.rename(body)),
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let a = || Type::Var(0);
+        let b = || Type::Var(1);
+
+        assert_eq!(
+            Type::arrow(a(), Type::arrow(b(), a())).to_string(),
+            "a -> b -> a"
+        );
+        assert_eq!(
+            Type::arrow(Type::arrow(a(), b()), Type::arrow(a(), b())).to_string(),
+            "(a -> b) -> a -> b"
+        );
+        assert_eq!(Type::Var(27).to_string(), "b1");
+
+        let nat = || Type::named("Nat");
+        assert_eq!(Type::arrow(nat(), nat()).to_string(), "Nat -> Nat");
+        assert_eq!(
+            Type::forall("α", Type::arrow(Type::named("α"), a())).to_string(),
+            "∀α. α -> a"
+        );
+        assert_eq!(
+            Type::arrow(Type::forall("α", Type::named("α")), nat()).to_string(),
+            "(∀α. α) -> Nat"
+        );
+    }
+
+    #[test]
+    fn test_substitute() {
+        let alpha = || Type::named("α");
+        let beta = || Type::named("β");
+
+        // (α -> β)[α := Nat] = Nat -> β
+        assert_eq!(
+            Type::arrow(alpha(), beta()).substitute(&"α".into(), &Type::named("Nat")),
+            Type::arrow(Type::named("Nat"), beta())
+        );
+
+        // (∀α. α)[α := Nat] = ∀α. α
+        let ty = Type::forall("α", alpha());
+        assert_eq!(ty.substitute(&"α".into(), &Type::named("Nat")), ty);
+
+        // (∀β. α -> β)[α := β] = ∀β1. β -> β1
+        assert_eq!(
+            Type::forall("β", Type::arrow(alpha(), beta())).substitute(&"α".into(), &beta()),
+            Type::forall("β1", Type::arrow(beta(), Type::named("β1")))
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let ty = Type::arrow(Type::Var(5), Type::arrow(Type::Var(2), Type::Var(5)));
+        assert_eq!(
+            ty.normalize(),
+            Type::arrow(Type::Var(0), Type::arrow(Type::Var(1), Type::Var(0)))
+        );
+    }
+}